@@ -34,11 +34,11 @@ async fn main() {
 
   loop {
     // Draw text
-    let text1 = fonts.draw_text("Press \"", 20.0, 0.0, 69, Color::from([0.9; 4]));
-    let text2 = fonts.draw_text("r", 20.0 + text1.width, 0.0, 69, Color::from([0.9, 0.2, 0.9, 1.0]));
-    fonts.draw_text("\" to toggle fonts", 20.0 + text1.width + text2.width, 0.0, 69, Color::from([0.9; 4]));
+    let text1 = fonts.draw_text("Press \"", 20.0, 0.0, 69.0, Color::from([0.9; 4]));
+    let text2 = fonts.draw_text("r", 20.0 + text1.width, 0.0, 69.0, Color::from([0.9, 0.2, 0.9, 1.0]));
+    fonts.draw_text("\" to toggle fonts", 20.0 + text1.width + text2.width, 0.0, 69.0, Color::from([0.9; 4]));
 
-    fonts.draw_text("Nice 良い", 20.0, 40.0 + text2.height, 169, Color::from([1.0; 4]));
+    fonts.draw_text("Nice 良い", 20.0, 40.0 + text2.height, 169.0, Color::from([1.0; 4]));
 
     if is_key_released(KeyCode::R) {
       if toggle {
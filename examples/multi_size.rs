@@ -33,13 +33,13 @@ async fn main() {
 
     while i <= screen_width() as u16 * 2 {
       // Draw text
-      fonts.draw_text_ex(&TextParams {
-        text: "a",
+      fonts.draw_text_ex("a", &TextParams {
         x: prev,
         y: screen_height(),
-        size: i,
+        size: i as f32,
         color: Color::from([1.0; 4]),
-        draw: DrawFrom::BottomLeft
+        draw: DrawFrom::BottomLeft,
+        ..Default::default()
       });
 
       prev = i as f32;
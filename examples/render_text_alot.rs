@@ -1,6 +1,6 @@
 use macroquad::prelude::*;
 
-use macroquad_text::Fonts;
+use macroquad_text::{Fonts, HorizontalAlign, TextParams};
 
 // Include Fonts
 const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
@@ -44,9 +44,14 @@ async fn _main() {
     clear_background(BLACK);
 
     // Draws a bunch of characters
-    for (i, line) in chars.iter().enumerate() {
-      fonts.draw_text(line, 0.0, 24.0 * i as f32, 18, Color::from([1.0; 4]));
-    }
+    fonts.draw_lines(
+      &chars,
+      &TextParams {
+        size: 18.0,
+        ..Default::default()
+      },
+      HorizontalAlign::Left,
+    );
 
     next_frame().await;
   }
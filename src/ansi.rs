@@ -0,0 +1,228 @@
+use macroquad::prelude::Color;
+
+use crate::{ColoredStr, Component, FontStyle, FontWeight};
+
+/// The 16 basic terminal colors, the same as xterm uses
+const BASIC: [[u8; 3]; 16] = [
+  [0, 0, 0],
+  [205, 0, 0],
+  [0, 205, 0],
+  [205, 205, 0],
+  [0, 0, 238],
+  [205, 0, 205],
+  [0, 205, 205],
+  [229, 229, 229],
+  [127, 127, 127],
+  [255, 0, 0],
+  [0, 255, 0],
+  [255, 255, 0],
+  [92, 92, 255],
+  [255, 0, 255],
+  [0, 255, 255],
+  [255, 255, 255],
+];
+
+impl<'a> ColoredStr<'a> {
+  /// Parses text with ANSI escape sequences into a [ColoredStr]
+  ///
+  /// SGR sequences (`ESC[...m`) set the foreground color
+  /// with the 16 basic colors, the 256 color palette or true colors,
+  /// and bold, italic and underline, background colors are ignored,
+  /// every other escape sequence is removed from the text
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = ColoredStr::from_ansi("\x1b[1;31merror\x1b[0m: file not found");
+  ///
+  /// fonts.draw_colored_text_ex(&text, &TextParams::default());
+  /// ```
+  pub fn from_ansi(ansi: &'a str) -> Self {
+    let mut text = ColoredStr::new();
+    let mut rest = ansi;
+
+    while let Some(start) = rest.find('\x1b') {
+      if start > 0 {
+        text.push(Component::Str(&rest[..start]));
+      }
+
+      rest = &rest[start + 1..];
+
+      // only CSI sequences ("ESC[") are parsed, other escapes just lose the ESC
+      let Some(csi) = rest.strip_prefix('[') else {
+        continue;
+      };
+
+      // parameters and intermediate bytes are in 0x20..=0x3F, then a final byte
+      let Some(end) = csi.find(|c: char| !(' '..='?').contains(&c)) else {
+        rest = "";
+        break;
+      };
+
+      if csi[end..].starts_with('m') {
+        text.extend(Self::sgr(&csi[..end]));
+      }
+
+      rest = &csi[end + 1..];
+    }
+
+    if !rest.is_empty() {
+      text.push(Component::Str(rest));
+    }
+
+    text
+  }
+
+  /// Turns the parameters of an SGR sequence into components
+  fn sgr(params: &str) -> Vec<Component<'a>> {
+    let mut codes = params
+      .split([';', ':'])
+      .map(|it| it.parse::<u8>().unwrap_or(0));
+    let mut components = Vec::new();
+
+    while let Some(code) = codes.next() {
+      let component = match code {
+        0 => {
+          components.extend([
+            Component::Weight(FontWeight::Regular),
+            Component::Style(FontStyle::Normal),
+            Component::Underline(false),
+          ]);
+          Component::ResetColor
+        }
+        1 => Component::Weight(FontWeight::Bold),
+        22 => Component::Weight(FontWeight::Regular),
+        3 => Component::Style(FontStyle::Italic),
+        23 => Component::Style(FontStyle::Normal),
+        4 => Component::Underline(true),
+        24 => Component::Underline(false),
+        30..=37 => Component::Color(palette(code - 30)),
+        90..=97 => Component::Color(palette(code - 90 + 8)),
+        39 => Component::ResetColor,
+        38 => match extended_color(&mut codes) {
+          Some(color) => Component::Color(color),
+          None => continue,
+        },
+        // the color of a background still has to be skipped
+        48 => {
+          extended_color(&mut codes);
+          continue;
+        }
+        _ => continue,
+      };
+
+      components.push(component);
+    }
+
+    components
+  }
+}
+
+/// Reads the rest of a `38;5;n` or `38;2;r;g;b` color
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+  match codes.next()? {
+    5 => Some(palette(codes.next()?)),
+    2 => Some(Color::from_rgba(
+      codes.next()?,
+      codes.next()?,
+      codes.next()?,
+      255,
+    )),
+    _ => None,
+  }
+}
+
+/// Returns a color of the 256 color palette
+fn palette(index: u8) -> Color {
+  let [r, g, b] = match index {
+    0..=15 => BASIC[index as usize],
+    16..=231 => {
+      let level = |it: u8| if it == 0 { 0 } else { 55 + it * 40 };
+      let index = index - 16;
+
+      [level(index / 36), level(index / 6 % 6), level(index % 6)]
+    }
+    _ => [8 + (index - 232) * 10; 3],
+  };
+
+  Color::from_rgba(r, g, b, 255)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn components(ansi: &str) -> Vec<Component<'_>> {
+    ColoredStr::from_ansi(ansi).components().to_vec()
+  }
+
+  fn rgb(r: u8, g: u8, b: u8) -> Component<'static> {
+    Component::Color(Color::from_rgba(r, g, b, 255))
+  }
+
+  #[test]
+  fn reset_resets_everything() {
+    assert_eq!(
+      components("\x1b[1;3;4;31mx\x1b[0my\x1b[mz"),
+      [
+        Component::Weight(FontWeight::Bold),
+        Component::Style(FontStyle::Italic),
+        Component::Underline(true),
+        rgb(205, 0, 0),
+        Component::Str("x"),
+        Component::Weight(FontWeight::Regular),
+        Component::Style(FontStyle::Normal),
+        Component::Underline(false),
+        Component::ResetColor,
+        Component::Str("y"),
+        Component::Weight(FontWeight::Regular),
+        Component::Style(FontStyle::Normal),
+        Component::Underline(false),
+        Component::ResetColor,
+        Component::Str("z"),
+      ]
+    );
+  }
+
+  #[test]
+  fn basic_and_bright_colors() {
+    assert_eq!(
+      components("\x1b[34ma\x1b[94mb\x1b[39mc"),
+      [
+        rgb(0, 0, 238),
+        Component::Str("a"),
+        rgb(92, 92, 255),
+        Component::Str("b"),
+        Component::ResetColor,
+        Component::Str("c"),
+      ]
+    );
+  }
+
+  #[test]
+  fn palette_and_true_colors() {
+    assert_eq!(components("\x1b[38;5;9m"), [rgb(255, 0, 0)]);
+    assert_eq!(components("\x1b[38;5;16m"), [rgb(0, 0, 0)]);
+    assert_eq!(components("\x1b[38;5;196m"), [rgb(255, 0, 0)]);
+    assert_eq!(components("\x1b[38;5;110m"), [rgb(135, 175, 215)]);
+    assert_eq!(components("\x1b[38;5;232m"), [rgb(8, 8, 8)]);
+    assert_eq!(components("\x1b[38;5;255m"), [rgb(238, 238, 238)]);
+    assert_eq!(components("\x1b[38:2:1:2:3m"), [rgb(1, 2, 3)]);
+  }
+
+  #[test]
+  fn backgrounds_are_skipped() {
+    assert_eq!(
+      components("\x1b[48;5;196;1;41mx"),
+      [Component::Weight(FontWeight::Bold), Component::Str("x")]
+    );
+    assert_eq!(components("\x1b[48;2;1;2;3;32m"), [rgb(0, 205, 0)]);
+  }
+
+  #[test]
+  fn other_escapes_are_removed() {
+    assert_eq!(ColoredStr::from_ansi("a\x1b[2Kb\x1b[1;1Hc").text(), "abc");
+    assert_eq!(ColoredStr::from_ansi("a\x1b(Bb").text(), "a(Bb");
+    assert_eq!(ColoredStr::from_ansi("a\x1b[31").text(), "a");
+    assert_eq!(ColoredStr::from_ansi("[31m").text(), "[31m");
+  }
+}
@@ -4,10 +4,65 @@
 
 #![allow(dead_code)]
 
-use std::collections::HashMap;
+use std::{
+  collections::HashMap,
+  sync::atomic::{AtomicU64, Ordering},
+};
 
 use macroquad::prelude::{Color, FilterMode, Image, Rect, Texture2D};
 
+/// How big the atlas glyphs of a font are cached in starts and how it grows
+///
+/// **Example**
+/// ```rs
+/// // the game caches thousands of CJK glyphs while loading
+/// fonts.set_atlas_config(AtlasConfig {
+///   initial_size: 4096,
+///   growth_factor: 2.0,
+///   max_size: 8192,
+///   // titles don't take up space in the atlas of the CJK glyphs
+///   large_glyph_size: Some(96),
+/// });
+/// ```
+///
+/// **See** [Fonts::set_atlas_config](crate::Fonts::set_atlas_config)
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AtlasConfig {
+  /// The width and height of the atlas in pixels before it grows
+  ///
+  /// **Default** `8192`
+  pub initial_size: u16,
+  /// What the width and height are multiplied by every time the atlas is full
+  ///
+  /// **Default** `2.0`
+  pub growth_factor: f32,
+  /// The atlas never grows past this width and height, glyphs that don't fit anymore
+  /// aren't drawn until the cache is trimmed or cleared
+  ///
+  /// **Default** [u16::MAX], which doesn't limit growth
+  pub max_size: u16,
+  /// Glyphs rasterized at this size or bigger are cached in a separate atlas,
+  /// so a few huge title glyphs don't grow the atlas thousands of small glyphs are packed in,
+  /// both atlases use the same config, the atlas of large glyphs is only made
+  /// once the first large glyph gets cached
+  ///
+  /// **Default** `None`, every glyph is cached in the same atlas
+  pub large_glyph_size: Option<u16>,
+}
+
+impl Default for AtlasConfig {
+  fn default() -> Self {
+    Self {
+      initial_size: 8192,
+      growth_factor: 2.0,
+      max_size: u16::MAX,
+      large_glyph_size: None,
+    }
+  }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Sprite {
   pub rect: Rect,
@@ -18,12 +73,115 @@ pub struct Atlas {
   pub sprites: HashMap<u64, Sprite>,
   pub dirty: bool,
   filter: FilterMode,
-  texture: Texture2D,
+  config: AtlasConfig,
+  /// Created the first time the atlas gets drawn,
+  /// so glyphs can be cached and measured without a window
+  texture: Option<Texture2D>,
   image: Image,
   max_line_height: u16,
   cursor_x: u16,
   cursor_y: u16,
   unique_id: u64,
+  /// Changes every time sprites move around in the atlas,
+  /// unique across all atlases
+  pub generation: u64,
+}
+
+/// The atlases the glyphs of a font are cached in, split by [AtlasConfig::large_glyph_size],
+/// sprite keys contain the index of the atlas they're in
+#[derive(Debug)]
+pub struct Atlases {
+  /// The atlas of large glyphs is only here after the first large glyph got cached
+  atlases: Vec<Atlas>,
+  filter: FilterMode,
+  config: AtlasConfig,
+}
+
+impl Atlases {
+  // the bits of a key above this are the index of the atlas
+  const INDEX_SHIFT: u32 = 48;
+
+  pub fn new(filter: FilterMode, config: AtlasConfig) -> Self {
+    Self {
+      atlases: vec![Atlas::new(filter, config)],
+      filter,
+      config,
+    }
+  }
+
+  /// Returns the index of the atlas a sprite key belongs to
+  pub fn index_of(key: u64) -> usize {
+    (key >> Self::INDEX_SHIFT) as usize
+  }
+
+  /// Returns the atlas a sprite key belongs to
+  pub fn atlas(&self, key: u64) -> &Atlas {
+    &self.atlases[Self::index_of(key)]
+  }
+
+  /// Returns the atlas a sprite key belongs to
+  pub fn atlas_mut(&mut self, key: u64) -> &mut Atlas {
+    &mut self.atlases[Self::index_of(key)]
+  }
+
+  /// Returns the atlas at an index
+  pub fn nth(&self, index: usize) -> Option<&Atlas> {
+    self.atlases.get(index)
+  }
+
+  /// Returns the atlas at an index
+  pub fn nth_mut(&mut self, index: usize) -> Option<&mut Atlas> {
+    self.atlases.get_mut(index)
+  }
+
+  pub fn len(&self) -> usize {
+    self.atlases.len()
+  }
+
+  /// Returns every atlas, glyphs smaller than [AtlasConfig::large_glyph_size] are in the first
+  pub fn iter(&self) -> impl Iterator<Item = &Atlas> {
+    self.atlases.iter()
+  }
+
+  pub fn get(&self, key: u64) -> Option<Sprite> {
+    self.atlases.get(Self::index_of(key))?.get(key)
+  }
+
+  /// Packs the sprite of a glyph rasterized at a given size into the atlas for that size,
+  /// returns its key, `None` if it doesn't fit since the atlas can't grow anymore
+  pub fn cache_sprite(&mut self, size: u16, sprite: Image) -> Option<u64> {
+    let index = self.config.large_glyph_size.is_some_and(|it| size >= it) as usize;
+
+    if index == self.atlases.len() {
+      self.atlases.push(Atlas::new(self.filter, self.config));
+    }
+
+    let atlas = &mut self.atlases[index];
+    let key = (index as u64) << Self::INDEX_SHIFT | atlas.new_unique_id();
+
+    atlas.cache_sprite(key, sprite).then_some(key)
+  }
+
+  /// Removes every sprite, the atlas goes back to its initial size
+  /// and the atlas of large glyphs is dropped until the next large glyph
+  pub fn clear(&mut self) {
+    self.atlases.truncate(1);
+    self.atlases[0].clear();
+  }
+
+  /// Only keeps the sprites `keep` returns true for
+  pub fn retain(&mut self, mut keep: impl FnMut(u64) -> bool) {
+    for atlas in &mut self.atlases {
+      atlas.retain(&mut keep);
+    }
+  }
+}
+
+/// Returns a generation no atlas had before
+fn next_generation() -> u64 {
+  static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+  GENERATION.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Atlas {
@@ -32,20 +190,22 @@ impl Atlas {
   // well..
   const UNIQUENESS_OFFSET: u64 = 100000;
 
-  pub fn new(filter: FilterMode) -> Atlas {
-    let image = Image::gen_image_color(8192, 8192, Color::new(0.0, 0.0, 0.0, 0.0));
-    let texture = Texture2D::from_rgba8(image.width, image.height, &image.bytes);
+  pub fn new(filter: FilterMode, config: AtlasConfig) -> Atlas {
+    let size = config.initial_size.clamp(1, config.max_size.max(1));
+    let image = Image::gen_image_color(size, size, Color::new(0.0, 0.0, 0.0, 0.0));
 
     Atlas {
       image,
-      texture,
+      texture: None,
       filter,
+      config,
       cursor_x: 0,
       cursor_y: 0,
       dirty: false,
       max_line_height: 0,
       sprites: HashMap::new(),
       unique_id: Self::UNIQUENESS_OFFSET,
+      generation: next_generation(),
     }
   }
 
@@ -66,27 +226,84 @@ impl Atlas {
     self.image.height
   }
 
+  /// The image sprites are packed into, the texture is a copy of it
+  pub fn image(&self) -> &Image {
+    &self.image
+  }
+
+  /// Returns the amount of pixels taken up by sprites, without the gaps between them
+  pub fn used_area(&self) -> u64 {
+    self
+      .sprites
+      .values()
+      .map(|it| it.rect.w as u64 * it.rect.h as u64)
+      .sum()
+  }
+
   pub fn texture(&mut self) -> &Texture2D {
+    let (width, height) = (self.image.width, self.image.height);
+    let outdated = self
+      .texture
+      .as_ref()
+      .is_none_or(|it| it.width() != width as f32 || it.height() != height as f32);
+
+    if outdated {
+      let texture = Texture2D::from_rgba8(width, height, &self.image.bytes[..]);
+      texture.set_filter(self.filter);
+
+      self.dirty = false;
+      self.texture = Some(texture);
+    }
+
+    let texture = self.texture.as_ref().unwrap();
+
     if self.dirty {
       self.dirty = false;
-      if self.texture.width() != self.image.width as _
-        || self.texture.height() != self.image.height as _
-      {
-        self.texture =
-          Texture2D::from_rgba8(self.image.width, self.image.height, &self.image.bytes[..]);
-        self.texture.set_filter(self.filter);
-      }
+      texture.update(&self.image);
+    }
 
-      self.texture.update(&self.image);
+    texture
+  }
+
+  /// Removes every sprite, the atlas goes back to its initial size
+  pub fn clear(&mut self) {
+    self.retain(|_| false);
+  }
+
+  /// Only keeps the sprites `keep` returns true for and packs them again,
+  /// so the space of removed sprites can be used again
+  pub fn retain(&mut self, mut keep: impl FnMut(u64) -> bool) {
+    let sprites = self
+      .sprites
+      .drain()
+      .filter(|(key, _)| keep(*key))
+      .map(|(key, sprite)| (key, self.image.sub_image(sprite.rect)))
+      .collect::<Vec<_>>();
+
+    *self = Atlas {
+      unique_id: self.unique_id,
+      ..Atlas::new(self.filter, self.config)
+    };
+    self.dirty = true;
+
+    // sprites that were already cached are never dropped, even if they're packed worse now
+    for (key, image) in sprites {
+      self.pack_sprite(key, image, u16::MAX);
     }
+  }
+
+  /// Returns the width and height the atlas grows to, `None` if it can't grow past `limit`
+  fn grown_size(&self, limit: u16) -> Option<u16> {
+    let size = self.image.width;
+    let grown = (size as f32 * self.config.growth_factor).ceil() as u32;
 
-    &self.texture
+    (size < limit).then(|| grown.clamp(size as u32 + 1, limit as u32) as u16)
   }
 
   pub fn get_uv_rect(&self, key: u64) -> Option<Rect> {
     self.get(key).map(|sprite| {
-      let w = self.texture.width();
-      let h = self.texture.height();
+      let w = self.image.width as f32;
+      let h = self.image.height as f32;
 
       Rect::new(
         sprite.rect.x / w,
@@ -97,10 +314,17 @@ impl Atlas {
     })
   }
 
-  pub fn cache_sprite(&mut self, key: u64, sprite: Image) {
+  /// Packs a sprite into the atlas, growing it if needed,
+  /// returns false if the sprite doesn't fit since the atlas can't grow anymore
+  pub fn cache_sprite(&mut self, key: u64, sprite: Image) -> bool {
+    self.pack_sprite(key, sprite, self.config.max_size)
+  }
+
+  fn pack_sprite(&mut self, key: u64, sprite: Image, limit: u16) -> bool {
     let (width, height) = (sprite.width as usize, sprite.height as usize);
+    let cursor = (self.cursor_x, self.cursor_y, self.max_line_height);
 
-    let x = if self.cursor_x + (width as u16) < self.image.width {
+    let x = if self.cursor_x + Self::GAP + width as u16 <= self.image.width {
       if height as u16 > self.max_line_height {
         self.max_line_height = height as u16;
       }
@@ -116,7 +340,14 @@ impl Atlas {
     let y = self.cursor_y;
 
     // texture bounds exceeded
-    if self.cursor_y + height as u16 > self.image.height {
+    if self.cursor_y as usize + height > self.image.height as usize
+      || width + Self::GAP as usize > self.image.width as usize
+    {
+      let Some(size) = self.grown_size(limit) else {
+        (self.cursor_x, self.cursor_y, self.max_line_height) = cursor;
+        return false;
+      };
+
       // reset glyph cache state
       let sprites = self.sprites.drain().collect::<Vec<_>>();
       self.cursor_x = 0;
@@ -124,22 +355,19 @@ impl Atlas {
       self.max_line_height = 0;
 
       let old_image = self.image.clone();
+      self.generation = next_generation();
 
       // increase font texture size
-      self.image = Image::gen_image_color(
-        self.image.width * 2,
-        self.image.height * 2,
-        Color::new(0.0, 0.0, 0.0, 0.0),
-      );
+      self.image = Image::gen_image_color(size, size, Color::new(0.0, 0.0, 0.0, 0.0));
 
-      // recache all previously cached symbols
+      // recache all previously cached symbols, they fit since the atlas only got bigger
       for (key, sprite) in sprites {
         let image = old_image.sub_image(sprite.rect);
-        self.cache_sprite(key, image);
+        self.pack_sprite(key, image, u16::MAX);
       }
 
       // cache the new sprite
-      self.cache_sprite(key, sprite);
+      self.pack_sprite(key, sprite, limit)
     } else {
       self.dirty = true;
 
@@ -159,12 +387,159 @@ impl Atlas {
           rect: Rect::new(x as f32, y as f32, width as f32, height as f32),
         },
       );
+
+      true
     }
   }
 }
 
 impl Default for Atlas {
   fn default() -> Self {
-    Atlas::new(FilterMode::Linear)
+    Atlas::new(FilterMode::Linear, AtlasConfig::default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sprite(size: u16) -> Image {
+    Image::gen_image_color(size, size, Color::new(1.0, 1.0, 1.0, 1.0))
+  }
+
+  fn config(initial_size: u16, growth_factor: f32, max_size: u16) -> AtlasConfig {
+    AtlasConfig {
+      initial_size,
+      growth_factor,
+      max_size,
+      large_glyph_size: None,
+    }
+  }
+
+  #[test]
+  fn starts_at_initial_size() {
+    let atlas = Atlas::new(FilterMode::Linear, config(64, 2.0, u16::MAX));
+
+    assert_eq!((atlas.width(), atlas.height()), (64, 64));
+    assert_eq!(Atlas::default().width(), 8192);
+  }
+
+  #[test]
+  fn packs_sprites_in_rows() {
+    let mut atlas = Atlas::new(FilterMode::Linear, config(32, 2.0, u16::MAX));
+
+    for key in 0..3 {
+      assert!(atlas.cache_sprite(key, sprite(10)));
+    }
+
+    let rects = (0..3)
+      .map(|key| atlas.get(key).unwrap().rect)
+      .collect::<Vec<_>>();
+
+    assert_eq!(rects[0], Rect::new(2.0, 0.0, 10.0, 10.0));
+    assert_eq!(rects[1], Rect::new(16.0, 0.0, 10.0, 10.0));
+    assert_eq!(rects[2], Rect::new(2.0, 14.0, 10.0, 10.0));
+    assert_eq!(atlas.width(), 32);
+    assert_eq!(atlas.used_area(), 300);
+  }
+
+  #[test]
+  fn grows_by_growth_factor() {
+    let mut atlas = Atlas::new(FilterMode::Linear, config(16, 1.5, u16::MAX));
+    let generation = atlas.generation;
+
+    assert!(atlas.cache_sprite(0, sprite(10)));
+    assert!(atlas.cache_sprite(1, sprite(10)));
+
+    assert_eq!((atlas.width(), atlas.height()), (24, 24));
+    assert_ne!(atlas.generation, generation);
+    // sprites cached before growing are kept
+    assert!(atlas.get(0).is_some());
+    assert!(atlas.get(1).is_some());
+  }
+
+  #[test]
+  fn never_grows_past_max_size() {
+    let mut atlas = Atlas::new(FilterMode::Linear, config(16, 2.0, 24));
+
+    assert!(atlas.cache_sprite(0, sprite(10)));
+    assert!(atlas.cache_sprite(1, sprite(10)));
+    assert_eq!(atlas.width(), 24);
+    assert!(!atlas.cache_sprite(2, sprite(20)));
+    assert_eq!(atlas.width(), 24);
+    assert!(atlas.get(2).is_none());
+    // a sprite that doesn't fit leaves room for the ones that do
+    assert!(atlas.cache_sprite(3, sprite(4)));
+  }
+
+  #[test]
+  fn initial_size_is_clamped_to_max_size() {
+    let atlas = Atlas::new(FilterMode::Linear, config(64, 2.0, 32));
+
+    assert_eq!(atlas.width(), 32);
+  }
+
+  #[test]
+  fn clear_goes_back_to_initial_size() {
+    let mut atlas = Atlas::new(FilterMode::Linear, config(16, 2.0, u16::MAX));
+
+    atlas.cache_sprite(0, sprite(10));
+    atlas.cache_sprite(1, sprite(10));
+    atlas.clear();
+
+    assert_eq!(atlas.width(), 16);
+    assert!(atlas.sprites.is_empty());
+  }
+
+  #[test]
+  fn retain_repacks_kept_sprites() {
+    let mut atlas = Atlas::new(FilterMode::Linear, config(32, 2.0, u16::MAX));
+
+    for key in 0..3 {
+      atlas.cache_sprite(key, sprite(10));
+    }
+
+    atlas.retain(|key| key == 2);
+
+    assert_eq!(atlas.sprites.len(), 1);
+    assert_eq!(atlas.get(2).unwrap().rect, Rect::new(2.0, 0.0, 10.0, 10.0));
+  }
+
+  #[test]
+  fn large_glyphs_get_their_own_atlas() {
+    let mut atlases = Atlases::new(
+      FilterMode::Linear,
+      AtlasConfig {
+        large_glyph_size: Some(48),
+        ..config(32, 2.0, u16::MAX)
+      },
+    );
+
+    let small = atlases.cache_sprite(47, sprite(10)).unwrap();
+
+    // the atlas of large glyphs isn't made until it's needed
+    assert_eq!(atlases.len(), 1);
+
+    let large = atlases.cache_sprite(48, sprite(20)).unwrap();
+
+    assert_eq!(atlases.len(), 2);
+    assert_eq!(Atlases::index_of(small), 0);
+    assert_eq!(Atlases::index_of(large), 1);
+    assert_eq!(atlases.get(large).unwrap().rect.w, 20.0);
+    assert_eq!(atlases.nth(0).unwrap().sprites.len(), 1);
+    assert_eq!(atlases.nth(1).unwrap().sprites.len(), 1);
+
+    atlases.clear();
+
+    assert_eq!(atlases.len(), 1);
+  }
+
+  #[test]
+  fn one_atlas_without_large_glyph_size() {
+    let mut atlases = Atlases::new(FilterMode::Linear, config(32, 2.0, u16::MAX));
+    let key = atlases.cache_sprite(200, sprite(10)).unwrap();
+
+    assert_eq!(atlases.len(), 1);
+    assert_eq!(Atlases::index_of(key), 0);
   }
 }
@@ -0,0 +1,206 @@
+use macroquad::prelude::Color;
+
+use crate::{markup::parse_color, ColoredStr, Component, FontStyle, FontWeight};
+
+/// Open tags while parsing BBCode, used to know what closing a tag switches back to
+#[derive(Default)]
+struct OpenTags {
+  colors: Vec<Color>,
+  bold: usize,
+  italic: usize,
+  underline: usize,
+}
+
+impl OpenTags {
+  /// Opens or closes a tag, returns `None` if the tag isn't known
+  /// and `Some(None)` if the tag doesn't change anything
+  fn apply<'a>(&mut self, tag: &str) -> Option<Option<Component<'a>>> {
+    let (closing, tag) = match tag.strip_prefix('/') {
+      Some(tag) => (true, tag),
+      None => (false, tag),
+    };
+    let (name, value) = match tag.split_once('=') {
+      Some((name, value)) => (name, Some(value)),
+      None => (tag, None),
+    };
+
+    let component = match (name.to_ascii_lowercase().as_str(), value, closing) {
+      ("b", None, false) => Self::open(&mut self.bold, Component::Weight(FontWeight::Bold)),
+      ("b", None, true) => Self::close(&mut self.bold, Component::ResetWeight),
+      ("i", None, false) => Self::open(&mut self.italic, Component::Style(FontStyle::Italic)),
+      ("i", None, true) => Self::close(&mut self.italic, Component::ResetStyle),
+      ("u", None, false) => Self::open(&mut self.underline, Component::Underline(true)),
+      ("u", None, true) => Self::close(&mut self.underline, Component::Underline(false)),
+      ("color", Some(value), false) => {
+        let color = parse_color(value)?;
+
+        self.colors.push(color);
+        Some(Component::Color(color))
+      }
+      ("color", None, true) => self.colors.pop().map(|_| {
+        self
+          .colors
+          .last()
+          .map_or(Component::ResetColor, |it| Component::Color(*it))
+      }),
+      _ => return None,
+    };
+
+    Some(component)
+  }
+
+  /// Opens a tag that can be nested, only the outermost one changes anything
+  fn open<'a>(depth: &mut usize, component: Component<'a>) -> Option<Component<'a>> {
+    *depth += 1;
+
+    (*depth == 1).then_some(component)
+  }
+
+  /// Closes a tag that can be nested, closing tags that were never opened are ignored
+  fn close<'a>(depth: &mut usize, component: Component<'a>) -> Option<Component<'a>> {
+    *depth = depth.checked_sub(1)?;
+
+    (*depth == 0).then_some(component)
+  }
+}
+
+impl<'a> ColoredStr<'a> {
+  /// Parses BBCode into a [ColoredStr], supports `[b]`, `[i]`, `[u]` and `[color=VALUE]`,
+  /// color values are the same as in [Self::from_markup]
+  ///
+  /// Unknown tags are kept as text, closing tags that were never opened are ignored
+  /// and tags that are never closed last until the end of the text,
+  /// `[/b]` and `[/i]` switch back to the weight and style of the [TextParams](crate::TextParams)
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = ColoredStr::from_bbcode("[b]Warning:[/b] the [color=red]bridge[/color] is out");
+  ///
+  /// fonts.draw_colored_text_ex(&text, &TextParams::default());
+  /// ```
+  pub fn from_bbcode(bbcode: &'a str) -> Self {
+    let mut text = ColoredStr::new();
+    let mut tags = OpenTags::default();
+    let mut rest = bbcode;
+
+    while let Some(start) = rest.find('[') {
+      let Some(end) = rest[start..].find(']').map(|it| start + it) else {
+        break;
+      };
+
+      // unknown tags stay part of the text, only skip past the '['
+      let Some(component) = tags.apply(&rest[start + 1..end]) else {
+        text.push(Component::Str(&rest[..=start]));
+        rest = &rest[start + 1..];
+        continue;
+      };
+
+      if start > 0 {
+        text.push(Component::Str(&rest[..start]));
+      }
+
+      text.extend(component);
+      rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+      text.push(Component::Str(rest));
+    }
+
+    text
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::color::colors::{BLUE, RED};
+
+  use crate::TextParams;
+
+  use super::*;
+
+  fn components(bbcode: &str) -> Vec<Component<'_>> {
+    ColoredStr::from_bbcode(bbcode).components().to_vec()
+  }
+
+  #[test]
+  fn nested_tags_only_change_on_the_outermost() {
+    assert_eq!(
+      components("[b]a[B]b[/b]c[/B]d"),
+      [
+        Component::Weight(FontWeight::Bold),
+        Component::Str("a"),
+        Component::Str("b"),
+        Component::Str("c"),
+        Component::ResetWeight,
+        Component::Str("d"),
+      ]
+    );
+  }
+
+  #[test]
+  fn closing_tags_restore_the_surrounding_face() {
+    let params = TextParams {
+      weight: FontWeight::Bold,
+      style: FontStyle::Italic,
+      ..Default::default()
+    };
+    let spans = ColoredStr::from_bbcode("[b][i]a[/i][/b]b").spans(&params);
+
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].text, "ab");
+    assert_eq!(spans[0].weight, FontWeight::Bold);
+    assert_eq!(spans[0].style, FontStyle::Italic);
+  }
+
+  #[test]
+  fn nested_colors_restore_the_outer_color() {
+    assert_eq!(
+      components("[color=red]a[color=blue]b[/color]c[/color]"),
+      [
+        Component::Color(RED),
+        Component::Str("a"),
+        Component::Color(BLUE),
+        Component::Str("b"),
+        Component::Color(RED),
+        Component::Str("c"),
+        Component::ResetColor,
+      ]
+    );
+  }
+
+  #[test]
+  fn unclosed_tags_last_until_the_end() {
+    assert_eq!(
+      components("[i][u]text"),
+      [
+        Component::Style(FontStyle::Italic),
+        Component::Underline(true),
+        Component::Str("text"),
+      ]
+    );
+  }
+
+  #[test]
+  fn closing_tags_never_opened_are_ignored() {
+    assert_eq!(
+      components("a[/b][/color]b"),
+      [Component::Str("a"), Component::Str("b")]
+    );
+  }
+
+  #[test]
+  fn unknown_tags_stay_text() {
+    assert_eq!(
+      ColoredStr::from_bbcode("[url]x[/url]").text(),
+      "[url]x[/url]"
+    );
+    assert_eq!(ColoredStr::from_bbcode("[b=1]x").text(), "[b=1]x");
+    assert_eq!(
+      ColoredStr::from_bbcode("[color=nope]x").text(),
+      "[color=nope]x"
+    );
+    assert_eq!(ColoredStr::from_bbcode("[[b]x").text(), "[x");
+    assert_eq!(ColoredStr::from_bbcode("a [").text(), "a [");
+  }
+}
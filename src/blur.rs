@@ -0,0 +1,234 @@
+use fontdue::Metrics;
+use macroquad::prelude::Rect;
+
+use crate::{layout::GlyphPlacement, CharacterInfo, Font, Fonts, MeasuredText, TextParams};
+
+/// Blurs a coverage bitmap with three box blurs, which is close to a gaussian blur,
+/// the bitmap grows by the returned padding on every side so the blur isn't cut off
+fn blur_coverage(bitmap: &[u8], width: usize, height: usize, radius: u16) -> (Vec<u8>, usize) {
+  // three boxes spread as far as a single box three times as wide
+  let half = (radius as usize).div_ceil(3).max(1);
+  let pad = half * 3;
+  let (w, h) = (width + pad * 2, height + pad * 2);
+  let mut values = vec![0f32; w * h];
+
+  for y in 0..height {
+    for x in 0..width {
+      values[(y + pad) * w + x + pad] = bitmap[y * width + x] as f32;
+    }
+  }
+
+  let mut line = Vec::new();
+
+  for _ in 0..3 {
+    for y in 0..h {
+      box_blur(&mut values[y * w..(y + 1) * w], 1, half, &mut line);
+    }
+
+    for x in 0..w {
+      box_blur(&mut values[x..], w, half, &mut line);
+    }
+  }
+
+  (values.into_iter().map(|it| it.round() as u8).collect(), pad)
+}
+
+/// Box blurs every `stride`th value, averaging each one with `half` values on both sides
+fn box_blur(values: &mut [f32], stride: usize, half: usize, line: &mut Vec<f32>) {
+  line.clear();
+  line.extend(values.iter().step_by(stride));
+
+  let len = line.len();
+  let size = (half * 2 + 1) as f32;
+  let at = |i: isize| match usize::try_from(i) {
+    Ok(i) if i < len => line[i],
+    _ => 0.0,
+  };
+  let mut sum = (-(half as isize)..=half as isize).map(at).sum::<f32>();
+
+  for i in 0..len {
+    values[i * stride] = sum / size;
+    sum += at((i + half + 1) as isize) - at(i as isize - half as isize);
+  }
+}
+
+impl<'a> Font<'a> {
+  /// Caches a blurred glyph at a given size and blur radius in pixels,
+  /// blurred glyphs are cached separately from sharp ones, `None` if the atlas is full
+  pub(crate) fn cache_blurred_glyph(
+    &self,
+    c: char,
+    size: u16,
+    radius: u16,
+  ) -> Option<CharacterInfo> {
+    if let Some(info) = self.blurred.borrow().get(&(c, size, radius)) {
+      return Some(*info);
+    }
+
+    let (metrics, bitmap) = self.rasterize(c, size as f32);
+    let (blurred, pad) = blur_coverage(&bitmap, metrics.width, metrics.height, radius);
+    let info = self.insert_bitmap(
+      size,
+      Metrics {
+        xmin: metrics.xmin - pad as i32,
+        ymin: metrics.ymin - pad as i32,
+        width: metrics.width + pad * 2,
+        height: metrics.height + pad * 2,
+        ..metrics
+      },
+      &blurred,
+    )?;
+
+    self.blurred.borrow_mut().insert((c, size, radius), info);
+    Some(info)
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws text with given [TextParams] with every glyph blurred by a radius in pixels,
+  /// useful for soft shadows and glows behind text,
+  /// blurred glyphs are cached in the atlas next to the sharp ones
+  ///
+  /// **Example**
+  /// ```rs
+  /// // a glow behind the title
+  /// fonts.draw_text_blurred("Title", &TextParams { color: GOLD, ..params }, 6.0);
+  /// fonts.draw_text_ex("Title", &params);
+  /// ```
+  ///
+  /// **See** [Shadow::blur](crate::Shadow::blur)
+  pub fn draw_text_blurred(&self, text: &str, params: &TextParams, radius: f32) -> MeasuredText {
+    if radius <= 0.0 {
+      return self.draw_text_ex(text, params);
+    }
+
+    if !text.contains('\n') {
+      return self.draw_line_blurred(text, params, radius);
+    }
+
+    let lines = self.split_lines(text, params).map(|(line, line_params)| {
+      (
+        line,
+        line_params.y - params.y,
+        self.draw_line_blurred(line, &line_params, radius),
+      )
+    });
+
+    Self::stack_lines(lines, params)
+  }
+
+  /// Draws a single line of text with every glyph blurred like [Self::draw_text_blurred],
+  /// the sharp glyphs are only measured, not cached
+  fn draw_line_blurred(&self, text: &str, params: &TextParams, radius: f32) -> MeasuredText {
+    // the blurred glyph of every drawn character, all cached before any is drawn,
+    // so the atlas doesn't change halfway through
+    let mut blurred = Vec::new();
+
+    self.layout_glyphs(text, params, false, |_, c, placement| {
+      if placement.blank || placement.rect.w <= 0.0 || placement.rect.h <= 0.0 {
+        blurred.push(None);
+        return;
+      }
+
+      let font = placement.font;
+      let c = self.control_chars.apply(c).unwrap_or(c);
+      let (size, _) = self.raster_size(font, params);
+      // the radius is in pixels of the text, the glyph can be rasterized at another size
+      let radius = (radius * size as f32 / font.adjusted_size(params.size)).ceil() as u16;
+
+      blurred.push(font.cache_blurred_glyph(c, size, radius.max(1)));
+    });
+
+    let center_x = match params.flip_x {
+      true => params.x + self.measure_text_metrics(text, params).width / 2.0,
+      false => params.x,
+    };
+    let mut blurred = blurred.into_iter();
+    let mut sink = self.draw_sink(params);
+
+    self.layout_measured(text, params, false, |_, _, placement| {
+      let Some(Some(info)) = blurred.next() else {
+        return;
+      };
+      let Some(sprite) = placement.font.atlases.borrow().get(info.id) else {
+        return;
+      };
+
+      // how much bigger the glyph is on screen than it was rasterized,
+      // the same for the blurred glyph as for the sharp one
+      let (_, glyph_scale) = self.raster_size(placement.font, params);
+      let (sx, sy) = (glyph_scale * params.scale_x, glyph_scale * params.scale_y);
+      let rect = placement.rect;
+      let (pad_x, pad_y) = (
+        (sprite.rect.w * sx - rect.w) / 2.0,
+        (sprite.rect.h * sy - rect.h) / 2.0,
+      );
+      let placement = GlyphPlacement {
+        sprite: Some(sprite.rect),
+        id: info.id,
+        rect: Rect::new(
+          rect.x - pad_x,
+          rect.y - pad_y,
+          rect.w + pad_x * 2.0,
+          rect.h + pad_y * 2.0,
+        ),
+        ..*placement
+      };
+
+      self.write_placement(&placement, params, center_x, &mut sink);
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  /// Params whose glyphs are all clipped away, so no quad needs an atlas texture
+  fn clipped(size: f32) -> TextParams {
+    TextParams {
+      size,
+      clip: Some(Rect::new(-100.0, -100.0, 1.0, 1.0)),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn blurred_text_breaks_lines() {
+    let fonts = fonts();
+    let blurred = fonts.draw_text_blurred("Game\nOver", &clipped(20.0), 4.0);
+    let measured = fonts.measure_text("Game\nOver", 20.0);
+
+    assert_eq!(blurred.lines, 2);
+    assert_eq!(blurred.width, measured.width);
+    assert_eq!(blurred.height, measured.height);
+  }
+
+  #[test]
+  fn only_blurred_glyphs_are_cached() {
+    let fonts = fonts();
+
+    fonts.draw_text_blurred("A", &clipped(20.0), 4.0);
+
+    let font = &fonts.fonts()[0];
+
+    assert!(font.cached_glyph('A', 20).is_none());
+    assert_eq!(font.blurred.borrow().len(), 1);
+  }
+}
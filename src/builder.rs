@@ -0,0 +1,140 @@
+use std::borrow::Cow;
+
+use crate::{AtlasConfig, FontError, FontResult, Fonts, RasterBackend, ScalingMode};
+
+/// The name, bytes and scale of a font added to a [FontsBuilder]
+type BuilderFont<'a> = (&'a str, Cow<'a, [u8]>, Option<f32>);
+
+/// Builder for [Fonts], lets you set up all your fonts in a single expression
+///
+/// **Example**
+/// ```rs
+/// let fonts = FontsBuilder::new()
+///   .with_scaling(ScalingMode::Nearest)
+///   .with_font("Noto Sans", NOTO_SANS)
+///   .with_font("Noto Sans JP", NOTO_SANS_JP)
+///   .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct FontsBuilder<'a> {
+  fonts: Vec<BuilderFont<'a>>,
+  scaling: ScalingMode,
+  scale: f32,
+  atlas_config: AtlasConfig,
+  raster_backend: RasterBackend,
+}
+
+impl<'a> Default for FontsBuilder<'a> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<'a> FontsBuilder<'a> {
+  /// Creates a new builder with [ScalingMode::Linear] and a default scale of 100.0
+  pub fn new() -> Self {
+    Self {
+      fonts: Vec::new(),
+      scaling: ScalingMode::Linear,
+      scale: 100.0,
+      atlas_config: AtlasConfig::default(),
+      raster_backend: RasterBackend::default(),
+    }
+  }
+
+  /// Adds a font from borrowed or owned bytes with a given name,
+  /// fonts are loaded in the order they're added
+  pub fn with_font(mut self, name: &'a str, bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+    self.fonts.push((name, bytes.into(), None));
+    self
+  }
+
+  /// Adds a font from bytes with a given name and scale,
+  /// this overrides the scale set by [Self::with_scale] for this font only
+  ///
+  /// **See** [Fonts::load_font_from_bytes_with_scale]
+  pub fn with_font_scaled(
+    mut self,
+    name: &'a str,
+    bytes: impl Into<Cow<'a, [u8]>>,
+    scale: f32,
+  ) -> Self {
+    self.fonts.push((name, bytes.into(), Some(scale)));
+    self
+  }
+
+  /// Sets the [ScalingMode] used for every font
+  pub fn with_scaling(mut self, scaling: ScalingMode) -> Self {
+    self.scaling = scaling;
+    self
+  }
+
+  /// Sets how big atlases start and how they grow
+  ///
+  /// **See** [Fonts::set_atlas_config]
+  pub fn with_atlas_config(mut self, config: AtlasConfig) -> Self {
+    self.atlas_config = config;
+    self
+  }
+
+  /// Sets which rasterizer glyphs are rasterized with
+  ///
+  /// **See** [Fonts::set_raster_backend]
+  pub fn with_raster_backend(mut self, backend: RasterBackend) -> Self {
+    self.raster_backend = backend;
+    self
+  }
+
+  /// Sets the scale used for every font that doesn't have its own scale
+  ///
+  /// **See** [Fonts::load_font_from_bytes_with_scale]
+  pub fn with_scale(mut self, scale: f32) -> Self {
+    self.scale = scale;
+    self
+  }
+
+  /// Builds the [Fonts] instance, loading every font in the order they were added,
+  /// fails with [FontError::Named] for the first font that couldn't be loaded
+  pub fn build(self) -> FontResult<Fonts<'a>> {
+    let mut fonts = Fonts::new(self.scaling);
+    fonts.set_atlas_config(self.atlas_config);
+    fonts.set_raster_backend(self.raster_backend)?;
+
+    for (name, bytes, scale) in self.fonts {
+      fonts
+        .load_font_from_bytes_with_scale(name, bytes, scale.unwrap_or(self.scale))
+        .map_err(|error| FontError::Named {
+          name: name.to_string(),
+          error: Box::new(error),
+        })?;
+    }
+
+    Ok(fonts)
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Creates a [Fonts] instance with [ScalingMode::Linear] from fonts with their names,
+  /// loaded in order, so the first font is the main font and the rest are fallbacks,
+  /// fails with [FontError::Named] for the first font that couldn't be loaded
+  ///
+  /// **Example**
+  /// ```rs
+  /// let fonts = Fonts::from_fonts([
+  ///   ("Noto Sans", NOTO_SANS),
+  ///   ("Noto Sans JP", NOTO_SANS_JP),
+  /// ])?;
+  /// ```
+  ///
+  /// **See** [FontsBuilder] for setting the scaling mode and scales
+  pub fn from_fonts<B: Into<Cow<'a, [u8]>>>(
+    fonts: impl IntoIterator<Item = (&'a str, B)>,
+  ) -> FontResult<Self> {
+    fonts
+      .into_iter()
+      .fold(FontsBuilder::new(), |builder, (name, bytes)| {
+        builder.with_font(name, bytes)
+      })
+      .build()
+  }
+}
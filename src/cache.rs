@@ -0,0 +1,387 @@
+use std::{collections::HashSet, ops::RangeInclusive, time::Duration};
+
+use crate::{misc::is_blank, Font, Fonts, TextParams};
+
+/// How far warming the cache got
+///
+/// **See** [Fonts::warm_cache]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WarmProgress {
+  /// How many of the characters are cached
+  pub cached: usize,
+  /// How many characters there are
+  pub total: usize,
+}
+
+impl WarmProgress {
+  /// Returns true if every character is cached
+  pub fn is_done(&self) -> bool {
+    self.cached == self.total
+  }
+
+  /// Returns how much is cached from 0 to 1, useful for loading bars
+  pub fn fraction(&self) -> f32 {
+    match self.total {
+      0 => 1.0,
+      total => self.cached as f32 / total as f32,
+    }
+  }
+}
+
+impl<'a> Font<'a> {
+  /// Removes every cached glyph, bitmap and outline of this font and shrinks its atlas,
+  /// glyphs are cached again the next time they're drawn
+  pub fn clear_cache(&self) {
+    self.chars.borrow_mut().clear();
+    self.bitmaps.borrow_mut().clear();
+    self.blurred.borrow_mut().clear();
+    self.outlines.borrow_mut().clear();
+    self.atlases.borrow_mut().clear();
+  }
+
+  /// Only keeps the cached glyphs `keep` returns true for, given the character and size,
+  /// and packs the atlas again so the space of removed glyphs can be used again
+  ///
+  /// **See** [Fonts::trim_cache]
+  pub fn trim_cache(&self, mut keep: impl FnMut(char, u16) -> bool) {
+    let mut chars = self.chars.borrow_mut();
+
+    let mut blurred = self.blurred.borrow_mut();
+
+    chars.retain(|(c, size), _| keep(*c, *size));
+    blurred.retain(|(c, size, _), _| keep(*c, *size));
+    self
+      .bitmaps
+      .borrow_mut()
+      .retain(|(c, size), _| keep(*c, *size));
+
+    let ids = chars
+      .values()
+      .chain(blurred.values())
+      .map(|it| it.id)
+      .collect::<HashSet<_>>();
+
+    self.atlases.borrow_mut().retain(|id| ids.contains(&id));
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Removes every cached glyph of every font and shrinks their atlases,
+  /// useful for reclaiming memory on scene transitions
+  ///
+  /// **See** [Font::clear_cache]
+  pub fn clear_cache(&self) {
+    for font in &self.fonts {
+      font.clear_cache();
+    }
+  }
+
+  /// Only keeps the cached glyphs of every font `keep` returns true for,
+  /// given the character and size
+  ///
+  /// **Example**
+  /// ```rs
+  /// // the title screen is gone, drop its huge glyphs
+  /// fonts.trim_cache(|_, size| size <= 48);
+  /// ```
+  ///
+  /// **See** [Font::trim_cache]
+  pub fn trim_cache(&self, mut keep: impl FnMut(char, u16) -> bool) {
+    for font in &self.fonts {
+      font.trim_cache(&mut keep);
+    }
+  }
+
+  /// Caches every character of the text at a given size up front,
+  /// in the font that would draw it, so drawing it later doesn't have to rasterize,
+  /// this ignores the raster budget
+  ///
+  /// **Example**
+  /// ```rs
+  /// // while loading, before the dialogue shows up
+  /// fonts.cache_str(&dialogue, 22.0);
+  /// ```
+  pub fn cache_str(&self, text: &str, size: f32) {
+    self.cache_str_ex(
+      text,
+      &TextParams {
+        size,
+        ..Default::default()
+      },
+    );
+  }
+
+  /// Caches every character of the text up front like [Self::cache_str],
+  /// at the size and in the font it's drawn with given [TextParams],
+  /// so text drawn scaled or with a forced font is cached too
+  ///
+  /// **Example**
+  /// ```rs
+  /// // the minimap labels are drawn at half scale
+  /// fonts.cache_str_ex(&labels, &TextParams { size: 22.0, scale: 0.5, ..Default::default() });
+  /// ```
+  pub fn cache_str_ex(&self, text: &str, params: &TextParams) {
+    let chars = self
+      .layout_chars(text, params)
+      .filter(|(_, _, skipped)| !skipped)
+      .filter_map(|(_, c, _)| self.control_chars.apply(c));
+
+    self.cache_chars(chars, params);
+  }
+
+  /// Caches every character in a range at a given size up front, like [Self::cache_str],
+  /// characters no font contains are skipped
+  ///
+  /// **Example**
+  /// ```rs
+  /// // hiragana and katakana
+  /// fonts.cache_range('\u{3040}'..='\u{30FF}', 22.0);
+  /// ```
+  pub fn cache_range(&self, range: RangeInclusive<char>, size: f32) {
+    let params = TextParams {
+      size,
+      ..Default::default()
+    };
+
+    self.cache_chars(range, &params);
+  }
+
+  /// Returns the index of the font and the size a character gets cached at
+  /// when it's drawn with given [TextParams],
+  /// `None` for blank characters and characters no font contains
+  fn cache_target(&self, c: char, params: &TextParams) -> Option<(usize, u16)> {
+    if is_blank(c) || !self.contains(c) {
+      return None;
+    }
+
+    Some(self.raster_target(c, params))
+  }
+
+  /// Caches characters in the fonts that would draw them with given [TextParams]
+  #[cfg(not(feature = "rayon"))]
+  fn cache_chars(&self, chars: impl Iterator<Item = char>, params: &TextParams) {
+    for c in chars {
+      if let Some((index, size)) = self.cache_target(c, params) {
+        self.fonts[index].cache_glyph(c, size);
+      }
+    }
+  }
+
+  /// Caches characters in the fonts that would draw them at a given size,
+  /// glyphs are rasterized on multiple threads and then packed into the atlases
+  #[cfg(feature = "rayon")]
+  fn cache_chars(&self, chars: impl Iterator<Item = char>, params: &TextParams) {
+    use rayon::prelude::*;
+
+    let mut glyphs = Vec::new();
+    let mut seen = HashSet::new();
+
+    for c in chars {
+      let Some((index, size)) = self.cache_target(c, params) else {
+        continue;
+      };
+
+      if !self.fonts[index].chars.borrow().contains_key(&(c, size)) && seen.insert((index, c, size))
+      {
+        glyphs.push((index, c, size));
+      }
+    }
+
+    // Font isn't Sync because of its caches, so only the fontdue fonts and rasterizers are shared
+    let faces = self
+      .fonts
+      .iter()
+      .map(|it| (&it.font, &*it.rasterizer))
+      .collect::<Vec<_>>();
+    let rasterized = glyphs
+      .into_par_iter()
+      .map(|(index, c, size)| {
+        let (font, rasterizer) = faces[index];
+        let (color, (matrix, bitmap)) = match rasterizer.rasterize_color(font, c, size as f32) {
+          Some(pixels) => (true, pixels),
+          None => (false, rasterizer.rasterize(font, c, size as f32)),
+        };
+
+        (index, c, size, color, matrix, bitmap)
+      })
+      .collect::<Vec<_>>();
+
+    for (index, c, size, color, matrix, bitmap) in rasterized {
+      let font = &self.fonts[index];
+      let info = match color {
+        true => font.insert_pixels(size, matrix, bitmap),
+        false => font.insert_bitmap(size, matrix, &bitmap),
+      };
+
+      if let Some(info) = info {
+        font.chars.borrow_mut().insert((c, size), info);
+      }
+    }
+  }
+
+  /// Caches characters at a given size until `budget` runs out, call this every frame
+  /// with the same characters until it's done, characters that are already cached are skipped,
+  /// so large character sets can be cached across frames of a loading screen without stalls
+  ///
+  /// Characters no font contains and blank characters like spaces count as cached
+  ///
+  /// The budget is timed with [std::time::Instant], so this works before a window is open,
+  /// except on the web, where it's timed with [macroquad::time::get_time]
+  ///
+  /// **Example**
+  /// ```rs
+  /// let chars = ('\u{4E00}'..='\u{9FFF}').collect::<Vec<_>>();
+  ///
+  /// loop {
+  ///   let progress = fonts.warm_cache(chars.iter().copied(), 22.0, Duration::from_millis(8));
+  ///
+  ///   if progress.is_done() {
+  ///     break;
+  ///   }
+  ///
+  ///   draw_loading_bar(progress.fraction());
+  ///   next_frame().await;
+  /// }
+  /// ```
+  pub fn warm_cache(
+    &self,
+    chars: impl IntoIterator<Item = char>,
+    size: f32,
+    budget: Duration,
+  ) -> WarmProgress {
+    let out_of_time = deadline(budget);
+    let params = TextParams {
+      size,
+      ..Default::default()
+    };
+    let mut progress = WarmProgress::default();
+
+    for c in chars {
+      progress.total += 1;
+
+      let Some((index, size)) = self.cache_target(c, &params) else {
+        progress.cached += 1;
+        continue;
+      };
+
+      let font = &self.fonts[index];
+
+      if font.chars.borrow().contains_key(&(c, size)) {
+        progress.cached += 1;
+      } else if !out_of_time() {
+        font.cache_glyph(c, size);
+        progress.cached += 1;
+      }
+    }
+
+    progress
+  }
+}
+
+/// Returns a function that says if `budget` ran out since this was called,
+/// the clock of std doesn't need a macroquad context, but the web has none
+#[cfg(not(target_arch = "wasm32"))]
+fn deadline(budget: Duration) -> impl Fn() -> bool {
+  let end = std::time::Instant::now() + budget;
+
+  move || std::time::Instant::now() >= end
+}
+
+/// Returns a function that says if `budget` ran out since this was called
+#[cfg(target_arch = "wasm32")]
+fn deadline(budget: Duration) -> impl Fn() -> bool {
+  use macroquad::time::get_time;
+
+  let end = get_time() + budget.as_secs_f64();
+
+  move || get_time() >= end
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+  const NOTO_SANS_JP: &[u8] = include_bytes!("../assets/fonts/NotoSansJP-Regular.otf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+      .load_font_from_bytes("Noto Sans JP", NOTO_SANS_JP)
+      .unwrap();
+    fonts
+  }
+
+  fn cached(fonts: &Fonts, index: usize) -> usize {
+    fonts.fonts()[index].chars.borrow().len()
+  }
+
+  #[test]
+  fn characters_are_cached_in_the_font_that_draws_them() {
+    let fonts = fonts();
+
+    fonts.cache_str("aあ", 20.0);
+
+    assert!(fonts.fonts()[0].cached_glyph('a', 20).is_some());
+    assert!(fonts.fonts()[1].cached_glyph('あ', 20).is_some());
+    assert_eq!(cached(&fonts, 0), 1);
+    assert_eq!(cached(&fonts, 1), 1);
+  }
+
+  #[test]
+  fn unsupported_characters_arent_cached() {
+    let fonts = fonts();
+
+    fonts.cache_str("\u{E000} ", 20.0);
+    fonts.cache_range('\u{E000}'..='\u{E0FF}', 20.0);
+
+    assert_eq!(cached(&fonts, 0), 0);
+    assert_eq!(cached(&fonts, 1), 0);
+  }
+
+  #[test]
+  fn caching_ahead_matches_drawing() {
+    let mut fonts = fonts();
+    let params = TextParams {
+      size: 40.0,
+      scale: 0.5,
+      ..Default::default()
+    };
+
+    fonts.set_rasterize_downscaled(true);
+    fonts.cache_str_ex("a", &params);
+
+    assert!(fonts.fonts()[0].cached_glyph('a', 20).is_some());
+
+    // drawing finds everything already cached
+    fonts.cache_text_glyphs("a", &params);
+
+    assert_eq!(cached(&fonts, 0), 1);
+  }
+
+  #[test]
+  fn warming_stops_when_the_budget_runs_out() {
+    let fonts = fonts();
+    let chars = ['a', 'b', ' ', '\u{E000}'];
+
+    // blank and unsupported characters count as cached without any budget
+    let progress = fonts.warm_cache(chars, 20.0, Duration::ZERO);
+
+    assert_eq!((progress.cached, progress.total), (2, 4));
+    assert_eq!(cached(&fonts, 0), 0);
+
+    let progress = fonts.warm_cache(chars, 20.0, Duration::from_secs(60));
+
+    assert!(progress.is_done());
+    assert_eq!(cached(&fonts, 0), 2);
+  }
+}
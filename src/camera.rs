@@ -0,0 +1,62 @@
+use macroquad::prelude::{pop_camera_state, push_camera_state, set_default_camera};
+
+/// Which space text is drawn in
+///
+/// **Default** [DrawSpace::Camera]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawSpace {
+  /// Draws text with the camera that's currently active,
+  /// so positions are in world coordinates when a world camera is set
+  #[default]
+  Camera,
+  /// Draws text in screen coordinates even when a camera is set,
+  /// useful for HUD text while a zoomed world camera is active,
+  /// glyphs are drawn right away, even if queuing
+  Screen,
+}
+
+/// Runs a function with the default camera, so everything drawn in it is in screen coordinates,
+/// the camera that was active before is set again afterwards
+///
+/// **Example**
+/// ```rs
+/// set_camera(&world_camera);
+/// draw_world();
+///
+/// in_screen_space(|| {
+///   fonts.draw_text(&format!("HP: {hp}"), 10.0, 10.0, 22.0, WHITE);
+///   draw_minimap();
+/// });
+/// ```
+pub fn in_screen_space<T>(f: impl FnOnce() -> T) -> T {
+  let _screen = ScreenSpace::enter();
+
+  f()
+}
+
+/// Sets the default camera until it's dropped
+pub(crate) struct ScreenSpace(());
+
+impl ScreenSpace {
+  /// Sets the default camera, `None` if text is drawn in camera space
+  pub fn new(space: DrawSpace) -> Option<Self> {
+    match space {
+      DrawSpace::Camera => None,
+      DrawSpace::Screen => Some(Self::enter()),
+    }
+  }
+
+  fn enter() -> Self {
+    push_camera_state();
+    set_default_camera();
+
+    Self(())
+  }
+}
+
+impl Drop for ScreenSpace {
+  fn drop(&mut self) {
+    pop_camera_state();
+  }
+}
@@ -0,0 +1,254 @@
+use std::ops::Range;
+
+use macroquad::prelude::{Rect, Vec2};
+
+use crate::{Fonts, TextLayout, TextLine, TextParams};
+
+/// Where a caret should be drawn
+///
+/// **See** [Fonts::caret_at]
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct Caret {
+  /// x-coordinate of the caret
+  pub x: f32,
+  /// y-coordinate of the top of the caret
+  pub y: f32,
+  /// The height of the caret, the height of the line box
+  pub height: f32,
+}
+
+impl<'a> Fonts<'a> {
+  /// Returns the top and bottom of the line box on screen,
+  /// made from the ascent and descent of the first loaded font
+  pub(crate) fn line_box(&self, params: &TextParams) -> (f32, f32) {
+    let baseline = self.baseline_y(params);
+    let (ascent, descent) = self
+      .fonts
+      .first()
+      .and_then(|font| font.horizontal_line_metrics(params.size))
+      .map_or((params.size, 0.0), |metrics| {
+        (metrics.ascent, metrics.descent)
+      });
+
+    (
+      baseline - ascent * params.vertical_scale(),
+      baseline - descent * params.vertical_scale(),
+    )
+  }
+
+  /// Maps a point on screen to the nearest caret position in the text,
+  /// returns the byte index of the character the caret would be in front of,
+  /// which is the end of the line when the caret would be at the end of a line
+  ///
+  /// '\n' starts a new line like in [Self::measure_multiline]
+  ///
+  /// Returns `None` if the point is above or below all lines or if no fonts are loaded
+  ///
+  /// **Example**
+  /// ```rs
+  /// if is_mouse_button_pressed(MouseButton::Left) {
+  ///   let (x, y) = mouse_position();
+  ///
+  ///   if let Some(index) = fonts.char_index_at(&text, &params, vec2(x, y)) {
+  ///     caret = index;
+  ///   }
+  /// }
+  /// ```
+  pub fn char_index_at(&self, text: &str, params: &TextParams, point: Vec2) -> Option<usize> {
+    if self.fonts.is_empty() {
+      return None;
+    }
+
+    let layout = self.measure_multiline(text, params, None);
+    let line = layout
+      .lines
+      .iter()
+      .find(|line| point.y >= line.top && point.y <= line.top + line.height)?;
+
+    let index = layout.glyphs[line.glyphs.clone()]
+      .iter()
+      .find(|glyph| point.x < glyph.x + glyph.advance / 2.0)
+      .map_or(line.range.end, |glyph| glyph.index);
+
+    Some(index)
+  }
+
+  /// Returns where the caret should be drawn when it's in front of the character
+  /// at a given byte index, indices past the end put the caret at the end of the text
+  ///
+  /// This is the inverse of [Self::char_index_at]
+  ///
+  /// **Example**
+  /// ```rs
+  /// let caret = fonts.caret_at(&text, &params, index);
+  ///
+  /// draw_line(caret.x, caret.y, caret.x, caret.y + caret.height, 2.0, WHITE);
+  /// ```
+  pub fn caret_at(&self, text: &str, params: &TextParams, index: usize) -> Caret {
+    let (top, bottom) = self.line_box(params);
+    let layout = self.measure_multiline(text, params, None);
+    let line = layout
+      .lines
+      .iter()
+      .find(|line| index <= line.range.end)
+      .unwrap_or_else(|| layout.lines.last().unwrap());
+
+    Caret {
+      x: Self::caret_x(&layout, line, params, index),
+      y: line.top,
+      height: bottom - top,
+    }
+  }
+
+  /// Returns the rects that cover a byte range of the text, one per line,
+  /// draw these behind the text to highlight a selection
+  ///
+  /// Returns no rects if the range is empty
+  ///
+  /// **Example**
+  /// ```rs
+  /// for rect in fonts.selection_rects(&text, &params, selection.clone()) {
+  ///   draw_rectangle(rect.x, rect.y, rect.w, rect.h, BLUE);
+  /// }
+  ///
+  /// fonts.draw_text_ex(&text, &params);
+  /// ```
+  pub fn selection_rects(&self, text: &str, params: &TextParams, range: Range<usize>) -> Vec<Rect> {
+    if range.is_empty() {
+      return Vec::new();
+    }
+
+    let (top, bottom) = self.line_box(params);
+    let layout = self.measure_multiline(text, params, None);
+
+    layout
+      .lines
+      .iter()
+      .filter(|line| range.start <= line.range.end && range.end >= line.range.start)
+      .map(|line| {
+        let start = Self::caret_x(&layout, line, params, range.start.max(line.range.start));
+        let end = Self::caret_x(&layout, line, params, range.end.min(line.range.end));
+
+        Rect::new(start, line.top, end - start, bottom - top)
+      })
+      .filter(|rect| rect.w > 0.0)
+      .collect()
+  }
+
+  /// Returns the x-coordinate of the caret in front of the character at a byte index on a line
+  fn caret_x(layout: &TextLayout, line: &TextLine, params: &TextParams, index: usize) -> f32 {
+    let glyphs = &layout.glyphs[line.glyphs.clone()];
+
+    glyphs
+      .iter()
+      .find(|glyph| glyph.index >= index)
+      .or(glyphs.last())
+      .map_or(params.x, |glyph| {
+        if glyph.index >= index {
+          glyph.x
+        } else {
+          glyph.x + glyph.advance
+        }
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::prelude::vec2;
+
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+  const TEXT: &str = "ab\ncd";
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  fn params() -> TextParams {
+    TextParams {
+      x: 10.0,
+      y: 20.0,
+      size: 24.0,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn char_index_at_is_the_inverse_of_caret_at() {
+    let fonts = fonts();
+
+    for index in 0..=TEXT.len() {
+      let caret = fonts.caret_at(TEXT, &params(), index);
+      let point = vec2(caret.x + 0.5, caret.y + caret.height / 2.0);
+
+      assert_eq!(fonts.char_index_at(TEXT, &params(), point), Some(index));
+    }
+  }
+
+  #[test]
+  fn carets_follow_lines() {
+    let fonts = fonts();
+    let first = fonts.caret_at(TEXT, &params(), 0);
+    let second = fonts.caret_at(TEXT, &params(), 3);
+
+    assert_eq!(first.x, 10.0);
+    assert_eq!(second.x, 10.0);
+    assert!(second.y > first.y);
+    assert_eq!(
+      fonts.caret_at(TEXT, &params(), 100),
+      fonts.caret_at(TEXT, &params(), TEXT.len())
+    );
+  }
+
+  #[test]
+  fn points_outside_of_the_lines_have_no_index() {
+    let fonts = fonts();
+    let top = fonts.caret_at(TEXT, &params(), 0).y;
+
+    assert_eq!(
+      fonts.char_index_at(TEXT, &params(), vec2(10.0, top - 1.0)),
+      None
+    );
+    assert_eq!(
+      fonts.char_index_at(TEXT, &params(), vec2(10.0, 1000.0)),
+      None
+    );
+    assert_eq!(
+      Fonts::default().char_index_at(TEXT, &params(), vec2(10.0, top)),
+      None
+    );
+  }
+
+  #[test]
+  fn selections_get_a_rect_per_line() {
+    let fonts = fonts();
+    let caret = |index| fonts.caret_at(TEXT, &params(), index);
+    let rects = fonts.selection_rects(TEXT, &params(), 1..4);
+
+    assert_eq!(rects.len(), 2);
+
+    for (rect, (start, end)) in rects
+      .iter()
+      .zip([(caret(1), caret(2)), (caret(3), caret(4))])
+    {
+      assert_eq!(rect.x, start.x);
+      assert_eq!(rect.y, start.y);
+      assert_eq!(rect.w, end.x - start.x);
+      assert_eq!(rect.h, start.height);
+    }
+
+    assert!(fonts.selection_rects(TEXT, &params(), 2..2).is_empty());
+  }
+}
@@ -0,0 +1,47 @@
+use ttf_parser::{fonts_in_collection, name_id, Face};
+
+/// A face inside a font collection (.ttc/.otc)
+///
+/// **See** [collection_faces]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionFace {
+  /// The collection index of this face,
+  /// pass this to [Fonts::load_font_from_bytes_with_index](crate::Fonts::load_font_from_bytes_with_index)
+  pub index: u32,
+  /// The family name of this face, like "Source Han Sans JP"
+  pub family: Option<String>,
+  /// The subfamily name of this face, like "Bold"
+  pub subfamily: Option<String>,
+}
+
+/// Lists all faces inside a font collection (.ttc/.otc),
+/// a regular font file is treated as a collection with a single face
+///
+/// **Example**
+/// ```rs
+/// for face in collection_faces(SOURCE_HAN_SANS) {
+///   println!("{}: {:?} {:?}", face.index, face.family, face.subfamily);
+/// }
+/// ```
+pub fn collection_faces(bytes: &[u8]) -> Vec<CollectionFace> {
+  let len = fonts_in_collection(bytes).unwrap_or(1);
+
+  (0..len)
+    .filter_map(|index| {
+      let face = Face::from_slice(bytes, index).ok()?;
+      let name = |id| {
+        face
+          .names()
+          .into_iter()
+          .filter(|name| name.name_id == id)
+          .find_map(|name| name.to_string())
+      };
+
+      Some(CollectionFace {
+        index,
+        family: name(name_id::FAMILY),
+        subfamily: name(name_id::SUBFAMILY),
+      })
+    })
+    .collect()
+}
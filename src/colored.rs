@@ -0,0 +1,607 @@
+use macroquad::{
+  color::hsl_to_rgb,
+  prelude::{Color, TextDimensions, Vec2},
+  time::get_time,
+};
+
+use crate::{
+  fade::fade_params, DrawFrom, FontError, FontResult, FontStyle, FontWeight, Fonts, MeasuredText,
+  TextParams,
+};
+
+/// A piece of a [ColoredStr]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Component<'a> {
+  /// Text drawn with the current color
+  Str(&'a str),
+  /// A character drawn with the current color
+  Char(char),
+  /// Changes the color of everything after it
+  Color(Color),
+  /// Changes the color back to the color of the [TextParams]
+  ResetColor,
+  /// Changes the weight of everything after it
+  Weight(FontWeight),
+  /// Changes the weight back to the weight of the [TextParams]
+  ResetWeight,
+  /// Changes the style of everything after it
+  Style(FontStyle),
+  /// Changes the style back to the style of the [TextParams]
+  ResetStyle,
+  /// Turns underlining of everything after it on or off
+  Underline(bool),
+  /// Changes the size of everything after it,
+  /// text of different sizes shares the same baseline
+  Size(f32),
+  /// Changes the size back to the size of the [TextParams]
+  ResetSize,
+  /// Draws everything after it with the font with this name where it has the characters,
+  /// instead of looking up a font that contains each character
+  Font(&'a str),
+  /// Switches back to looking up fonts by character
+  ResetFont,
+  /// Moves everything after it by this offset without moving the pen,
+  /// positive y moves down, useful for superscripts and subscripts
+  Offset(Vec2),
+  /// Rotates every glyph after it around its own center by this angle in radians,
+  /// put one in front of every character to spin letters independently
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = "TITLE"
+  ///   .chars()
+  ///   .enumerate()
+  ///   .flat_map(|(i, c)| [Component::Rotation((time + i as f32 * 0.3).sin() * 0.4), c.into()])
+  ///   .collect::<ColoredStr>();
+  ///
+  /// fonts.draw_colored_text_ex(&text, &params);
+  /// ```
+  Rotation(f32),
+  /// Colors every character after it with a color that changes over time,
+  /// until the color is changed again
+  Animated(AnimatedColor),
+}
+
+/// Picks the color of a character from the time in seconds
+/// and the index of the character in the [ColoredStr]
+///
+/// **Example**
+/// ```rs
+/// let text = ColoredStr::from(vec![
+///   Component::Animated(AnimatedColor::RAINBOW),
+///   Component::Str("Rainbow"),
+/// ]);
+///
+/// loop {
+///   // no need to build the text again every frame
+///   fonts.draw_colored_text_ex(&text, &params);
+///   next_frame().await;
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct AnimatedColor(pub fn(f32, usize) -> Color);
+
+impl AnimatedColor {
+  /// Cycles every character through the hues, each one a bit ahead of the one before it
+  pub const RAINBOW: Self =
+    Self(|time, index| hsl_to_rgb((time * 0.5 + index as f32 * 0.05).fract(), 1.0, 0.6));
+
+  /// Returns the color of a character at a given time in seconds
+  pub fn color(&self, time: f32, index: usize) -> Color {
+    (self.0)(time, index)
+  }
+}
+
+impl PartialEq for AnimatedColor {
+  fn eq(&self, other: &Self) -> bool {
+    std::ptr::fn_addr_eq(self.0, other.0)
+  }
+}
+
+/// How the characters of a [Span] colored by an [AnimatedColor] are colored
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SpanAnimation {
+  pub color: AnimatedColor,
+  /// The time in seconds the colors are picked for
+  pub time: f32,
+  /// The index of the first character of the span in the [ColoredStr]
+  pub first: usize,
+}
+
+impl SpanAnimation {
+  /// Returns the color of a character of the span by its number in the span
+  pub fn color(&self, number: usize) -> Color {
+    self.color.color(self.time, self.first + number)
+  }
+}
+
+/// A piece of a [ColoredStr] where everything is drawn the same way
+///
+/// **See** [ColoredStr::spans]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span<'a> {
+  pub text: String,
+  /// The color of the span, the color of its first character if it's animated
+  pub color: Color,
+  /// Colors every character of the span instead of [Self::color] when it's set,
+  /// underlines are drawn with [Self::color]
+  pub animated: Option<SpanAnimation>,
+  pub weight: FontWeight,
+  pub style: FontStyle,
+  pub underline: bool,
+  pub size: f32,
+  /// The name of the font to draw with, `None` looks fonts up by character
+  pub font: Option<&'a str>,
+  pub offset: Vec2,
+  /// The angle in radians every glyph is rotated by around its center
+  pub rotation: f32,
+}
+
+impl<'a> Span<'a> {
+  /// Returns `params` with the color, weight, style, size and rotation of this span
+  pub fn params(&self, params: &TextParams) -> TextParams {
+    TextParams {
+      color: self.color,
+      weight: self.weight,
+      style: self.style,
+      size: self.size,
+      rotation: self.rotation,
+      ..*params
+    }
+  }
+
+  /// Returns true if both spans are drawn the same way
+  fn same_style(&self, other: &Span) -> bool {
+    // animated spans only share the color of their first character
+    (self.color == other.color || self.animated.is_some())
+      && self.animated.map(|it| it.color) == other.animated.map(|it| it.color)
+      && self.weight == other.weight
+      && self.style == other.style
+      && self.underline == other.underline
+      && self.size == other.size
+      && self.font == other.font
+      && self.offset == other.offset
+      && self.rotation == other.rotation
+  }
+}
+
+/// Text made of [Component]s, so parts of it can be drawn in different colors
+///
+/// **Example**
+/// ```rs
+/// let text = ColoredStr::from(vec![
+///   Component::Str("Press "),
+///   Component::Color(RED),
+///   Component::Char('R'),
+///   Component::ResetColor,
+///   Component::Str(" to restart"),
+/// ]);
+///
+/// fonts.draw_colored_text_ex(&text, &TextParams::default());
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ColoredStr<'a> {
+  components: Vec<Component<'a>>,
+}
+
+impl<'a> ColoredStr<'a> {
+  /// Creates an empty colored string
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a component to the end
+  pub fn push(&mut self, component: Component<'a>) {
+    self.components.push(component);
+  }
+
+  /// Returns every component in order
+  pub fn components(&self) -> &[Component<'a>] {
+    &self.components
+  }
+
+  /// Returns the text without any colors
+  pub fn text(&self) -> String {
+    let mut text = String::new();
+
+    for component in &self.components {
+      match component {
+        Component::Str(str) => text.push_str(str),
+        Component::Char(c) => text.push(*c),
+        _ => {}
+      }
+    }
+
+    text
+  }
+
+  /// Returns the text split into pieces that are drawn the same way,
+  /// the color, weight and style of `params` are used until a component changes them
+  ///
+  /// Characters colored by an [AnimatedColor] share a span, [Span::animated]
+  /// colors each of them for the current time
+  pub fn spans(&self, params: &TextParams) -> Vec<Span<'a>> {
+    self.spans_at(params, None)
+  }
+
+  /// Returns the spans like [Self::spans], animated colors are picked for `time`
+  /// or the current time if it's `None`
+  pub(crate) fn spans_at(&self, params: &TextParams, mut time: Option<f32>) -> Vec<Span<'a>> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut animated = None;
+    let mut index = 0;
+    let mut current = Span {
+      text: String::new(),
+      color: params.color,
+      animated: None,
+      weight: params.weight,
+      style: params.style,
+      underline: false,
+      size: params.size,
+      font: None,
+      offset: Vec2::ZERO,
+      rotation: params.rotation,
+    };
+
+    for component in &self.components {
+      let mut buf = [0; 4];
+      let text: &str = match component {
+        Component::Str(str) => str,
+        Component::Char(c) => c.encode_utf8(&mut buf),
+        Component::Color(color) => {
+          current.color = *color;
+          animated = None;
+          continue;
+        }
+        Component::ResetColor => {
+          current.color = params.color;
+          animated = None;
+          continue;
+        }
+        Component::Animated(color) => {
+          animated = Some(*color);
+          continue;
+        }
+        Component::Weight(weight) => {
+          current.weight = *weight;
+          continue;
+        }
+        Component::ResetWeight => {
+          current.weight = params.weight;
+          continue;
+        }
+        Component::Style(style) => {
+          current.style = *style;
+          continue;
+        }
+        Component::ResetStyle => {
+          current.style = params.style;
+          continue;
+        }
+        Component::Underline(underline) => {
+          current.underline = *underline;
+          continue;
+        }
+        Component::Size(size) => {
+          current.size = *size;
+          continue;
+        }
+        Component::ResetSize => {
+          current.size = params.size;
+          continue;
+        }
+        Component::Font(font) => {
+          current.font = Some(font);
+          continue;
+        }
+        Component::ResetFont => {
+          current.font = None;
+          continue;
+        }
+        Component::Offset(offset) => {
+          current.offset = *offset;
+          continue;
+        }
+        Component::Rotation(rotation) => {
+          current.rotation = *rotation;
+          continue;
+        }
+      };
+
+      let animation = animated.map(|color| SpanAnimation {
+        color,
+        time: *time.get_or_insert_with(|| get_time() as f32),
+        first: index,
+      });
+      let span = Span {
+        color: animation.map_or(current.color, |it| it.color(0)),
+        animated: animation,
+        ..current.clone()
+      };
+
+      index += text.chars().count();
+
+      match spans.last_mut() {
+        Some(last) if last.same_style(&span) => last.text.push_str(text),
+        _ => spans.push(Span {
+          text: text.to_string(),
+          ..span
+        }),
+      }
+    }
+
+    spans
+  }
+}
+
+impl<'a> From<&'a str> for Component<'a> {
+  fn from(str: &'a str) -> Self {
+    Self::Str(str)
+  }
+}
+
+impl<'a> From<char> for Component<'a> {
+  fn from(c: char) -> Self {
+    Self::Char(c)
+  }
+}
+
+impl<'a> From<Color> for Component<'a> {
+  fn from(color: Color) -> Self {
+    Self::Color(color)
+  }
+}
+
+impl<'a> From<FontWeight> for Component<'a> {
+  fn from(weight: FontWeight) -> Self {
+    Self::Weight(weight)
+  }
+}
+
+impl<'a> From<FontStyle> for Component<'a> {
+  fn from(style: FontStyle) -> Self {
+    Self::Style(style)
+  }
+}
+
+impl<'a> From<AnimatedColor> for Component<'a> {
+  fn from(color: AnimatedColor) -> Self {
+    Self::Animated(color)
+  }
+}
+
+impl<'a> From<Vec<Component<'a>>> for ColoredStr<'a> {
+  fn from(components: Vec<Component<'a>>) -> Self {
+    Self { components }
+  }
+}
+
+impl<'a> From<&'a str> for ColoredStr<'a> {
+  fn from(str: &'a str) -> Self {
+    Self::from(vec![Component::Str(str)])
+  }
+}
+
+impl<'a> FromIterator<Component<'a>> for ColoredStr<'a> {
+  fn from_iter<T: IntoIterator<Item = Component<'a>>>(iter: T) -> Self {
+    Self {
+      components: iter.into_iter().collect(),
+    }
+  }
+}
+
+impl<'a> Extend<Component<'a>> for ColoredStr<'a> {
+  fn extend<T: IntoIterator<Item = Component<'a>>>(&mut self, iter: T) {
+    self.components.extend(iter);
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws a [ColoredStr] with given [TextParams],
+  /// the params are used until a component changes them,
+  /// every span is drawn on the baseline of the params
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = ColoredStr::from_markup("<color=#ff0044>danger</color> ahead");
+  ///
+  /// fonts.draw_colored_text_ex(&text, &TextParams::default());
+  /// ```
+  ///
+  /// **See** [Self::draw_text_ex], [Self::try_draw_colored_text_ex]
+  pub fn draw_colored_text_ex(&self, text: &ColoredStr, params: &TextParams) -> MeasuredText {
+    let mut x = params.x;
+
+    for span in text.spans(params) {
+      let params = self.span_params(&span, params, x);
+      let width = self.draw_span(&span, &params, |_| 1.0).width;
+
+      if span.underline {
+        self.draw_underline(&params, width);
+      }
+
+      x += width;
+    }
+
+    self.measure_colored_text(text, params)
+  }
+  /// Draws colored text like [Self::draw_colored_text_ex],
+  /// but returns [FontError::NoFontsLoaded] instead of drawing nothing when no fonts are loaded
+  pub fn try_draw_colored_text_ex(
+    &self,
+    text: &ColoredStr,
+    params: &TextParams,
+  ) -> FontResult<MeasuredText> {
+    if self.fonts().is_empty() && !text.text().is_empty() {
+      return Err(FontError::NoFontsLoaded);
+    }
+
+    Ok(self.draw_colored_text_ex(text, params))
+  }
+
+  /// Measures a [ColoredStr] with given [TextParams]
+  pub fn measure_colored_text(&self, text: &ColoredStr, params: &TextParams) -> MeasuredText {
+    let mut measured = MeasuredText::empty(params);
+    let mut width = 0.0;
+    // how far the text reaches above and below the baseline
+    let mut above = f32::MIN;
+    let mut below = f32::MIN;
+
+    for span in text.spans(params).iter().filter(|it| !it.text.is_empty()) {
+      let offset = span.offset.y;
+      let span = self.measure_params(
+        &span.text,
+        &self.span_params(span, params, params.x + width),
+      );
+
+      measured.add(&span);
+      width += span.width;
+      above = above.max(span.offset_y - offset);
+      below = below.max(span.height - span.offset_y + offset);
+    }
+
+    let dimensions = match above == f32::MIN {
+      true => TextDimensions::default(),
+      false => TextDimensions {
+        width,
+        height: above + below,
+        offset_y: above,
+      },
+    };
+
+    // every span is on the same line
+    MeasuredText {
+      lines: 1,
+      ..measured
+    }
+    .with_dimensions(dimensions, params)
+  }
+
+  /// Draws a span with the params [Self::span_params] returns for it,
+  /// the alpha of every character is multiplied by what `alpha` returns
+  /// for the number of the character in the span
+  pub(crate) fn draw_span(
+    &self,
+    span: &Span,
+    params: &TextParams,
+    alpha: impl Fn(usize) -> f32,
+  ) -> MeasuredText {
+    self.cache_text_glyphs(&span.text, params);
+    self.write_text(
+      &span.text,
+      params,
+      &mut self.draw_sink(params),
+      |number, params| {
+        if let Some(animation) = span.animated {
+          params.color = animation.color(number);
+        }
+
+        fade_params(params, alpha(number))
+      },
+    )
+  }
+
+  /// Returns the params a span is drawn with when the pen is at `x`,
+  /// drawn from the baseline of `params`
+  pub(crate) fn span_params(&self, span: &Span, params: &TextParams, x: f32) -> TextParams {
+    TextParams {
+      x: x + span.offset.x,
+      y: self.baseline_y(params) + span.offset.y,
+      draw: DrawFrom::Baseline,
+      font: span
+        .font
+        .and_then(|it| self.get_index_by_name(it))
+        .or(params.font),
+      ..span.params(params)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::RefCell;
+
+  use macroquad::{
+    color::colors::{RED, WHITE},
+    prelude::Rect,
+  };
+
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  thread_local! {
+    /// The index of every character [record] was asked to color, in order
+    static RECORDED: RefCell<Vec<usize>> = RefCell::default();
+  }
+
+  /// Colors every character white and records its index
+  fn record(_: f32, index: usize) -> Color {
+    RECORDED.with_borrow_mut(|it| it.push(index));
+    WHITE
+  }
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  #[test]
+  fn animated_characters_share_a_span() {
+    let text = ColoredStr::from(vec![
+      Component::Str("xy"),
+      Component::Animated(AnimatedColor::RAINBOW),
+      Component::Str("ab"),
+      Component::Char('c'),
+      Component::Color(RED),
+      Component::Str("d"),
+    ]);
+    let spans = text.spans_at(&TextParams::default(), Some(1.0));
+    let texts = spans.iter().map(|it| it.text.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(texts, ["xy", "abc", "d"]);
+
+    let animation = spans[1].animated.unwrap();
+
+    assert_eq!(animation.color, AnimatedColor::RAINBOW);
+    assert_eq!(animation.time, 1.0);
+    assert_eq!(animation.first, 2);
+    assert_eq!(spans[1].color, animation.color(0));
+    assert_eq!(spans[2].animated, None);
+  }
+
+  #[test]
+  fn animated_spans_color_every_glyph() {
+    let fonts = fonts();
+    let text = ColoredStr::from(vec![
+      Component::Str("xy"),
+      Component::Animated(AnimatedColor(record)),
+      Component::Str("abc"),
+    ]);
+    // clipped away, so no quad needs an atlas texture
+    let params = TextParams {
+      clip: Some(Rect::new(-100.0, -100.0, 1.0, 1.0)),
+      ..Default::default()
+    };
+    let spans = text.spans_at(&params, Some(1.0));
+
+    RECORDED.with_borrow_mut(Vec::clear);
+    fonts.draw_span(
+      &spans[1],
+      &fonts.span_params(&spans[1], &params, 0.0),
+      |_| 1.0,
+    );
+
+    assert_eq!(RECORDED.take(), [2, 3, 4]);
+  }
+}
@@ -0,0 +1,213 @@
+use macroquad::prelude::{Color, Vec2};
+
+use crate::{AnimatedColor, ColoredStr, Component, FontStyle, FontWeight};
+
+/// An owned [Component], used by [ColoredString]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum OwnedComponent {
+  /// Text drawn with the current color
+  String(String),
+  /// A character drawn with the current color
+  Char(char),
+  /// Changes the color of everything after it
+  Color(Color),
+  /// Changes the color back to the color of the [TextParams](crate::TextParams)
+  ResetColor,
+  /// Changes the weight of everything after it
+  Weight(FontWeight),
+  /// Changes the weight back to the weight of the [TextParams](crate::TextParams)
+  ResetWeight,
+  /// Changes the style of everything after it
+  Style(FontStyle),
+  /// Changes the style back to the style of the [TextParams](crate::TextParams)
+  ResetStyle,
+  /// Turns underlining of everything after it on or off
+  Underline(bool),
+  /// Changes the size of everything after it
+  Size(f32),
+  /// Changes the size back to the size of the [TextParams](crate::TextParams)
+  ResetSize,
+  /// Draws everything after it with the font with this name
+  Font(String),
+  /// Switches back to looking up fonts by character
+  ResetFont,
+  /// Moves everything after it by this offset without moving the pen
+  Offset(Vec2),
+  /// Rotates every glyph after it around its own center by this angle in radians
+  Rotation(f32),
+  /// Colors every character after it with a color that changes over time
+  Animated(AnimatedColor),
+}
+
+impl OwnedComponent {
+  /// Borrows this as a [Component]
+  pub fn as_component(&self) -> Component<'_> {
+    match self {
+      Self::String(string) => Component::Str(string),
+      Self::Char(c) => Component::Char(*c),
+      Self::Color(color) => Component::Color(*color),
+      Self::ResetColor => Component::ResetColor,
+      Self::Weight(weight) => Component::Weight(*weight),
+      Self::ResetWeight => Component::ResetWeight,
+      Self::Style(style) => Component::Style(*style),
+      Self::ResetStyle => Component::ResetStyle,
+      Self::Underline(underline) => Component::Underline(*underline),
+      Self::Size(size) => Component::Size(*size),
+      Self::ResetSize => Component::ResetSize,
+      Self::Font(font) => Component::Font(font),
+      Self::ResetFont => Component::ResetFont,
+      Self::Offset(offset) => Component::Offset(*offset),
+      Self::Rotation(rotation) => Component::Rotation(*rotation),
+      Self::Animated(color) => Component::Animated(*color),
+    }
+  }
+}
+
+impl From<Component<'_>> for OwnedComponent {
+  fn from(component: Component<'_>) -> Self {
+    match component {
+      Component::Str(str) => Self::String(str.to_string()),
+      Component::Char(c) => Self::Char(c),
+      Component::Color(color) => Self::Color(color),
+      Component::ResetColor => Self::ResetColor,
+      Component::Weight(weight) => Self::Weight(weight),
+      Component::ResetWeight => Self::ResetWeight,
+      Component::Style(style) => Self::Style(style),
+      Component::ResetStyle => Self::ResetStyle,
+      Component::Underline(underline) => Self::Underline(underline),
+      Component::Size(size) => Self::Size(size),
+      Component::ResetSize => Self::ResetSize,
+      Component::Font(font) => Self::Font(font.to_string()),
+      Component::ResetFont => Self::ResetFont,
+      Component::Offset(offset) => Self::Offset(offset),
+      Component::Rotation(rotation) => Self::Rotation(rotation),
+      Component::Animated(color) => Self::Animated(color),
+    }
+  }
+}
+
+impl From<String> for OwnedComponent {
+  fn from(string: String) -> Self {
+    Self::String(string)
+  }
+}
+
+impl From<&str> for OwnedComponent {
+  fn from(str: &str) -> Self {
+    Self::String(str.to_string())
+  }
+}
+
+impl From<char> for OwnedComponent {
+  fn from(c: char) -> Self {
+    Self::Char(c)
+  }
+}
+
+impl From<Color> for OwnedComponent {
+  fn from(color: Color) -> Self {
+    Self::Color(color)
+  }
+}
+
+impl From<FontWeight> for OwnedComponent {
+  fn from(weight: FontWeight) -> Self {
+    Self::Weight(weight)
+  }
+}
+
+impl From<FontStyle> for OwnedComponent {
+  fn from(style: FontStyle) -> Self {
+    Self::Style(style)
+  }
+}
+
+/// An owned [ColoredStr], useful for text built from formatted strings at runtime
+/// or text that's stored in game state
+///
+/// **Example**
+/// ```rs
+/// let mut text = ColoredString::new();
+///
+/// text.push(format!("{} hit you for ", enemy.name));
+/// text.push(RED);
+/// text.push(damage.to_string());
+///
+/// fonts.draw_colored_text_ex(&text.as_colored_str(), &TextParams::default());
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ColoredString {
+  components: Vec<OwnedComponent>,
+}
+
+impl ColoredString {
+  /// Creates an empty colored string
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a component to the end
+  pub fn push(&mut self, component: impl Into<OwnedComponent>) {
+    self.components.push(component.into());
+  }
+
+  /// Returns every component in order
+  pub fn components(&self) -> &[OwnedComponent] {
+    &self.components
+  }
+
+  /// Borrows this as a [ColoredStr]
+  pub fn as_colored_str(&self) -> ColoredStr<'_> {
+    self
+      .components
+      .iter()
+      .map(OwnedComponent::as_component)
+      .collect()
+  }
+
+  /// Returns the text without any colors
+  pub fn text(&self) -> String {
+    self.as_colored_str().text()
+  }
+}
+
+impl From<ColoredStr<'_>> for ColoredString {
+  fn from(str: ColoredStr<'_>) -> Self {
+    Self::from(&str)
+  }
+}
+
+impl From<&ColoredStr<'_>> for ColoredString {
+  fn from(str: &ColoredStr<'_>) -> Self {
+    str.components().iter().copied().collect()
+  }
+}
+
+impl<'a> From<&'a ColoredString> for ColoredStr<'a> {
+  fn from(string: &'a ColoredString) -> Self {
+    string.as_colored_str()
+  }
+}
+
+impl From<String> for ColoredString {
+  fn from(string: String) -> Self {
+    Self {
+      components: vec![OwnedComponent::String(string)],
+    }
+  }
+}
+
+impl<T: Into<OwnedComponent>> FromIterator<T> for ColoredString {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self {
+      components: iter.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl<T: Into<OwnedComponent>> Extend<T> for ColoredString {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.components.extend(iter.into_iter().map(Into::into));
+  }
+}
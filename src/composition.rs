@@ -0,0 +1,93 @@
+use macroquad::prelude::Color;
+
+use crate::{Caret, Fonts, TextParams};
+
+/// Text that is still being composed by an input method (IME),
+/// also known as pre-edit text, it isn't part of the committed text yet
+///
+/// The app is responsible for getting the composition from the platform,
+/// this only describes how to draw it
+///
+/// **See** [Fonts::draw_text_with_composition]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Composition<'t> {
+  /// The text being composed
+  pub text: &'t str,
+  /// The byte index of the cursor in the composed text
+  pub cursor: usize,
+  /// The color of the composed text, `None` uses the color of the text
+  pub color: Option<Color>,
+}
+
+impl<'t> Composition<'t> {
+  /// Creates a composition with the cursor at the end of the text
+  pub fn new(text: &'t str) -> Self {
+    Self {
+      text,
+      cursor: text.len(),
+      color: None,
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws text with given [TextParams] with the composition of an input method inserted
+  /// at the byte index `index`, the composition is underlined to set it apart
+  /// from the committed text
+  ///
+  /// Returns the caret at the cursor of the composition,
+  /// useful for placing the candidate window of the input method
+  ///
+  /// **Example**
+  /// ```rs
+  /// let caret = fonts.draw_text_with_composition(&text, &params, caret_index, &Composition {
+  ///   text: "にほ",
+  ///   cursor: 6,
+  ///   color: Some(GRAY),
+  /// });
+  ///
+  /// draw_line(caret.x, caret.y, caret.x, caret.y + caret.height, 2.0, WHITE);
+  /// ```
+  pub fn draw_text_with_composition(
+    &self,
+    text: &str,
+    params: &TextParams,
+    index: usize,
+    composition: &Composition,
+  ) -> Caret {
+    let index = index.min(text.len());
+    let (before, after) = text.split_at(index);
+
+    let before_width = self.draw_text_ex(before, params).width;
+    let composition_params = TextParams {
+      x: params.x + before_width,
+      color: composition.color.unwrap_or(params.color),
+      ..*params
+    };
+    let composition_width = self
+      .draw_text_ex(composition.text, &composition_params)
+      .width;
+
+    self.draw_text_ex(
+      after,
+      &TextParams {
+        x: composition_params.x + composition_width,
+        ..*params
+      },
+    );
+
+    self.draw_underline(&composition_params, composition_width);
+
+    let (top, bottom) = self.line_box(params);
+    let cursor = composition.cursor.min(composition.text.len());
+
+    Caret {
+      x: composition_params.x
+        + self
+          .measure_params(&composition.text[..cursor], params)
+          .width,
+      y: top,
+      height: bottom - top,
+    }
+  }
+}
@@ -0,0 +1,108 @@
+use crate::Fonts;
+
+/// What happens to control characters like '\r' and '\0' when text is laid out,
+/// tabs and line breaks aren't affected
+///
+/// They're looked up like any other character by default, like they always were,
+/// pick [Self::Skip] for text from outside the game
+///
+/// **See** [Fonts::set_control_chars]
+#[derive(Default, Debug, Copy, Clone)]
+pub enum ControlChars {
+  /// Control characters take up no space and draw nothing
+  Skip,
+  /// Control characters are drawn as their symbol from the Control Pictures block,
+  /// like '␀' for '\0', useful for debugging strings from outside the game,
+  /// needs a font that contains those symbols
+  Picture,
+  /// Control characters are looked up like any other character,
+  /// which usually draws the missing glyph of the font
+  #[default]
+  Draw,
+  /// Control characters get replaced by the character this returns,
+  /// or skipped if it returns `None`
+  Map(fn(char) -> Option<char>),
+}
+
+impl ControlChars {
+  /// Returns the character drawn in place of a character, `None` if it's skipped
+  pub fn apply(&self, c: char) -> Option<char> {
+    if !c.is_control() || c == '\t' || c == '\n' {
+      return Some(c);
+    }
+
+    match self {
+      Self::Skip => None,
+      Self::Picture => match c {
+        '\0'..='\u{1F}' => char::from_u32(0x2400 + c as u32),
+        '\u{7F}' => Some('\u{2421}'),
+        _ => None,
+      },
+      Self::Draw => Some(c),
+      Self::Map(f) => f(c),
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Sets what happens to control characters like '\r' and '\0' when text is laid out,
+  /// so strings from files, the clipboard or the network don't draw missing glyphs
+  ///
+  /// **Default** [ControlChars::Draw]
+  ///
+  /// **Example**
+  /// ```rs
+  /// // pasted text often has '\r' in it
+  /// fonts.set_control_chars(ControlChars::Skip);
+  ///
+  /// // show where stray control characters are while debugging
+  /// fonts.set_control_chars(ControlChars::Picture);
+  ///
+  /// // or draw them as a middle dot
+  /// fonts.set_control_chars(ControlChars::Map(|_| Some('·')));
+  /// ```
+  pub fn set_control_chars(&mut self, control_chars: ControlChars) {
+    self.control_chars = control_chars;
+    self.layout_cache.get_mut().clear();
+  }
+
+  /// Returns what happens to control characters when text is laid out
+  ///
+  /// **See** [Self::set_control_chars]
+  pub fn control_chars(&self) -> ControlChars {
+    self.control_chars
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn draws_control_chars_by_default() {
+    assert_eq!(ControlChars::default().apply('\r'), Some('\r'));
+  }
+
+  #[test]
+  fn tabs_and_line_breaks_are_kept() {
+    for control_chars in [
+      ControlChars::Skip,
+      ControlChars::Picture,
+      ControlChars::Map(|_| None),
+    ] {
+      assert_eq!(control_chars.apply('\t'), Some('\t'));
+      assert_eq!(control_chars.apply('\n'), Some('\n'));
+      assert_eq!(control_chars.apply('a'), Some('a'));
+    }
+  }
+
+  #[test]
+  fn applies_policy() {
+    assert_eq!(ControlChars::Skip.apply('\0'), None);
+    assert_eq!(ControlChars::Picture.apply('\0'), Some('\u{2400}'));
+    assert_eq!(ControlChars::Picture.apply('\r'), Some('\u{240D}'));
+    assert_eq!(ControlChars::Picture.apply('\u{7F}'), Some('\u{2421}'));
+    assert_eq!(ControlChars::Picture.apply('\u{85}'), None);
+    assert_eq!(ControlChars::Map(|_| Some('·')).apply('\r'), Some('·'));
+  }
+}
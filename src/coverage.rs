@@ -0,0 +1,122 @@
+use std::fmt::{self, Debug, Formatter};
+
+use crate::{misc::is_blank, Fonts, TextParams};
+
+/// A character no loaded font can draw
+///
+/// **See** [Fonts::unsupported_chars]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedChar {
+  pub c: char,
+  /// The byte index of every place the character is at in the text
+  pub indices: Vec<usize>,
+}
+
+/// The function [Fonts::set_missing_glyph_handler] sets
+pub(crate) struct MissingGlyphHandler<'a>(Box<dyn Fn(char) + 'a>);
+
+impl Debug for MissingGlyphHandler<'_> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str("MissingGlyphHandler")
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Returns every character of the text no loaded font can draw, in the order they first show up,
+  /// each with every byte index it's at, useful for checking localization files before shipping
+  ///
+  /// Characters are checked like they're laid out, so skipped control characters
+  /// and combining marks that got composed by normalization aren't reported
+  ///
+  /// **Example**
+  /// ```rs
+  /// for (key, text) in &translations {
+  ///   for missing in fonts.unsupported_chars(text) {
+  ///     println!("{key}: {:?} (U+{:04X}) at {:?}", missing.c, missing.c as u32, missing.indices);
+  ///   }
+  /// }
+  /// ```
+  pub fn unsupported_chars(&self, text: &str) -> Vec<UnsupportedChar> {
+    let mut unsupported = Vec::<UnsupportedChar>::new();
+
+    for (index, c, skipped) in self.layout_chars(text, &TextParams::default()) {
+      let Some(c) = self.control_chars.apply(c).filter(|_| !skipped) else {
+        continue;
+      };
+
+      if c == '\n' || c == '\t' || self.get_index_by_char(c).is_some() {
+        continue;
+      }
+
+      match unsupported.iter_mut().find(|it| it.c == c) {
+        Some(it) => it.indices.push(index),
+        None => unsupported.push(UnsupportedChar {
+          c,
+          indices: vec![index],
+        }),
+      }
+    }
+
+    unsupported
+  }
+
+  /// Sets a function that gets called the first time a character no loaded font contains
+  /// gets looked up, so missing glyphs show up in logs while playtesting
+  /// instead of silently being drawn as the missing glyph of the first font,
+  /// blank characters like spaces are never reported, `None` removes the handler
+  ///
+  /// Setting a handler forgets which characters were already reported,
+  /// the handler can capture state, like a list of characters to show in a debug overlay
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_missing_glyph_handler(Some(Box::new(|c| {
+  ///   eprintln!("missing glyph {c:?} (U+{:04X})", c as u32);
+  /// })));
+  /// ```
+  pub fn set_missing_glyph_handler(&mut self, handler: Option<Box<dyn Fn(char) + 'a>>) {
+    self.missing_glyph_handler = handler.map(MissingGlyphHandler);
+    self.reported_missing.get_mut().clear();
+  }
+
+  /// Calls the missing glyph handler if the character wasn't reported yet
+  pub(crate) fn report_missing(&self, c: char) {
+    let Some(handler) = self.missing_glyph_handler.as_ref().filter(|_| !is_blank(c)) else {
+      return;
+    };
+
+    if self.reported_missing.borrow_mut().insert(c) {
+      (handler.0)(c);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  #[test]
+  fn missing_glyph_handler_can_capture_state() {
+    let mut fonts = Fonts::default();
+    let missing = Rc::new(RefCell::new(Vec::new()));
+    let reported = missing.clone();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts.set_missing_glyph_handler(Some(Box::new(move |c| reported.borrow_mut().push(c))));
+
+    fonts.measure_text_ex("a\u{E000} b\u{E000}\u{E001}", &TextParams::default());
+
+    assert_eq!(*missing.borrow(), ['\u{E000}', '\u{E001}']);
+  }
+}
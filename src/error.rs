@@ -0,0 +1,130 @@
+use std::{
+  error::Error,
+  fmt::{Display, Formatter},
+};
+
+use crate::misc::IoError;
+
+pub type FontResult<T> = Result<T, FontError>;
+
+/// Errors that can happen when loading fonts or drawing text
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FontError {
+  /// Reading a font file failed
+  Io(IoError),
+  /// Fetching a font with macroquad's file loading failed
+  Load(macroquad::Error),
+  /// The font data is in a known format but couldn't be parsed,
+  /// contains the error message from fontdue
+  Parse(&'static str),
+  /// The font data isn't in a format that can be loaded, like WOFF
+  UnsupportedFormat(&'static str),
+  /// No font was found, like a system font that isn't installed
+  NotFound(String),
+  /// There are no fonts loaded to draw text with
+  NoFontsLoaded,
+  /// Loading one of many fonts failed, contains the name of the font that failed
+  ///
+  /// **See** [Fonts::from_fonts](crate::Fonts::from_fonts)
+  Named { name: String, error: Box<FontError> },
+}
+
+impl FontError {
+  /// Checks the magic bytes of font data to catch formats
+  /// fontdue can't read before trying to parse them
+  pub(crate) fn check_format(bytes: &[u8]) -> FontResult<()> {
+    match bytes.get(..4) {
+      Some(b"wOFF") => Err(Self::UnsupportedFormat("WOFF")),
+      Some(b"wOF2") => Err(Self::UnsupportedFormat("WOFF2")),
+      Some([0, 1, 0, 0] | b"OTTO" | b"true" | b"typ1" | b"ttcf") => Ok(()),
+      _ => Err(Self::UnsupportedFormat("unknown")),
+    }
+  }
+}
+
+impl Display for FontError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Io(err) => write!(f, "failed to read font: {err}"),
+      Self::Load(err) => write!(f, "failed to load font: {err}"),
+      Self::Parse(err) => write!(f, "failed to parse font: {err}"),
+      Self::UnsupportedFormat(format) => write!(f, "unsupported font format: {format}"),
+      Self::NotFound(name) => write!(f, "font not found: {name}"),
+      Self::NoFontsLoaded => write!(f, "there is no font currently loaded"),
+      Self::Named { name, error } => write!(f, "{name}: {error}"),
+    }
+  }
+}
+
+impl Error for FontError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      Self::Io(err) => Some(err),
+      Self::Load(err) => Some(err),
+      Self::Named { error, .. } => Some(error.as_ref()),
+      _ => None,
+    }
+  }
+}
+
+impl From<IoError> for FontError {
+  fn from(err: IoError) -> Self {
+    Self::Io(err)
+  }
+}
+
+impl From<macroquad::Error> for FontError {
+  fn from(err: macroquad::Error) -> Self {
+    Self::Load(err)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{ColoredStr, FontError, Fonts, TextParams};
+
+  #[test]
+  fn garbage_bytes_are_an_unsupported_format() {
+    let mut fonts = Fonts::default();
+    let result = fonts.load_font_from_bytes("garbage", &b"definitely not a font"[..]);
+
+    assert!(matches!(
+      result,
+      Err(FontError::UnsupportedFormat("unknown"))
+    ));
+    assert!(fonts.fonts().is_empty());
+  }
+
+  #[test]
+  fn woff_is_an_unsupported_format() {
+    let mut fonts = Fonts::default();
+    let result = fonts.load_font_from_bytes("woff", &b"wOF2\0\0\0\0"[..]);
+
+    assert!(matches!(result, Err(FontError::UnsupportedFormat("WOFF2"))));
+  }
+
+  #[test]
+  fn drawing_without_fonts_does_not_panic() {
+    let fonts = Fonts::default();
+    let params = TextParams::default();
+
+    assert_eq!(fonts.measure_text("Some Text", 22.0).width, 0.0);
+    assert_eq!(fonts.draw_text_ex("Some Text", &params).width, 0.0);
+    assert_eq!(fonts.draw_batch(&[("1", params), ("2", params)]).len(), 2);
+
+    assert!(matches!(
+      fonts.try_draw_text_ex("Some Text", &params),
+      Err(FontError::NoFontsLoaded)
+    ));
+    assert!(matches!(
+      fonts.try_draw_colored_text_ex(&ColoredStr::from("Some Text"), &params),
+      Err(FontError::NoFontsLoaded)
+    ));
+    assert!(matches!(
+      fonts.try_draw_batch(&[("1", params)]),
+      Err(FontError::NoFontsLoaded)
+    ));
+    assert!(fonts.try_draw_text_ex("", &params).is_ok());
+  }
+}
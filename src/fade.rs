@@ -0,0 +1,103 @@
+use crate::{misc::fade_color, ColoredStr, Fonts, MeasuredText, TextParams};
+
+/// How far text has faded in, character by character
+///
+/// **See** [Fonts::draw_text_faded]
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct Fade {
+  /// How many characters are revealed, fractions reveal the next character partially
+  pub revealed: f32,
+  /// Over how many characters the leading edge fades from invisible to fully visible,
+  /// 0 reveals characters all at once
+  pub edge: f32,
+}
+
+impl Fade {
+  /// Fades in text with `chars` characters as `t` goes from 0 to 1,
+  /// nothing is visible at 0 and everything is visible at 1,
+  /// an edge as long as the text fades the whole text in at once
+  ///
+  /// **Example**
+  /// ```rs
+  /// let fade = Fade::over(elapsed / 2.0, text.chars().count(), 6.0);
+  /// ```
+  pub fn over(t: f32, chars: usize, edge: f32) -> Self {
+    Self {
+      revealed: t.clamp(0.0, 1.0) * (chars as f32 + edge),
+      edge,
+    }
+  }
+
+  /// Returns what the alpha of a character gets multiplied by,
+  /// given the number of the character in the text
+  pub fn alpha(&self, number: usize) -> f32 {
+    let behind = self.revealed - number as f32;
+
+    if self.edge <= 0.0 {
+      return if behind > 0.0 { 1.0 } else { 0.0 };
+    }
+
+    (behind / self.edge).clamp(0.0, 1.0)
+  }
+}
+
+/// Fades the colors of params by `alpha`, returns false if nothing is left to draw
+pub(crate) fn fade_params(params: &mut TextParams, alpha: f32) -> bool {
+  params.color = fade_color(params.color, alpha);
+  params.bottom_color = params.bottom_color.map(|it| fade_color(it, alpha));
+
+  alpha > 0.0
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws text with given [TextParams], fading characters in with a [Fade],
+  /// useful for typewriter text with a soft leading edge
+  ///
+  /// Returns the dimensions of the whole text, even the parts that aren't visible
+  ///
+  /// **Example**
+  /// ```rs
+  /// let revealed = (get_time() - start) as f32 * 30.0;
+  ///
+  /// fonts.draw_text_faded(&dialogue, &params, Fade { revealed, edge: 4.0 });
+  /// ```
+  pub fn draw_text_faded(&self, text: &str, params: &TextParams, fade: Fade) -> MeasuredText {
+    self.cache_text_glyphs(text, params);
+    self.write_text(text, params, &mut self.draw_sink(params), |number, params| {
+      fade_params(params, fade.alpha(number))
+    })
+  }
+
+  /// Draws a [ColoredStr] with given [TextParams] like [Self::draw_colored_text_ex],
+  /// fading characters in with a [Fade] across every span
+  pub fn draw_colored_text_faded(
+    &self,
+    text: &ColoredStr,
+    params: &TextParams,
+    fade: Fade,
+  ) -> MeasuredText {
+    let mut x = params.x;
+    let mut number = 0;
+
+    for span in text.spans(params) {
+      let params = self.span_params(&span, params, x);
+      let width = self
+        .draw_span(&span, &params, |it| fade.alpha(number + it))
+        .width;
+
+      if span.underline {
+        let params = TextParams {
+          color: fade_color(params.color, fade.alpha(number)),
+          ..params
+        };
+
+        self.draw_underline(&params, width);
+      }
+
+      number += span.text.chars().count();
+      x += width;
+    }
+
+    self.measure_colored_text(text, params)
+  }
+}
@@ -0,0 +1,108 @@
+/// The weight of a font face
+///
+/// **Default** [FontWeight::Regular]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontWeight {
+  /// this is the default
+  #[default]
+  Regular,
+  Bold,
+}
+
+/// The style of a font face
+///
+/// **Default** [FontStyle::Normal]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontStyle {
+  /// this is the default
+  #[default]
+  Normal,
+  Italic,
+}
+
+/// A face inside a font family, a combination of [FontWeight] and [FontStyle]
+///
+/// **Default** regular and normal
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FontFace {
+  pub weight: FontWeight,
+  pub style: FontStyle,
+}
+
+impl FontFace {
+  pub const REGULAR: Self = Self::new(FontWeight::Regular, FontStyle::Normal);
+  pub const BOLD: Self = Self::new(FontWeight::Bold, FontStyle::Normal);
+  pub const ITALIC: Self = Self::new(FontWeight::Regular, FontStyle::Italic);
+  pub const BOLD_ITALIC: Self = Self::new(FontWeight::Bold, FontStyle::Italic);
+
+  pub const fn new(weight: FontWeight, style: FontStyle) -> Self {
+    Self { weight, style }
+  }
+
+  /// Checks if this is the regular face, only regular faces are used for fallback lookups
+  pub fn is_regular(&self) -> bool {
+    *self == Self::REGULAR
+  }
+
+  /// Returns the faces that can be used for this face from closest to furthest,
+  /// without the regular face, bold italic can be drawn with the bold face and a synthetic italic
+  /// or the italic face and a synthetic bold
+  pub(crate) fn closest_faces(&self) -> impl Iterator<Item = FontFace> {
+    let partial = (*self == Self::BOLD_ITALIC).then_some([Self::BOLD, Self::ITALIC]);
+
+    std::iter::once(*self)
+      .chain(partial.into_iter().flatten())
+      .filter(|it| !it.is_regular())
+  }
+
+  /// Returns what has to be synthesized to turn the `available` face into this face
+  pub(crate) fn synthesize_from(&self, available: FontFace) -> FontFace {
+    FontFace {
+      weight: if self.weight > available.weight {
+        self.weight
+      } else {
+        FontWeight::Regular
+      },
+      style: if self.style > available.style {
+        self.style
+      } else {
+        FontStyle::Normal
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bold_italic_falls_back_to_partial_faces() {
+    let closest = FontFace::BOLD_ITALIC.closest_faces().collect::<Vec<_>>();
+
+    assert_eq!(
+      closest,
+      [FontFace::BOLD_ITALIC, FontFace::BOLD, FontFace::ITALIC]
+    );
+    assert_eq!(
+      FontFace::BOLD.closest_faces().collect::<Vec<_>>(),
+      [FontFace::BOLD]
+    );
+    assert_eq!(FontFace::REGULAR.closest_faces().count(), 0);
+  }
+
+  #[test]
+  fn only_missing_parts_are_synthesized() {
+    let face = FontFace::BOLD_ITALIC;
+
+    assert_eq!(face.synthesize_from(FontFace::BOLD), FontFace::ITALIC);
+    assert_eq!(face.synthesize_from(FontFace::ITALIC), FontFace::BOLD);
+    assert_eq!(
+      face.synthesize_from(FontFace::REGULAR),
+      FontFace::BOLD_ITALIC
+    );
+    assert_eq!(face.synthesize_from(face), FontFace::REGULAR);
+  }
+}
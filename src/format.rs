@@ -0,0 +1,103 @@
+use std::fmt::{self, Arguments, Display, Write};
+
+use macroquad::prelude::TextDimensions;
+
+use crate::{Fonts, MeasuredText, TextParams};
+
+/// Draws every piece of text written to it right after the previous one
+struct DrawWriter<'f, 'a> {
+  fonts: &'f Fonts<'a>,
+  params: TextParams,
+  /// The bounds and glyphs of everything written so far
+  measured: MeasuredText,
+  width: f32,
+  /// How far the text reaches above and below the baseline
+  above: f32,
+  below: f32,
+}
+
+impl Write for DrawWriter<'_, '_> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    if s.is_empty() {
+      return Ok(());
+    }
+
+    let params = TextParams {
+      x: self.params.x + self.width,
+      ..self.params
+    };
+    let dimensions = self
+      .fonts
+      .draw_text_to_sink(s, &params, &mut self.fonts.draw_sink(&params));
+
+    self.measured.add(&dimensions);
+    self.width += dimensions.width;
+    self.above = self.above.max(dimensions.offset_y);
+    self.below = self.below.max(dimensions.height - dimensions.offset_y);
+
+    Ok(())
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Formats text into a buffer that's reused between calls and draws it
+  /// with given [TextParams], so text that changes every frame doesn't allocate every frame
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.draw_fmt(&params, format_args!("score: {}", score));
+  /// ```
+  ///
+  /// **See** [draw_text!](crate::draw_text)
+  pub fn draw_fmt(&self, params: &TextParams, args: Arguments) -> MeasuredText {
+    // taken out so formatting can't observe a borrowed buffer
+    let mut buffer = self.format_buffer.take();
+
+    buffer.clear();
+    buffer
+      .write_fmt(args)
+      .expect("formatting into a String can't fail");
+
+    let dimensions = self.draw_text_ex(&buffer, params);
+
+    self.format_buffer.replace(buffer);
+    dimensions
+  }
+
+  /// Draws anything that implements [Display] with given [TextParams],
+  /// the text is drawn while it's being formatted without allocating a string
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.draw_display(&get_fps(), &params);
+  /// ```
+  pub fn draw_display(&self, value: &impl Display, params: &TextParams) -> MeasuredText {
+    let mut writer = DrawWriter {
+      fonts: self,
+      params: *params,
+      measured: MeasuredText::empty(params),
+      width: 0.0,
+      above: f32::MIN,
+      below: f32::MIN,
+    };
+
+    // an error from the Display impl only stops the rest from being drawn
+    let _ = write!(writer, "{value}");
+
+    let dimensions = match writer.above == f32::MIN {
+      true => TextDimensions::default(),
+      false => TextDimensions {
+        width: writer.width,
+        height: writer.above + writer.below,
+        offset_y: writer.above,
+      },
+    };
+
+    // every piece is written on the same line
+    MeasuredText {
+      lines: 1,
+      ..writer.measured
+    }
+    .with_dimensions(dimensions, params)
+  }
+}
@@ -0,0 +1,79 @@
+//! An optional global [Fonts] instance with free functions mirroring macroquad's,
+//! so small games and examples don't have to pass `&Fonts` everywhere
+//!
+//! **Example**
+//! ```rs
+//! use macroquad_text::global;
+//!
+//! global::set_default_fonts(Fonts::from_fonts([("Noto Sans", NOTO_SANS)])?);
+//!
+//! loop {
+//!   global::draw_text("Hello, World!", 20.0, 20.0, 22.0, WHITE);
+//!   next_frame().await;
+//! }
+//! ```
+
+use std::cell::RefCell;
+
+use macroquad::prelude::Color;
+
+use crate::{Fonts, LocalFonts, MeasuredText, TextParams};
+
+thread_local! {
+  static DEFAULT_FONTS: RefCell<Option<LocalFonts<'static>>> = const { RefCell::new(None) };
+}
+
+/// Sets the fonts used by the free functions of this module, replacing the previous ones,
+/// the default fonts are per thread, set them on the thread macroquad draws on
+///
+/// Fonts can also be given as a [LocalFonts] handle to keep using them directly
+pub fn set_default_fonts(fonts: impl Into<LocalFonts<'static>>) {
+  DEFAULT_FONTS.with(|it| *it.borrow_mut() = Some(fonts.into()));
+}
+
+/// Removes the default fonts and returns them
+pub fn take_default_fonts() -> Option<LocalFonts<'static>> {
+  DEFAULT_FONTS.with(|it| it.borrow_mut().take())
+}
+
+/// Returns a handle to the default fonts, `None` if they weren't set
+pub fn default_fonts() -> Option<LocalFonts<'static>> {
+  DEFAULT_FONTS.with(|it| it.borrow().clone())
+}
+
+/// Runs a function with the default fonts
+///
+/// **Panics** if the default fonts weren't set
+pub fn with_default_fonts<R>(f: impl FnOnce(&Fonts<'static>) -> R) -> R {
+  let fonts = default_fonts().expect("default fonts are not set, call set_default_fonts first");
+  let fonts = fonts.borrow();
+
+  f(&fonts)
+}
+
+/// Draws text with the default fonts
+///
+/// **Panics** if the default fonts weren't set
+///
+/// **See** [Fonts::draw_text]
+pub fn draw_text(text: &str, x: f32, y: f32, size: f32, color: Color) -> MeasuredText {
+  with_default_fonts(|fonts| fonts.draw_text(text, x, y, size, color))
+}
+
+/// Draws text with the default fonts
+///
+/// **Panics** if the default fonts weren't set
+///
+/// **See** [Fonts::draw_text_ex]
+pub fn draw_text_ex(text: &str, params: &TextParams) -> MeasuredText {
+  with_default_fonts(|fonts| fonts.draw_text_ex(text, params))
+}
+
+/// Measures text with the default fonts
+///
+/// **Panics** if the default fonts weren't set
+///
+/// **See** [Fonts::measure_text]
+pub fn measure_text(text: &str, size: f32) -> MeasuredText {
+  with_default_fonts(|fonts| fonts.measure_text(text, size))
+}
@@ -0,0 +1,249 @@
+use std::rc::Rc;
+
+use fontdue::Metrics;
+use macroquad::{
+  miniquad::TextureId,
+  prelude::{Rect, Texture2D},
+};
+
+use crate::{atlas::Atlases, Font, Fonts};
+
+/// The metrics and coverage of a rasterized glyph
+pub(crate) type GlyphBitmap = (Metrics, Rc<[u8]>);
+
+/// A glyph cached in the atlas of a [Font]
+///
+/// **See** [Font::cached_glyph]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GlyphEntry {
+  /// The index of the atlas the glyph is in, always 0 unless
+  /// [AtlasConfig::large_glyph_size](crate::AtlasConfig::large_glyph_size) is set
+  ///
+  /// **See** [Font::atlas_texture_at]
+  pub atlas: usize,
+  /// Where the glyph is inside the atlas in pixels
+  pub source: Rect,
+  /// Where the glyph is inside the atlas in normalized (0..1) coordinates
+  pub uv: Rect,
+  /// How far the left edge of the glyph is from the pen in pixels
+  pub offset_x: f32,
+  /// How far the bottom edge of the glyph is above the baseline in pixels
+  pub offset_y: f32,
+  /// How far the pen moves after this glyph in pixels
+  pub advance: f32,
+}
+
+/// A glyph in an [AtlasSnapshot]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SnapshotGlyph {
+  pub c: char,
+  /// The size the glyph is cached at
+  pub size: u16,
+  pub glyph: GlyphEntry,
+}
+
+/// A copy of the glyphs cached in the atlas of a [Font] and the texture they're in,
+/// for drawing them with your own miniquad pipeline or egui-macroquad
+/// so everything shares a single glyph cache
+///
+/// Glyphs cached after the snapshot was taken aren't in it, glyphs move and the texture
+/// gets replaced when the atlas grows or gets cleared, which changes [Font::atlas_generation]
+///
+/// **See** [Fonts::atlas_snapshots]
+#[derive(Debug, Clone)]
+pub struct AtlasSnapshot {
+  /// The index of the font the atlas belongs to
+  pub font: usize,
+  /// The index of the atlas in the font
+  pub atlas: usize,
+  pub texture: Texture2D,
+  /// The miniquad id of [Self::texture]
+  pub texture_id: TextureId,
+  pub width: u16,
+  pub height: u16,
+  /// The [Font::atlas_generation] when the snapshot was taken
+  pub generation: u64,
+  pub glyphs: Vec<SnapshotGlyph>,
+}
+
+impl<'a> Fonts<'a> {
+  /// Returns a snapshot of every atlas of every font in load order,
+  /// glyphs that aren't cached yet can be cached with [Self::cache_str] first
+  ///
+  /// This needs a window
+  ///
+  /// **Example**
+  /// ```rs
+  /// for atlas in fonts.atlas_snapshots() {
+  ///   for it in &atlas.glyphs {
+  ///     my_pipeline.add_glyph(atlas.texture_id, it.c, it.size, it.glyph.uv, it.glyph.advance);
+  ///   }
+  /// }
+  /// ```
+  pub fn atlas_snapshots(&self) -> Vec<AtlasSnapshot> {
+    self
+      .fonts
+      .iter()
+      .enumerate()
+      .flat_map(|(index, font)| font.atlas_snapshots(index))
+      .collect()
+  }
+}
+
+impl<'a> Font<'a> {
+  /// Returns a snapshot of the glyphs cached in every atlas of this font
+  pub(crate) fn atlas_snapshots(&self, font: usize) -> Vec<AtlasSnapshot> {
+    let mut glyphs = self
+      .cached_chars()
+      .into_iter()
+      .filter_map(|(c, size)| {
+        let glyph = self.cached_glyph(c, size)?;
+
+        Some(SnapshotGlyph { c, size, glyph })
+      })
+      .collect::<Vec<_>>();
+
+    glyphs.sort_by_key(|it| (it.c, it.size));
+
+    (0..self.atlas_count())
+      .filter_map(|atlas| {
+        let texture = self.atlas_texture_at(atlas)?;
+        let (width, height) = self.atlas_size_at(atlas)?;
+
+        Some(AtlasSnapshot {
+          font,
+          atlas,
+          texture_id: texture.raw_miniquad_id(),
+          texture,
+          width,
+          height,
+          generation: self.atlas_generation(),
+          glyphs: glyphs
+            .iter()
+            .filter(|it| it.glyph.atlas == atlas)
+            .copied()
+            .collect(),
+        })
+      })
+      .collect()
+  }
+
+  /// Returns the atlas texture glyphs of this font are cached in,
+  /// the texture gets replaced when the atlas grows, so don't hold on to it between frames,
+  /// with [AtlasConfig::large_glyph_size](crate::AtlasConfig::large_glyph_size)
+  /// this is the atlas of the smaller glyphs
+  ///
+  /// This needs a window
+  pub fn atlas_texture(&self) -> Texture2D {
+    self.atlas_texture_at(0).unwrap()
+  }
+
+  /// Returns the texture of the atlas at an index, `None` if there's no atlas at that index
+  ///
+  /// This needs a window
+  ///
+  /// **See** [GlyphEntry::atlas]
+  pub fn atlas_texture_at(&self, index: usize) -> Option<Texture2D> {
+    Some(self.atlases.borrow_mut().nth_mut(index)?.texture().clone())
+  }
+
+  /// Returns how many atlases glyphs of this font are split across
+  pub fn atlas_count(&self) -> usize {
+    self.atlases.borrow().len()
+  }
+
+  /// Returns a number that changes every time glyphs move around in any atlas of this font,
+  /// unique across all fonts
+  pub fn atlas_generation(&self) -> u64 {
+    let atlases = self.atlases.borrow();
+
+    atlases
+      .iter()
+      .map(|it| it.generation)
+      .max()
+      .unwrap_or_default()
+  }
+
+  /// Returns the width and height of the atlas in pixels, with
+  /// [AtlasConfig::large_glyph_size](crate::AtlasConfig::large_glyph_size)
+  /// this is the atlas of the smaller glyphs
+  pub fn atlas_size(&self) -> (u16, u16) {
+    self.atlas_size_at(0).unwrap_or_default()
+  }
+
+  /// Returns the width and height of the atlas at an index in pixels,
+  /// `None` if there's no atlas at that index
+  pub fn atlas_size_at(&self, index: usize) -> Option<(u16, u16)> {
+    let atlases = self.atlases.borrow();
+    let atlas = atlases.nth(index)?;
+
+    Some((atlas.width(), atlas.height()))
+  }
+
+  /// Gets a glyph cached at a given size, `None` if it isn't cached
+  ///
+  /// **Example**
+  /// ```rs
+  /// let font = fonts.get_font_by_char_or_panic('A');
+  ///
+  /// font.cache_glyph('A', 22);
+  ///
+  /// let glyph = font.cached_glyph('A', 22).unwrap();
+  /// let texture = font.atlas_texture();
+  ///
+  /// // draw the glyph with your own mesh or particles using glyph.uv
+  /// ```
+  pub fn cached_glyph(&self, c: char, size: u16) -> Option<GlyphEntry> {
+    let info = *self.chars.borrow().get(&(c, size))?;
+    let atlases = self.atlases.borrow();
+    let source = atlases.get(info.id)?.rect;
+    let atlas = atlases.atlas(info.id);
+    let (width, height) = (atlas.width() as f32, atlas.height() as f32);
+
+    Some(GlyphEntry {
+      atlas: Atlases::index_of(info.id),
+      source,
+      uv: Rect::new(
+        source.x / width,
+        source.y / height,
+        source.w / width,
+        source.h / height,
+      ),
+      offset_x: info.offset_x,
+      offset_y: info.offset_y,
+      advance: info.advance,
+    })
+  }
+
+  /// Returns every cached character and the size it's cached at
+  pub fn cached_chars(&self) -> Vec<(char, u16)> {
+    self.chars.borrow().keys().copied().collect()
+  }
+
+  /// Returns the coverage bitmap of a glyph at a given size, one byte per pixel row by row,
+  /// useful for effects like dissolving text into particles,
+  /// bitmaps are cached separately from the atlas, so asking again doesn't rasterize again
+  ///
+  /// **Example**
+  /// ```rs
+  /// let (metrics, bitmap) = font.glyph_bitmap('A', 64);
+  ///
+  /// for (i, coverage) in bitmap.iter().enumerate() {
+  ///   if *coverage > 128 {
+  ///     spawn_particle(x + (i % metrics.width) as f32, y + (i / metrics.width) as f32);
+  ///   }
+  /// }
+  /// ```
+  pub fn glyph_bitmap(&self, c: char, size: u16) -> (Metrics, Rc<[u8]>) {
+    self
+      .bitmaps
+      .borrow_mut()
+      .entry((c, size))
+      .or_insert_with(|| {
+        let (metrics, bitmap) = self.rasterize(c, size as f32);
+
+        (metrics, bitmap.into())
+      })
+      .clone()
+  }
+}
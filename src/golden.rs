@@ -0,0 +1,245 @@
+//! Renders text into CPU images and compares them against reference PNGs,
+//! meant for regression testing how text looks without a window
+
+use std::path::Path;
+
+use macroquad::prelude::{Color, Image};
+
+use crate::{misc::mix_color, FontStyle, Fonts, TextParams};
+
+/// How much two images differ
+///
+/// **See** [compare_images]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ImageDiff {
+  /// How many pixels have a channel that differs by more than the tolerance
+  pub mismatched: usize,
+  /// The largest difference of a single channel
+  pub max_difference: u8,
+}
+
+impl ImageDiff {
+  /// Returns true if no pixel differs by more than the tolerance
+  pub fn is_match(&self) -> bool {
+    self.mismatched == 0
+  }
+}
+
+/// Compares two images channel by channel,
+/// returns `None` if the images aren't the same size
+///
+/// `tolerance` is how much a channel can differ before its pixel counts as mismatched,
+/// small tolerances keep tests from failing on rounding differences
+pub fn compare_images(actual: &Image, expected: &Image, tolerance: u8) -> Option<ImageDiff> {
+  if (actual.width, actual.height) != (expected.width, expected.height) {
+    return None;
+  }
+
+  let mut diff = ImageDiff::default();
+
+  for (a, b) in actual.bytes.chunks(4).zip(expected.bytes.chunks(4)) {
+    let difference = a
+      .iter()
+      .zip(b)
+      .map(|(a, b)| a.abs_diff(*b))
+      .max()
+      .unwrap_or(0);
+
+    diff.max_difference = diff.max_difference.max(difference);
+
+    if difference > tolerance {
+      diff.mismatched += 1;
+    }
+  }
+
+  Some(diff)
+}
+
+/// Loads an image from a PNG file
+pub fn load_png(path: impl AsRef<Path>) -> image::ImageResult<Image> {
+  let image = image::open(path)?.to_rgba8();
+
+  Ok(Image {
+    width: image.width() as u16,
+    height: image.height() as u16,
+    bytes: image.into_raw(),
+  })
+}
+
+/// Saves an image as a PNG file
+pub fn save_png(image: &Image, path: impl AsRef<Path>) -> image::ImageResult<()> {
+  image::save_buffer(
+    path,
+    &image.bytes,
+    image.width as u32,
+    image.height as u32,
+    image::ColorType::Rgba8,
+  )
+}
+
+/// Compares an image against the reference PNG at `path` and panics if they differ,
+/// the image is saved next to the reference with `.actual.png` at the end when they do
+///
+/// The reference is written instead when it doesn't exist yet
+/// or the `UPDATE_GOLDEN` environment variable is set
+///
+/// **Example**
+/// ```rs
+/// #[test]
+/// fn title() {
+///   let mut fonts = Fonts::default();
+///   fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+///
+///   let image = fonts.render_to_image("Title", &TextParams::default(), 80, 32);
+///
+///   assert_golden(&image, "tests/golden/title.png", 2);
+/// }
+/// ```
+pub fn assert_golden(image: &Image, path: impl AsRef<Path>, tolerance: u8) {
+  let path = path.as_ref();
+
+  if !path.exists() || std::env::var_os("UPDATE_GOLDEN").is_some() {
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent).expect("failed to create golden image directory");
+    }
+
+    save_png(image, path).expect("failed to save golden image");
+    return;
+  }
+
+  let expected = load_png(path).expect("failed to load golden image");
+  let actual_path = path.with_extension("actual.png");
+
+  match compare_images(image, &expected, tolerance) {
+    Some(diff) if diff.is_match() => {
+      let _ = std::fs::remove_file(actual_path);
+    }
+    diff => {
+      let _ = save_png(image, &actual_path);
+
+      match diff {
+        Some(diff) => panic!(
+          "{} pixels differ from {} by up to {}, see {}",
+          diff.mismatched,
+          path.display(),
+          diff.max_difference,
+          actual_path.display(),
+        ),
+        None => panic!(
+          "image is {}x{} but {} is {}x{}, see {}",
+          image.width,
+          image.height,
+          path.display(),
+          expected.width,
+          expected.height,
+          actual_path.display(),
+        ),
+      }
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws text into a transparent image of a given size on the CPU instead of on screen,
+  /// [TextParams::x] and [TextParams::y] are relative to the top left of the image
+  ///
+  /// Glyphs are sampled without filtering, so this is close to but not exactly
+  /// what gets drawn on screen, it doesn't need a window
+  ///
+  /// **See** [assert_golden]
+  pub fn render_to_image(&self, text: &str, params: &TextParams, width: u16, height: u16) -> Image {
+    let mut image = Image::gen_image_color(width, height, Color::new(0.0, 0.0, 0.0, 0.0));
+
+    self.layout_glyphs(text, params, true, |_, _, placement| {
+      let Some(source) = placement.sprite else {
+        return;
+      };
+
+      let dest = placement.rect;
+
+      if dest.w <= 0.0 || dest.h <= 0.0 {
+        return;
+      }
+
+      let atlases = placement.font.atlases.borrow();
+      let atlas = atlases.atlas(placement.id);
+      let bold_offset = Self::synthetic_bold_offset(placement.synthesized, params);
+      let skew = match placement.synthesized.style {
+        FontStyle::Italic => dest.h * 0.2,
+        FontStyle::Normal => 0.0,
+      };
+
+      for offset in [bold_offset, 0.0] {
+        let left = (dest.x + offset).floor().max(0.0) as u32;
+        let right = (dest.right() + offset + skew).ceil().min(width as f32) as u32;
+        let top = dest.y.floor().max(0.0) as u32;
+        let bottom = dest.bottom().ceil().min(height as f32) as u32;
+
+        for y in top..bottom {
+          let fy = (y as f32 + 0.5 - dest.y) / dest.h;
+
+          if !(0.0..1.0).contains(&fy) {
+            continue;
+          }
+
+          for x in left..right {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+
+            if params
+              .clip
+              .is_some_and(|clip| !clip.contains((px, py).into()))
+            {
+              continue;
+            }
+
+            let fx = (px - dest.x - offset - skew * (1.0 - fy)) / dest.w;
+
+            if !(0.0..1.0).contains(&fx) {
+              continue;
+            }
+
+            let coverage = atlas
+              .image()
+              .get_pixel(
+                (source.x + fx * source.w) as u32,
+                (source.y + fy * source.h) as u32,
+              )
+              .a;
+            let color = match params.bottom_color {
+              Some(bottom) => mix_color(params.color, bottom, fy),
+              None => params.color,
+            };
+
+            blend(&mut image, x, y, color, coverage);
+          }
+        }
+      }
+    });
+
+    image
+  }
+}
+
+/// Draws a color over a pixel with a given coverage
+fn blend(image: &mut Image, x: u32, y: u32, color: Color, coverage: f32) {
+  let alpha = color.a * coverage;
+
+  if alpha <= 0.0 {
+    return;
+  }
+
+  let under = image.get_pixel(x, y);
+  let out_alpha = alpha + under.a * (1.0 - alpha);
+  let channel = |src: f32, dst: f32| (src * alpha + dst * under.a * (1.0 - alpha)) / out_alpha;
+
+  image.set_pixel(
+    x,
+    y,
+    Color::new(
+      channel(color.r, under.r),
+      channel(color.g, under.g),
+      channel(color.b, under.b),
+      out_alpha,
+    ),
+  );
+}
@@ -0,0 +1,83 @@
+use macroquad::prelude::Color;
+
+use crate::{ColoredStr, Component};
+
+impl<'a> ColoredStr<'a> {
+  /// Builds a [ColoredStr] by asking `f` for the color of every word,
+  /// words are runs of letters, digits, `_` and `'`,
+  /// words `f` returns `None` for and everything between words keep the color of the text
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = ColoredStr::color_words("Alice hit the goblin for 12 damage", |word| match word {
+  ///   "Alice" => Some(SKYBLUE),
+  ///   "goblin" => Some(RED),
+  ///   _ if word.parse::<u32>().is_ok() => Some(YELLOW),
+  ///   _ => None,
+  /// });
+  ///
+  /// fonts.draw_colored_text_ex(&text, &TextParams::default());
+  /// ```
+  pub fn color_words(text: &'a str, mut f: impl FnMut(&str) -> Option<Color>) -> Self {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+    let mut colored = ColoredStr::new();
+    let mut plain_start = 0;
+    let mut rest = text;
+
+    while let Some(start) = rest.find(is_word) {
+      let end = rest[start..]
+        .find(|c| !is_word(c))
+        .map_or(rest.len(), |it| start + it);
+      let offset = text.len() - rest.len();
+
+      if let Some(color) = f(&rest[start..end]) {
+        colored.push_plain(&text[plain_start..offset + start]);
+        colored.extend([
+          Component::Color(color),
+          Component::Str(&rest[start..end]),
+          Component::ResetColor,
+        ]);
+        plain_start = offset + end;
+      }
+
+      rest = &rest[end..];
+    }
+
+    colored.push_plain(&text[plain_start..]);
+    colored
+  }
+
+  /// Builds a [ColoredStr] where every occurrence of `pattern` has a color,
+  /// useful for highlighting search matches
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = ColoredStr::color_matches(&description, &search, YELLOW);
+  /// ```
+  pub fn color_matches(text: &'a str, pattern: &str, color: Color) -> Self {
+    let mut colored = ColoredStr::new();
+    let mut plain_start = 0;
+
+    if !pattern.is_empty() {
+      for (start, found) in text.match_indices(pattern) {
+        colored.push_plain(&text[plain_start..start]);
+        colored.extend([
+          Component::Color(color),
+          Component::Str(found),
+          Component::ResetColor,
+        ]);
+        plain_start = start + found.len();
+      }
+    }
+
+    colored.push_plain(&text[plain_start..]);
+    colored
+  }
+
+  /// Adds text without changing the color, if it isn't empty
+  fn push_plain(&mut self, text: &'a str) {
+    if !text.is_empty() {
+      self.push(Component::Str(text));
+    }
+  }
+}
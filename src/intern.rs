@@ -0,0 +1,247 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{quad::QuadSink, Fonts, GlyphQuad, MeasuredText, TextParams};
+
+/// A handle to text interned by [Fonts::intern],
+/// only meaningful for the [Fonts] that interned it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InternedText(u32);
+
+/// Stores every interned string once
+#[derive(Debug, Default)]
+pub(crate) struct Interner {
+  ids: HashMap<Rc<str>, u32>,
+  texts: Vec<Rc<str>>,
+}
+
+/// The quads interned text was drawn with for some [TextParams]
+#[derive(Debug)]
+pub(crate) struct CachedQuads {
+  params: TextParams,
+  quads: Vec<GlyphQuad>,
+  dimensions: MeasuredText,
+  /// The generation of the atlas of every font when the quads were made,
+  /// the quads are outdated when any of them changed
+  generations: Vec<u64>,
+}
+
+impl<'a> Fonts<'a> {
+  /// How many different [TextParams] the quads of an interned text are remembered for
+  const INTERNED_PARAMS: usize = 4;
+
+  /// Interns text, so drawing it with [Self::draw_interned] can reuse
+  /// the quads it was drawn with the last time, interning the same text again
+  /// returns the same handle
+  ///
+  /// Interned text is kept until [Fonts] is dropped,
+  /// so only intern text that doesn't change, like HUD labels
+  ///
+  /// **Example**
+  /// ```rs
+  /// let score_label = fonts.intern("Score");
+  ///
+  /// loop {
+  ///   fonts.draw_interned(score_label, &params);
+  ///   next_frame().await;
+  /// }
+  /// ```
+  pub fn intern(&self, text: &str) -> InternedText {
+    let mut interner = self.interner.borrow_mut();
+
+    if let Some(id) = interner.ids.get(text) {
+      return InternedText(*id);
+    }
+
+    let id = interner.texts.len() as u32;
+    let text = Rc::<str>::from(text);
+
+    interner.texts.push(text.clone());
+    interner.ids.insert(text, id);
+
+    InternedText(id)
+  }
+
+  /// Returns the text of an interned handle,
+  /// `None` if the handle came from another [Fonts]
+  pub fn interned(&self, text: InternedText) -> Option<Rc<str>> {
+    self.interner.borrow().texts.get(text.0 as usize).cloned()
+  }
+
+  /// Draws interned text with given [TextParams] like [Self::draw_text_ex],
+  /// when it was drawn with the same params before and the atlases didn't change since,
+  /// the quads from back then are drawn again without laying out the text
+  ///
+  /// The quads are remembered for the last few params each text was drawn with
+  ///
+  /// **See** [Self::intern]
+  pub fn draw_interned(&self, text: InternedText, params: &TextParams) -> MeasuredText {
+    let Some(string) = self.interned(text) else {
+      return MeasuredText::empty(params);
+    };
+
+    let generations = self
+      .fonts
+      .iter()
+      .map(|font| font.atlas_generation())
+      .collect::<Vec<_>>();
+
+    {
+      let cache = self.layout_cache.borrow();
+      let cached = cache.quads.get(&text.0).and_then(|it| {
+        it.iter()
+          .find(|it| it.params == *params && it.generations == generations)
+      });
+
+      if let Some(cached) = cached {
+        let mut sink = self.draw_sink(params);
+
+        for quad in &cached.quads {
+          sink.push_quad(quad.clone());
+        }
+
+        return cached.dimensions;
+      }
+    }
+
+    let mut quads = Vec::new();
+
+    self.cache_text_glyphs(&string, params);
+    let dimensions = self.draw_text_to_sink(&string, params, &mut |quad| quads.push(quad));
+
+    let mut sink = self.draw_sink(params);
+
+    for quad in &quads {
+      sink.push_quad(quad.clone());
+    }
+
+    drop(sink);
+
+    // caching glyphs can grow the atlases
+    let generations = self
+      .fonts
+      .iter()
+      .map(|font| font.atlas_generation())
+      .collect();
+    let mut cache = self.layout_cache.borrow_mut();
+    let entries = cache.quads.entry(text.0).or_default();
+
+    entries.retain(|it| it.params != *params);
+
+    if entries.len() >= Self::INTERNED_PARAMS {
+      entries.remove(0);
+    }
+
+    entries.push(CachedQuads {
+      params: *params,
+      quads,
+      dimensions,
+      generations,
+    });
+
+    dimensions
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::prelude::Rect;
+
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  /// Params whose glyphs are all clipped away, so no quad needs an atlas texture
+  fn clipped(size: f32) -> TextParams {
+    TextParams {
+      size,
+      clip: Some(Rect::new(-100.0, -100.0, 1.0, 1.0)),
+      ..Default::default()
+    }
+  }
+
+  /// Changes the remembered dimensions of interned text, so reusing them can be told apart
+  /// from laying the text out again
+  fn poison(fonts: &Fonts, text: InternedText) {
+    for cached in fonts
+      .layout_cache
+      .borrow_mut()
+      .quads
+      .get_mut(&text.0)
+      .unwrap()
+    {
+      cached.dimensions.width = -1.0;
+    }
+  }
+
+  #[test]
+  fn interning_the_same_text_returns_the_same_handle() {
+    let fonts = fonts();
+    let score = fonts.intern("Score");
+
+    assert_eq!(fonts.intern("Score"), score);
+    assert_ne!(fonts.intern("Lives"), score);
+    assert_eq!(fonts.interned(score).as_deref(), Some("Score"));
+    assert_eq!(Fonts::default().interned(score), None);
+  }
+
+  #[test]
+  fn drawing_again_reuses_the_quads() {
+    let fonts = fonts();
+    let score = fonts.intern("Score");
+    let drawn = fonts.draw_interned(score, &clipped(20.0));
+
+    assert_eq!(drawn, fonts.measure_text_metrics("Score", &clipped(20.0)));
+
+    poison(&fonts, score);
+
+    assert_eq!(fonts.draw_interned(score, &clipped(20.0)).width, -1.0);
+    // other params are laid out
+    assert_ne!(fonts.draw_interned(score, &clipped(30.0)).width, -1.0);
+  }
+
+  #[test]
+  fn atlas_changes_invalidate_the_quads() {
+    let fonts = fonts();
+    let score = fonts.intern("Score");
+
+    fonts.draw_interned(score, &clipped(20.0));
+    poison(&fonts, score);
+    // clearing the atlases moves every glyph
+    fonts.clear_cache();
+
+    assert_ne!(fonts.draw_interned(score, &clipped(20.0)).width, -1.0);
+    assert_eq!(fonts.layout_cache.borrow().quads[&score.0].len(), 1);
+  }
+
+  #[test]
+  fn only_the_last_few_params_are_remembered() {
+    let fonts = fonts();
+    let score = fonts.intern("Score");
+
+    for size in [10.0, 20.0, 30.0, 40.0, 50.0] {
+      fonts.draw_interned(score, &clipped(size));
+    }
+
+    let cache = fonts.layout_cache.borrow();
+    let sizes = cache.quads[&score.0]
+      .iter()
+      .map(|it| it.params.size)
+      .collect::<Vec<_>>();
+
+    assert_eq!(sizes, [20.0, 30.0, 40.0, 50.0]);
+  }
+}
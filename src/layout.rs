@@ -0,0 +1,621 @@
+use std::ops::Range;
+
+use macroquad::prelude::{Rect, TextDimensions};
+
+use crate::{Font, FontFace, Fonts, MeasuredText, TextParams};
+
+/// A glyph that has been placed on screen
+///
+/// **See** [Fonts::measure_text_ex]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PositionedGlyph {
+  /// The character of this glyph
+  pub c: char,
+  /// The byte index of the character in the text
+  pub index: usize,
+  /// x-coordinate of the pen position before this glyph
+  pub x: f32,
+  /// y-coordinate of the baseline this glyph sits on
+  pub y: f32,
+  /// How far the pen moves after this glyph
+  pub advance: f32,
+  /// The bounding rect of the glyph on screen
+  pub rect: Rect,
+  /// The index of the font this glyph is drawn with
+  pub font_index: usize,
+}
+
+/// A character drawn by [Fonts::draw_char]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DrawnChar {
+  /// How far the pen moves after this character
+  pub advance: f32,
+  /// The bounding rect of the glyph on screen
+  pub rect: Rect,
+  /// The index of the font the character is drawn with
+  pub font_index: usize,
+}
+
+/// The result of laying out text, every positioned glyph and the dimensions of the whole text
+///
+/// **See** [Fonts::measure_text_ex]
+#[derive(Debug, Clone, Default)]
+pub struct TextLayout {
+  pub glyphs: Vec<PositionedGlyph>,
+  /// Every line of the text, always contains at least one line
+  pub lines: Vec<TextLine>,
+  pub dimensions: MeasuredText,
+}
+
+/// A single line of laid out text
+///
+/// **See** [Fonts::measure_multiline]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+  /// The byte range of the line in the text, without the line break
+  pub range: Range<usize>,
+  /// The range of [TextLayout::glyphs] that are on this line
+  pub glyphs: Range<usize>,
+  /// The width of the line, without trailing whitespace
+  pub width: f32,
+  /// y-coordinate of the baseline of the line
+  pub baseline: f32,
+  /// y-coordinate of the top of the line box
+  pub top: f32,
+  /// The height of the line box
+  pub height: f32,
+}
+
+/// How paragraphs of multi-line text are laid out, for book-style text
+///
+/// **See** [Fonts::measure_paragraphs]
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct ParagraphStyle {
+  /// How far the first line after every line break, and the first line of the text,
+  /// is moved to the right in pixels
+  ///
+  /// **Default** `0.0`
+  pub indent: f32,
+  /// The space between paragraphs in pixels, replacing the blank lines that separate them,
+  /// `None` lays out blank lines like any other line
+  ///
+  /// **Default** `None`
+  pub spacing: Option<f32>,
+}
+
+/// Where a single glyph ended up after layout, used internally for drawing and measuring
+pub(crate) struct GlyphPlacement<'f, 'a> {
+  pub font: &'f Font<'a>,
+  pub font_index: usize,
+  /// What has to be synthesized for this glyph
+  pub synthesized: FontFace,
+  /// Where the glyph is in the atlas, `None` if it isn't cached
+  pub sprite: Option<Rect>,
+  /// The id of the sprite in the atlas, only meaningful if [Self::sprite] is some
+  pub id: u64,
+  /// The character has nothing to draw, like a space, so it never gets a sprite
+  pub blank: bool,
+  pub advance: f32,
+  pub rect: Rect,
+}
+
+impl<'a> Fonts<'a> {
+  /// Measures text with given [TextParams], also returning where every glyph is placed
+  ///
+  /// **Example**
+  /// ```rs
+  /// let layout = fonts.measure_text_ex("Some Text", &TextParams::default());
+  ///
+  /// for glyph in layout.glyphs {
+  ///   println!("{:?} at {}, {} from font {}", glyph.c, glyph.x, glyph.y, glyph.font_index);
+  /// }
+  /// ```
+  ///
+  /// **See** [PositionedGlyph]
+  pub fn measure_text_ex(&self, text: &str, params: &TextParams) -> TextLayout {
+    let baseline = self.baseline_y(params);
+    let mut glyphs = Vec::with_capacity(text.len());
+    let mut x = params.x;
+
+    let dimensions = self.layout_measured(text, params, true, |index, c, placement| {
+      glyphs.push(PositionedGlyph {
+        c,
+        index,
+        x,
+        y: baseline,
+        advance: placement.advance,
+        rect: placement.rect,
+        font_index: placement.font_index,
+      });
+
+      x += placement.advance;
+    });
+
+    let (top, bottom) = self.line_box(params);
+    let width = Self::trimmed_width(&glyphs, params.x);
+    let lines = vec![TextLine {
+      range: 0..text.len(),
+      glyphs: 0..glyphs.len(),
+      width,
+      baseline,
+      top,
+      height: bottom - top,
+    }];
+
+    TextLayout {
+      glyphs,
+      lines,
+      dimensions,
+    }
+  }
+
+  /// Measures text that can span multiple lines with given [TextParams],
+  /// '\n' starts a new line and lines are wrapped at whitespace
+  /// when they'd get wider than `max_width`, words that don't fit on a line by
+  /// themselves are broken up
+  ///
+  /// Lines are spaced by the line height of the first loaded font
+  ///
+  /// **Example**
+  /// ```rs
+  /// let layout = fonts.measure_multiline("Some\nlong text", &TextParams::default(), Some(200.0));
+  ///
+  /// for line in &layout.lines {
+  ///   println!("{:?} is {} wide", &text[line.range.clone()], line.width);
+  /// }
+  /// ```
+  ///
+  /// **See** [TextLine]
+  pub fn measure_multiline(
+    &self,
+    text: &str,
+    params: &TextParams,
+    max_width: Option<f32>,
+  ) -> TextLayout {
+    self.measure_paragraphs(text, params, max_width, &ParagraphStyle::default())
+  }
+
+  /// Measures text that can span multiple lines like [Self::measure_multiline],
+  /// but indents and spaces paragraphs with a given [ParagraphStyle]
+  ///
+  /// Blank lines separate paragraphs, with [ParagraphStyle::spacing]
+  /// they're kept as lines with a height of 0
+  ///
+  /// **Example**
+  /// ```rs
+  /// let layout = fonts.measure_paragraphs(&chapter, &params, Some(400.0), &ParagraphStyle {
+  ///   indent: 24.0,
+  ///   spacing: Some(12.0),
+  /// });
+  /// ```
+  pub fn measure_paragraphs(
+    &self,
+    text: &str,
+    params: &TextParams,
+    max_width: Option<f32>,
+    paragraph: &ParagraphStyle,
+  ) -> TextLayout {
+    self.measure_paragraphs_until(text, params, max_width, paragraph, f32::INFINITY)
+  }
+
+  /// Measures text like [Self::measure_paragraphs], but stops after the first line
+  /// that starts more than `until` pixels below the first line,
+  /// so text that can't be seen doesn't get laid out
+  pub(crate) fn measure_paragraphs_until(
+    &self,
+    text: &str,
+    params: &TextParams,
+    max_width: Option<f32>,
+    paragraph: &ParagraphStyle,
+    until: f32,
+  ) -> TextLayout {
+    let line_height = self.line_height(params);
+    let mut layout = TextLayout::default();
+    let mut paragraph_start = 0;
+    let mut dy = 0.0;
+    // paragraphs only get spacing before them after blank lines that follow text
+    let mut after_text = false;
+    let mut spaced = false;
+
+    'paragraphs: for paragraph_text in text.split('\n') {
+      let blank = paragraph.spacing.is_some() && paragraph_text.trim().is_empty();
+
+      if let Some(spacing) = paragraph.spacing {
+        if blank {
+          spaced |= after_text;
+        } else if spaced {
+          dy += spacing;
+          spaced = false;
+        }
+      }
+
+      after_text |= !blank;
+
+      let single = self.measure_text_ex(
+        paragraph_text,
+        &TextParams {
+          x: params.x + paragraph.indent,
+          ..*params
+        },
+      );
+
+      for (i, glyph_range) in Self::wrap_glyphs(&single.glyphs, params.x, max_width)
+        .into_iter()
+        .enumerate()
+      {
+        let past = layout
+          .lines
+          .last()
+          .is_some_and(|last| last.top - layout.lines[0].top > until);
+
+        if past {
+          break 'paragraphs;
+        }
+
+        let line = &single.glyphs[glyph_range.clone()];
+        let line_x = match i {
+          0 => params.x + paragraph.indent,
+          _ => params.x,
+        };
+        let dx = line.first().map_or(0.0, |glyph| glyph.x - line_x);
+        let start = layout.glyphs.len();
+        let range = match (line.first(), line.last()) {
+          (Some(first), Some(last)) => {
+            paragraph_start + first.index..paragraph_start + last.index + last.c.len_utf8()
+          }
+          _ => paragraph_start..paragraph_start,
+        };
+
+        layout
+          .glyphs
+          .extend(line.iter().map(|glyph| PositionedGlyph {
+            index: glyph.index + paragraph_start,
+            x: glyph.x - dx,
+            y: glyph.y + dy,
+            rect: Rect::new(
+              glyph.rect.x - dx,
+              glyph.rect.y + dy,
+              glyph.rect.w,
+              glyph.rect.h,
+            ),
+            ..*glyph
+          }));
+
+        let first_line = &single.lines[0];
+
+        layout.lines.push(TextLine {
+          range,
+          glyphs: start..layout.glyphs.len(),
+          width: Self::trimmed_width(&layout.glyphs[start..], params.x),
+          baseline: first_line.baseline + dy,
+          top: first_line.top + dy,
+          height: if blank { 0.0 } else { line_height },
+        });
+
+        if !blank {
+          dy += line_height;
+        }
+      }
+
+      paragraph_start += paragraph_text.len() + 1;
+    }
+
+    Self::update_dimensions(&mut layout, params);
+    layout
+  }
+
+  /// Lays out text appended to the text of a layout made by [Self::measure_multiline],
+  /// only the last line of the layout and the appended text are laid out again,
+  /// so text that keeps growing, like chat logs or streaming dialogue,
+  /// doesn't get laid out from the start every frame
+  ///
+  /// `text` is the whole text after appending, which has to start with the text
+  /// the layout was made from, `params` and `max_width` have to be the same too
+  ///
+  /// **Example**
+  /// ```rs
+  /// let mut text = String::new();
+  /// let mut layout = fonts.measure_multiline(&text, &params, Some(400.0));
+  ///
+  /// // every time a message comes in
+  /// text.push_str(&message);
+  /// fonts.extend_multiline(&mut layout, &text, &params, Some(400.0));
+  /// ```
+  pub fn extend_multiline(
+    &self,
+    layout: &mut TextLayout,
+    text: &str,
+    params: &TextParams,
+    max_width: Option<f32>,
+  ) {
+    let Some(last) = layout.lines.pop() else {
+      *layout = self.measure_multiline(text, params, max_width);
+      return;
+    };
+
+    // the last line can wrap differently with the appended text, so it's laid out again
+    let start = last.range.start;
+    let first_glyph = last.glyphs.start;
+    let appended = self.measure_multiline(&text[start..], params, max_width);
+    let dy = last.baseline - appended.lines[0].baseline;
+
+    layout.glyphs.truncate(first_glyph);
+    layout
+      .glyphs
+      .extend(appended.glyphs.into_iter().map(|glyph| PositionedGlyph {
+        index: glyph.index + start,
+        y: glyph.y + dy,
+        rect: Rect::new(glyph.rect.x, glyph.rect.y + dy, glyph.rect.w, glyph.rect.h),
+        ..glyph
+      }));
+    layout
+      .lines
+      .extend(appended.lines.into_iter().map(|line| TextLine {
+        range: line.range.start + start..line.range.end + start,
+        glyphs: line.glyphs.start + first_glyph..line.glyphs.end + first_glyph,
+        baseline: line.baseline + dy,
+        top: line.top + dy,
+        ..line
+      }));
+
+    Self::update_dimensions(layout, params);
+  }
+
+  /// Sets the dimensions of a layout from its glyphs and lines
+  fn update_dimensions(layout: &mut TextLayout, params: &TextParams) {
+    let top = layout
+      .glyphs
+      .iter()
+      .map(|it| it.rect.y)
+      .fold(f32::MAX, f32::min);
+    let bottom = layout
+      .glyphs
+      .iter()
+      .map(|it| it.rect.bottom())
+      .fold(f32::MIN, f32::max);
+
+    let dimensions = match layout.glyphs.is_empty() {
+      true => TextDimensions::default(),
+      false => TextDimensions {
+        width: layout.lines.iter().map(|it| it.width).fold(0.0, f32::max),
+        height: bottom - top,
+        offset_y: layout.lines[0].baseline - top,
+      },
+    };
+    let mut measured = MeasuredText {
+      lines: layout.lines.len().max(1),
+      ..MeasuredText::empty(params)
+    };
+
+    for glyph in &layout.glyphs {
+      measured.add_glyph(glyph.rect);
+    }
+
+    layout.dimensions = measured.with_dimensions(dimensions, params);
+  }
+
+  /// Returns the distance between the baselines of two lines,
+  /// made from the line height of the first loaded font
+  pub(crate) fn line_height(&self, params: &TextParams) -> f32 {
+    self
+      .fonts
+      .first()
+      .and_then(|font| font.horizontal_line_metrics(params.size))
+      .map_or(params.size, |metrics| metrics.new_line_size)
+      * params.vertical_scale()
+  }
+
+  /// Returns the width of glyphs on a single line starting at `x`, without trailing whitespace
+  fn trimmed_width(glyphs: &[PositionedGlyph], x: f32) -> f32 {
+    glyphs
+      .iter()
+      .rev()
+      .find(|glyph| !glyph.c.is_whitespace())
+      .map_or(0.0, |glyph| glyph.x + glyph.advance - x)
+  }
+
+  /// Splits glyphs laid out on a single line starting at `x` into ranges of glyphs
+  /// that fit in `max_width`, breaking after whitespace where possible
+  fn wrap_glyphs(glyphs: &[PositionedGlyph], x: f32, max_width: Option<f32>) -> Vec<Range<usize>> {
+    let max_width = max_width.unwrap_or(f32::INFINITY);
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut line_x = x;
+    let mut last_break = None;
+
+    for (i, glyph) in glyphs.iter().enumerate() {
+      if glyph.c.is_whitespace() {
+        last_break = Some(i + 1);
+        continue;
+      }
+
+      if i > start && glyph.x + glyph.advance - line_x > max_width {
+        let end = last_break.filter(|it| *it > start).unwrap_or(i);
+
+        lines.push(start..end);
+        start = end;
+        line_x = glyphs[start].x;
+        last_break = None;
+      }
+    }
+
+    lines.push(start..glyphs.len());
+    lines
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  fn params(size: f32) -> TextParams {
+    TextParams {
+      size,
+      ..Default::default()
+    }
+  }
+
+  fn glyph(c: char, x: f32) -> PositionedGlyph {
+    PositionedGlyph {
+      c,
+      index: 0,
+      x,
+      y: 0.0,
+      advance: 10.0,
+      rect: Rect::default(),
+      font_index: 0,
+    }
+  }
+
+  #[test]
+  fn measure_text_ex_places_glyphs_one_after_another() {
+    let fonts = fonts();
+    let layout = fonts.measure_text_ex("Hi there", &params(32.0));
+
+    assert_eq!(layout.glyphs.len(), 8);
+    assert_eq!(layout.lines.len(), 1);
+    assert_eq!(layout.glyphs[0].x, 0.0);
+
+    for pair in layout.glyphs.windows(2) {
+      assert_eq!(pair[1].x, pair[0].x + pair[0].advance);
+      assert_eq!(pair[1].index, pair[0].index + 1);
+    }
+
+    assert!(layout.dimensions.width > 0.0);
+    assert!(layout.dimensions.height > 0.0);
+  }
+
+  #[test]
+  fn measure_text_ex_scales_with_size() {
+    let fonts = fonts();
+    let small = fonts.measure_text_ex("Scale", &params(16.0)).dimensions;
+    let large = fonts.measure_text_ex("Scale", &params(32.0)).dimensions;
+
+    assert!((large.width / small.width - 2.0).abs() < 0.1);
+  }
+
+  #[test]
+  fn measure_multiline_breaks_lines() {
+    let fonts = fonts();
+    let params = params(20.0);
+    let layout = fonts.measure_multiline("one\ntwo\n\nthree", &params, None);
+    let line_height = fonts.line_height(&params);
+
+    assert_eq!(layout.lines.len(), 4);
+    assert_eq!(layout.lines[1].range, 4..7);
+    assert_eq!(layout.lines[2].range, 8..8);
+    assert_eq!(layout.lines[3].range, 9..14);
+    assert_eq!(
+      layout.lines[3].baseline - layout.lines[0].baseline,
+      line_height * 3.0
+    );
+  }
+
+  #[test]
+  fn measure_multiline_wraps_at_max_width() {
+    let fonts = fonts();
+    let params = params(20.0);
+    let word = fonts.measure_text_ex("word", &params).dimensions.width;
+    let layout = fonts.measure_multiline("word word word", &params, Some(word * 2.5));
+
+    assert_eq!(layout.lines.len(), 2);
+    assert_eq!(layout.lines[0].range, 0..10);
+    assert_eq!(layout.lines[1].range, 10..14);
+    assert!(layout.lines.iter().all(|it| it.width <= word * 2.5));
+    assert_eq!(layout.lines[1].glyphs.start, 10);
+  }
+
+  #[test]
+  fn measure_without_fonts_is_empty() {
+    let fonts = Fonts::default();
+
+    assert_eq!(
+      fonts
+        .measure_text_ex("text", &params(20.0))
+        .dimensions
+        .width,
+      0.0
+    );
+    assert_eq!(
+      fonts
+        .measure_multiline("a\nb", &params(20.0), None)
+        .dimensions
+        .width,
+      0.0
+    );
+  }
+
+  #[test]
+  fn wrap_glyphs_breaks_after_whitespace() {
+    let glyphs = "ab cd ef"
+      .chars()
+      .enumerate()
+      .map(|(i, c)| glyph(c, i as f32 * 10.0))
+      .collect::<Vec<_>>();
+
+    assert_eq!(Fonts::wrap_glyphs(&glyphs, 0.0, None), vec![0..8]);
+    assert_eq!(Fonts::wrap_glyphs(&glyphs, 0.0, Some(50.0)), [0..6, 6..8]);
+    assert_eq!(
+      Fonts::wrap_glyphs(&glyphs, 0.0, Some(25.0)),
+      [0..3, 3..6, 6..8]
+    );
+  }
+
+  #[test]
+  fn wrap_glyphs_breaks_long_words() {
+    let glyphs = "abcdef"
+      .chars()
+      .enumerate()
+      .map(|(i, c)| glyph(c, i as f32 * 10.0))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      Fonts::wrap_glyphs(&glyphs, 0.0, Some(25.0)),
+      [0..2, 2..4, 4..6]
+    );
+    // every line gets at least one glyph
+    assert_eq!(
+      Fonts::wrap_glyphs(&glyphs[..2], 0.0, Some(1.0)),
+      [0..1, 1..2]
+    );
+    assert_eq!(Fonts::wrap_glyphs(&[], 0.0, Some(1.0)), vec![0..0]);
+  }
+
+  #[test]
+  fn measuring_caches_into_the_configured_atlas() {
+    let mut fonts = fonts();
+
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 64,
+      growth_factor: 2.0,
+      max_size: 1024,
+      large_glyph_size: Some(64),
+    });
+    fonts.measure_text_ex("abcdefghijklmnopqrstuvwxyz", &params(24.0));
+
+    let font = &fonts.fonts()[0];
+    let (width, height) = font.atlas_size_at(0).unwrap();
+
+    assert!(width > 64 && width <= 1024 && width.is_power_of_two());
+    assert_eq!(width, height);
+    // nothing was big enough for the atlas of large glyphs, so there isn't one
+    assert_eq!(font.atlas_count(), 1);
+    assert_eq!(font.atlas_size_at(1), None);
+  }
+}
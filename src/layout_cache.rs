@@ -0,0 +1,469 @@
+use std::{
+  collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+  hash::{Hash, Hasher},
+};
+
+use macroquad::prelude::{Rect, TextDimensions};
+
+use crate::{intern::CachedQuads, layout::GlyphPlacement, Digits, FontFace, Fonts, TextParams};
+
+/// Everything about [TextParams] that changes where glyphs end up relative to the pen,
+/// position, color and clipping are applied after layout so they aren't part of it
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct LayoutKey {
+  text: u64,
+  size: u32,
+  scale: u32,
+  scale_x: u32,
+  scale_y: u32,
+  spacing: u32,
+  face: FontFace,
+  font: Option<usize>,
+  /// `None` if the digits of the locale of [Fonts] are used
+  digits: Option<Digits>,
+}
+
+impl LayoutKey {
+  pub fn new(text: &str, params: &TextParams) -> Self {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+
+    Self {
+      text: hasher.finish(),
+      size: params.size.to_bits(),
+      scale: params.scale.to_bits(),
+      scale_x: params.scale_x.to_bits(),
+      scale_y: params.scale_y.to_bits(),
+      spacing: params.spacing.to_bits(),
+      face: params.face(),
+      font: params.font,
+      digits: params.locale.map(|it| it.digits),
+    }
+  }
+}
+
+/// A glyph of a cached layout, relative to the start of the baseline
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct CachedGlyph {
+  index: usize,
+  c: char,
+  font_index: usize,
+  synthesized: FontFace,
+  /// `None` for blank characters
+  id: Option<u64>,
+  advance: f32,
+  rect: Rect,
+}
+
+#[derive(Debug)]
+struct CachedLayout {
+  /// Kept to rule out hash collisions
+  text: String,
+  glyphs: Vec<CachedGlyph>,
+  dimensions: TextDimensions,
+  last_used: u64,
+}
+
+/// Remembers where glyphs of recently drawn text ended up,
+/// so text drawn every frame doesn't have to look up fonts and glyphs again
+///
+/// Text is only remembered the second time it's laid out,
+/// so text that changes every frame, like a timer, doesn't push out text that doesn't
+#[derive(Debug)]
+pub(crate) struct LayoutCache {
+  layouts: HashMap<LayoutKey, CachedLayout>,
+  /// Every time a layout is used, oldest first, only the entry whose tick is
+  /// the `last_used` of its layout still counts, the others get skipped or compacted away
+  order: VecDeque<(LayoutKey, u64)>,
+  /// Text that was laid out once but isn't remembered yet
+  seen: HashSet<LayoutKey>,
+  capacity: usize,
+  tick: u64,
+  /// The quads of interned text by interned id
+  pub quads: HashMap<u32, Vec<CachedQuads>>,
+}
+
+impl Default for LayoutCache {
+  fn default() -> Self {
+    Self {
+      layouts: HashMap::default(),
+      order: VecDeque::default(),
+      seen: HashSet::default(),
+      capacity: 256,
+      tick: 0,
+      quads: HashMap::default(),
+    }
+  }
+}
+
+impl LayoutCache {
+  pub fn clear(&mut self) {
+    self.layouts.clear();
+    self.order.clear();
+    self.seen.clear();
+    self.quads.clear();
+  }
+
+  /// Returns true if text should be remembered after laying it out,
+  /// which is the second time it's laid out
+  fn admit(&mut self, key: LayoutKey) -> bool {
+    if self.seen.remove(&key) {
+      return true;
+    }
+
+    // forgetting everything at once keeps this from growing without a per key timestamp
+    if self.seen.len() >= self.capacity {
+      self.seen.clear();
+    }
+
+    self.seen.insert(key);
+    false
+  }
+
+  /// Marks a layout as the most recently used one
+  fn touch(&mut self, key: LayoutKey) {
+    self.tick += 1;
+
+    if let Some(layout) = self.layouts.get_mut(&key) {
+      layout.last_used = self.tick;
+    }
+
+    self.order.push_back((key, self.tick));
+
+    // drop the outdated entries once they outnumber the layouts
+    if self.order.len() > self.capacity.max(self.layouts.len()) * 2 {
+      let layouts = &self.layouts;
+
+      self
+        .order
+        .retain(|(key, tick)| layouts.get(key).is_some_and(|it| it.last_used == *tick));
+    }
+  }
+
+  /// Removes the least recently used layout
+  fn remove_oldest(&mut self) {
+    while let Some((key, tick)) = self.order.pop_front() {
+      if self
+        .layouts
+        .get(&key)
+        .is_some_and(|it| it.last_used == tick)
+      {
+        self.layouts.remove(&key);
+        return;
+      }
+    }
+  }
+
+  /// Stores a layout, removing the least recently used one when full
+  fn insert(
+    &mut self,
+    key: LayoutKey,
+    text: &str,
+    glyphs: Vec<CachedGlyph>,
+    dimensions: TextDimensions,
+  ) {
+    if self.capacity == 0 {
+      return;
+    }
+
+    if self.layouts.len() >= self.capacity && !self.layouts.contains_key(&key) {
+      self.remove_oldest();
+    }
+
+    self.layouts.insert(
+      key,
+      CachedLayout {
+        text: text.to_string(),
+        glyphs,
+        dimensions,
+        last_used: 0,
+      },
+    );
+    self.touch(key);
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Sets how many laid out strings are remembered, **Default** 256
+  ///
+  /// Text that gets drawn with the same size, scale, spacing, face and font
+  /// reuses where its glyphs were placed the last time instead of looking up
+  /// fonts and glyphs for every character again, which makes drawing the same
+  /// strings every frame, like a HUD, a lot cheaper
+  ///
+  /// Strings are only remembered the second time they're laid out,
+  /// so text that changes every frame doesn't push out text that doesn't,
+  /// the least recently drawn string is forgotten when the cache is full,
+  /// `0` turns the cache off
+  pub fn set_layout_cache_capacity(&mut self, capacity: usize) {
+    let cache = self.layout_cache.get_mut();
+
+    cache.capacity = capacity;
+
+    while cache.layouts.len() > capacity {
+      cache.remove_oldest();
+    }
+  }
+
+  /// Returns how many laid out strings are remembered
+  ///
+  /// **See** [Self::set_layout_cache_capacity]
+  pub fn layout_cache_capacity(&self) -> usize {
+    self.layout_cache.borrow().capacity
+  }
+
+  /// Checks if the text has a cached layout for the given [TextParams]
+  pub(crate) fn is_layout_cached(&self, text: &str, params: &TextParams) -> bool {
+    let key = LayoutKey::new(text, params);
+
+    self
+      .layout_cache
+      .borrow()
+      .layouts
+      .get(&key)
+      .is_some_and(|layout| layout.text == text && self.is_in_atlas(layout))
+  }
+
+  /// Checks if every glyph of a cached layout is still in its atlas
+  fn is_in_atlas(&self, layout: &CachedLayout) -> bool {
+    layout.glyphs.iter().all(|glyph| {
+      glyph.id.is_none_or(|id| {
+        self.fonts[glyph.font_index]
+          .atlases
+          .borrow()
+          .get(id)
+          .is_some()
+      })
+    })
+  }
+
+  /// Calls `f` with every glyph of a cached layout of the text, like [Self::layout_glyphs],
+  /// returns `None` without calling `f` if the text isn't cached
+  /// or a glyph of it isn't in its atlas anymore
+  pub(crate) fn replay_layout(
+    &self,
+    key: &LayoutKey,
+    text: &str,
+    params: &TextParams,
+    f: &mut impl FnMut(usize, char, &GlyphPlacement<'_, 'a>),
+  ) -> Option<TextDimensions> {
+    let mut cache = self.layout_cache.borrow_mut();
+    let layout = cache.layouts.get(key)?;
+
+    if layout.text != text {
+      return None;
+    }
+
+    // make sure every glyph is still there before drawing any of them
+    if !self.is_in_atlas(layout) {
+      cache.layouts.remove(key);
+      return None;
+    }
+
+    cache.touch(*key);
+
+    let layout = &cache.layouts[key];
+    let baseline = self.baseline_y(params);
+
+    for glyph in &layout.glyphs {
+      let font = &self.fonts[glyph.font_index];
+      let sprite = glyph
+        .id
+        .and_then(|id| font.atlases.borrow().get(id))
+        .map(|it| it.rect);
+      let rect = Rect::new(
+        params.x + glyph.rect.x,
+        baseline + glyph.rect.y,
+        glyph.rect.w,
+        glyph.rect.h,
+      );
+
+      f(
+        glyph.index,
+        glyph.c,
+        &GlyphPlacement {
+          font,
+          font_index: glyph.font_index,
+          synthesized: glyph.synthesized,
+          sprite,
+          id: glyph.id.unwrap_or(0),
+          blank: glyph.id.is_none(),
+          advance: glyph.advance,
+          rect,
+        },
+      );
+    }
+
+    Some(layout.dimensions)
+  }
+
+  /// Returns true if the layout of the text should be remembered once it's laid out,
+  /// text is only remembered the second time it's laid out
+  pub(crate) fn should_remember_layout(&self, key: LayoutKey) -> bool {
+    self.layout_cache.borrow_mut().admit(key)
+  }
+
+  /// Remembers a layout of the text, `glyphs` is `None` if a glyph wasn't cached
+  /// because of the raster budget, those layouts aren't remembered
+  pub(crate) fn remember_layout(
+    &self,
+    key: LayoutKey,
+    text: &str,
+    glyphs: Option<Vec<CachedGlyph>>,
+    dimensions: TextDimensions,
+  ) {
+    if let Some(glyphs) = glyphs {
+      self
+        .layout_cache
+        .borrow_mut()
+        .insert(key, text, glyphs, dimensions);
+    }
+  }
+}
+
+impl CachedGlyph {
+  /// Remembers a placed glyph relative to the start of the baseline,
+  /// `None` if the glyph isn't cached and isn't blank
+  pub fn new(
+    index: usize,
+    c: char,
+    placement: &GlyphPlacement<'_, '_>,
+    params: &TextParams,
+    baseline: f32,
+  ) -> Option<Self> {
+    if placement.sprite.is_none() && !placement.blank {
+      return None;
+    }
+
+    Some(Self {
+      index,
+      c,
+      font_index: placement.font_index,
+      synthesized: placement.synthesized,
+      id: placement.sprite.map(|_| placement.id),
+      advance: placement.advance,
+      rect: Rect::new(
+        placement.rect.x - params.x,
+        placement.rect.y - baseline,
+        placement.rect.w,
+        placement.rect.h,
+      ),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  fn lay_out(fonts: &Fonts, text: &str) -> TextDimensions {
+    fonts.layout_glyphs(text, &TextParams::default(), true, |_, _, _| {})
+  }
+
+  fn cached(fonts: &Fonts, text: &str) -> bool {
+    fonts.is_layout_cached(text, &TextParams::default())
+  }
+
+  #[test]
+  fn text_is_cached_the_second_time() {
+    let fonts = fonts();
+
+    lay_out(&fonts, "Score");
+    assert!(!cached(&fonts, "Score"));
+
+    lay_out(&fonts, "Score");
+    assert!(cached(&fonts, "Score"));
+  }
+
+  #[test]
+  fn hits_lay_out_the_same() {
+    let fonts = fonts();
+    let first = lay_out(&fonts, "Score");
+
+    lay_out(&fonts, "Score");
+
+    let mut replayed = 0;
+    let key = LayoutKey::new("Score", &TextParams::default());
+    let dimensions = fonts.replay_layout(&key, "Score", &TextParams::default(), &mut |_, _, _| {
+      replayed += 1;
+    });
+
+    assert_eq!(replayed, 5);
+    assert_eq!(dimensions.map(|it| it.width), Some(first.width));
+  }
+
+  #[test]
+  fn text_drawn_once_doesnt_push_out_cached_text() {
+    let mut fonts = fonts();
+    fonts.set_layout_cache_capacity(4);
+
+    lay_out(&fonts, "HP");
+    lay_out(&fonts, "HP");
+
+    for frame in 0..100 {
+      lay_out(&fonts, &frame.to_string());
+    }
+
+    assert!(cached(&fonts, "HP"));
+    assert_eq!(fonts.layout_cache.borrow().layouts.len(), 1);
+  }
+
+  #[test]
+  fn least_recently_used_is_evicted() {
+    let mut fonts = fonts();
+    fonts.set_layout_cache_capacity(2);
+
+    for text in ["a", "a", "b", "b"] {
+      lay_out(&fonts, text);
+    }
+
+    // "a" gets used again, so "b" is the oldest
+    lay_out(&fonts, "a");
+    lay_out(&fonts, "c");
+    lay_out(&fonts, "c");
+
+    assert!(cached(&fonts, "a"));
+    assert!(!cached(&fonts, "b"));
+    assert!(cached(&fonts, "c"));
+
+    fonts.set_layout_cache_capacity(1);
+
+    assert!(!cached(&fonts, "a"));
+    assert!(cached(&fonts, "c"));
+  }
+
+  #[test]
+  fn layouts_with_evicted_glyphs_are_dropped() {
+    let fonts = fonts();
+
+    lay_out(&fonts, "Score");
+    lay_out(&fonts, "Score");
+    assert!(cached(&fonts, "Score"));
+
+    fonts.fonts[0].atlases.borrow_mut().clear();
+
+    assert!(!cached(&fonts, "Score"));
+
+    let key = LayoutKey::new("Score", &TextParams::default());
+    let replayed = fonts.replay_layout(&key, "Score", &TextParams::default(), &mut |_, _, _| {});
+
+    assert!(replayed.is_none());
+    assert!(fonts.layout_cache.borrow().layouts.is_empty());
+  }
+}
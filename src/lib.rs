@@ -36,23 +36,141 @@
 //!
 //! ![img.png](https://raw.githubusercontent.com/Ricky12Awesome/macroquad-text/main/examples/render_text_window.png)
 //!
+//! **Headless**
+//!
+//! Loading fonts, caching glyphs, measuring and laying out text don't need a window,
+//! atlas textures are only created the first time text is drawn,
+//! so measuring and wrapping can be tested in CI without a GL context
+//!
 
 #![deny(unsafe_code)]
 
-use std::{cell::RefCell, collections::HashMap, ops::Deref, path::Path};
+use std::{
+  borrow::Cow,
+  cell::{Cell, RefCell},
+  collections::{BTreeSet, HashMap, HashSet},
+  ops::{Deref, RangeInclusive},
+  path::Path,
+  rc::Rc,
+  sync::Arc,
+};
 
-use fontdue::{FontResult, FontSettings};
+use fontdue::{FontSettings, Metrics};
 use macroquad::prelude::{
-  draw_texture_ex, vec2, Color, DrawTextureParams, FilterMode, Image, TextDimensions,
+  draw_line, draw_mesh, load_file, Color, FilterMode, Image, Mat4, Mesh, Rect, TextDimensions, Vec2,
 };
 
 use crate::{
-  atlas::Atlas,
-  misc::{read_file, IoError, IoErrorKind, IoResult},
+  atlas::Atlases,
+  camera::ScreenSpace,
+  coverage::MissingGlyphHandler,
+  glyphs::GlyphBitmap,
+  intern::Interner,
+  layout::GlyphPlacement,
+  layout_cache::{CachedGlyph, LayoutCache, LayoutKey},
+  misc::{is_blank, mix_color, read_file},
+};
+
+#[cfg(feature = "golden")]
+pub use crate::golden::{assert_golden, compare_images, load_png, save_png, ImageDiff};
+#[cfg(feature = "log")]
+pub use crate::logger::{LogRecord, ScreenLogger};
+#[cfg(feature = "serde")]
+pub use crate::manifest::{FontManifest, FontManifestEntry};
+#[cfg(feature = "markdown")]
+pub use crate::markdown::MarkdownStyle;
+#[cfg(feature = "ab_glyph")]
+pub use crate::raster::AbGlyphRasterizer;
+#[cfg(feature = "swash")]
+pub use crate::raster::SwashRasterizer;
+pub use crate::{
+  atlas::AtlasConfig,
+  builder::FontsBuilder,
+  cache::WarmProgress,
+  camera::{in_screen_space, DrawSpace},
+  caret::Caret,
+  collection::{collection_faces, CollectionFace},
+  colored::{AnimatedColor, ColoredStr, Component, Span, SpanAnimation},
+  colored_string::{ColoredString, OwnedComponent},
+  composition::Composition,
+  control::ControlChars,
+  coverage::UnsupportedChar,
+  error::{FontError, FontResult},
+  fade::Fade,
+  family::{FontFace, FontStyle, FontWeight},
+  glyphs::{AtlasSnapshot, GlyphEntry, SnapshotGlyph},
+  intern::InternedText,
+  layout::{DrawnChar, ParagraphStyle, PositionedGlyph, TextLayout, TextLine},
+  local::LocalFonts,
+  locale::{Digits, Locale},
+  measured::MeasuredText,
+  metrics::VerticalMetrics,
+  quad::{GlyphQuad, MeshSink, QuadSink},
+  raster::{FontdueRasterizer, RasterBackend, Rasterizer},
+  renderer::TextRenderer,
+  script::Script,
+  stats::{CacheStats, FontCacheStats},
+  style::{Shadow, TextStyle, TextTransform},
+  text_box::{BoxAnchor, BoxStyle, HorizontalAlign, Overflow, TextBoxStyle, VerticalAlign},
 };
 
+pub(crate) mod ansi;
 pub(crate) mod atlas;
+pub(crate) mod bbcode;
+pub(crate) mod blur;
+pub(crate) mod builder;
+pub(crate) mod cache;
+pub(crate) mod camera;
+pub(crate) mod caret;
+pub(crate) mod collection;
+pub(crate) mod colored;
+pub(crate) mod colored_string;
+pub(crate) mod composition;
+pub(crate) mod control;
+pub(crate) mod coverage;
+pub(crate) mod error;
+pub(crate) mod fade;
+pub(crate) mod family;
+pub(crate) mod format;
+pub mod global;
+pub(crate) mod glyphs;
+#[cfg(feature = "golden")]
+pub(crate) mod golden;
+pub(crate) mod highlight;
+pub(crate) mod intern;
+pub(crate) mod layout;
+pub(crate) mod layout_cache;
+pub(crate) mod local;
+pub(crate) mod locale;
+#[cfg(feature = "log")]
+pub(crate) mod logger;
+pub(crate) mod macros;
+#[cfg(feature = "serde")]
+pub(crate) mod manifest;
+#[cfg(feature = "markdown")]
+pub(crate) mod markdown;
+pub(crate) mod markup;
+pub(crate) mod measured;
+pub(crate) mod metrics;
 pub(crate) mod misc;
+pub(crate) mod normalize;
+pub(crate) mod quad;
+pub(crate) mod queue;
+pub(crate) mod raster;
+#[cfg(feature = "hot-reload")]
+pub(crate) mod reload;
+pub(crate) mod renderer;
+pub(crate) mod script;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_with;
+pub(crate) mod stats;
+pub(crate) mod style;
+#[cfg(feature = "system-fonts")]
+pub(crate) mod system;
+pub(crate) mod text_box;
+pub(crate) mod vector;
+#[cfg(feature = "widgets")]
+pub mod widgets;
 
 pub type ScalingMode = FilterMode;
 pub type FontdueFont = fontdue::Font;
@@ -60,20 +178,21 @@ pub type FontdueFont = fontdue::Font;
 /// Where to draw from on the screen
 ///
 /// **Default** [DrawFrom::TopLeft]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DrawFrom {
-  /// Starts drawing from the bottom left corner
+  /// Starts drawing from the bottom left corner,
+  /// the bottom is the descent of the first loaded font
   BottomLeft,
   /// Starts drawing from the top left corner
   ///
   /// this is the default
+  #[default]
   TopLeft,
-}
-
-impl Default for DrawFrom {
-  fn default() -> Self {
-    Self::TopLeft
-  }
+  /// Starts drawing from the baseline on the left,
+  /// useful for aligning text with other baseline positioned elements
+  /// or mixing sizes on one line
+  Baseline,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd)]
@@ -86,6 +205,8 @@ pub(crate) struct CharacterInfo {
 
 /// Text parameters for [Fonts::draw_text_ex]
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TextParams {
   /// x-coordinate of the text
   pub x: f32,
@@ -98,10 +219,117 @@ pub struct TextParams {
   /// since it scales the texture not the
   /// font itself for performance reasons
   pub scale: f32,
+  /// Stretches text horizontally on top of [Self::scale],
+  /// values below 1 condense it
+  ///
+  /// **Default** `1.0`
+  pub scale_x: f32,
+  /// Stretches text vertically on top of [Self::scale],
+  /// values below 1 squash it
+  ///
+  /// **Default** `1.0`
+  pub scale_y: f32,
   /// The color of the text
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::color"))]
   pub color: Color,
+  /// The color of the bottom of every glyph, fading into [Self::color] at the top,
+  /// useful for two-tone text, `None` draws glyphs with a single color
+  ///
+  /// **Default** `None`
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::option_color"))]
+  pub bottom_color: Option<Color>,
   /// Where to draw from
   pub draw: DrawFrom,
+  /// The weight of the text, picks the matching face
+  /// from the font's family or synthesizes it
+  pub weight: FontWeight,
+  /// The style of the text, picks the matching face
+  /// from the font's family or synthesizes it
+  pub style: FontStyle,
+  /// Only draws the parts of glyphs inside this rect,
+  /// useful for text inside scrollable panels,
+  /// `None` doesn't clip
+  ///
+  /// **Default** `None`
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::option_rect"))]
+  pub clip: Option<Rect>,
+  /// Skips glyphs that are entirely outside this rect without clipping the ones partly inside,
+  /// and skips whole lines outside of it, usually the visible area of the screen,
+  /// useful for long scrolled text like chat history or credits,
+  /// `None` draws everything
+  ///
+  /// Glyphs are culled by their rect before they're rotated
+  ///
+  /// **Default** `None`
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::option_rect"))]
+  pub visible: Option<Rect>,
+  /// Draws characters with the font at this index if it contains them
+  /// instead of looking up a font that contains them,
+  /// useful for icon fonts, `None` or an index that isn't loaded looks fonts up
+  ///
+  /// **Default** `None`
+  pub font: Option<usize>,
+  /// Extra space added after every character in pixels, scaled by [Self::scale],
+  /// negative values move characters closer together
+  ///
+  /// **Default** `0.0`
+  pub spacing: f32,
+  /// The depth of the text, written into the z-coordinate of every vertex,
+  /// so text can be depth tested against other sprites
+  /// when drawn with a material that has depth testing on
+  ///
+  /// **Default** `0.0`
+  pub z: f32,
+  /// If the text is drawn with the active camera or in screen coordinates
+  ///
+  /// **Default** [DrawSpace::Camera]
+  pub space: DrawSpace,
+  /// Mirrors the text horizontally, glyphs are mirrored and drawn in reverse order
+  /// inside the width of the text, useful for mirrored worlds
+  ///
+  /// **Default** `false`
+  pub flip_x: bool,
+  /// Mirrors the text vertically inside its line box,
+  /// useful for drawing onto render targets that are flipped
+  ///
+  /// **Default** `false`
+  pub flip_y: bool,
+  /// Rotates every glyph around its own center by this angle in radians,
+  /// the glyphs still sit along the baseline, useful for spinning letters
+  ///
+  /// **Default** `0.0`
+  pub rotation: f32,
+  /// The language rules the text is drawn with, like which digits are drawn,
+  /// `None` uses the locale of [Fonts]
+  ///
+  /// **Default** `None`
+  ///
+  /// **See** [Fonts::set_locale]
+  pub locale: Option<Locale>,
+}
+
+impl TextParams {
+  /// Returns the [FontFace] made from [Self::weight] and [Self::style]
+  pub fn face(&self) -> FontFace {
+    FontFace::new(self.weight, self.style)
+  }
+
+  /// Returns true if a line from `top` to `bottom` is entirely above or below [Self::visible]
+  pub(crate) fn is_line_culled(&self, top: f32, bottom: f32) -> bool {
+    self
+      .visible
+      .is_some_and(|it| bottom < it.y || top > it.bottom())
+  }
+
+  /// Returns what widths are scaled by, [Self::scale] times [Self::scale_x]
+  pub(crate) fn horizontal_scale(&self) -> f32 {
+    self.scale * self.scale_x
+  }
+
+  /// Returns what heights are scaled by, [Self::scale] times [Self::scale_y]
+  pub(crate) fn vertical_scale(&self) -> f32 {
+    self.scale * self.scale_y
+  }
 }
 
 impl Default for TextParams {
@@ -111,8 +339,23 @@ impl Default for TextParams {
       y: 0.0,
       size: 22.,
       scale: 1.0,
+      scale_x: 1.0,
+      scale_y: 1.0,
       color: Color::from_rgba(255, 255, 255, 255),
+      bottom_color: None,
       draw: DrawFrom::TopLeft,
+      weight: FontWeight::Regular,
+      style: FontStyle::Normal,
+      clip: None,
+      visible: None,
+      font: None,
+      spacing: 0.0,
+      z: 0.0,
+      space: DrawSpace::Camera,
+      flip_x: false,
+      flip_y: false,
+      rotation: 0.0,
+      locale: None,
     }
   }
 }
@@ -121,9 +364,36 @@ impl Default for TextParams {
 #[derive(Debug)]
 pub struct Font<'a> {
   pub name: &'a str,
+  /// The family this font is a face of, if any
+  pub family: Option<&'a str>,
+  /// Which face of its family this font is,
+  /// only regular faces are used for fallback lookups
+  pub face: FontFace,
+  /// Moves glyphs of this font up by this fraction of the text size,
+  /// negative values move them down, useful for aligning fallback fonts
+  /// with mismatched vertical metrics
+  ///
+  /// **Default** 0.0
+  pub baseline_offset: f32,
+  /// Multiplies the text size for glyphs of this font,
+  /// useful for fallback fonts that look bigger or smaller than the main font
+  ///
+  /// **Default** 1.0
+  pub size_multiplier: f32,
   font: FontdueFont,
-  atlas: RefCell<Atlas>,
+  /// Maps coverage to alpha, `None` if coverage is used as is
+  gamma_table: Option<[u8; 256]>,
+  atlases: RefCell<Atlases>,
   chars: RefCell<HashMap<(char, u16), CharacterInfo>>,
+  bitmaps: RefCell<HashMap<(char, u16), GlyphBitmap>>,
+  /// Blurred glyphs by character, size and blur radius
+  blurred: RefCell<HashMap<(char, u16, u16), CharacterInfo>>,
+  /// The bytes the font was loaded from, glyph outlines are read from these,
+  /// shared with the rasterizer
+  data: Arc<[u8]>,
+  collection_index: u32,
+  outlines: RefCell<HashMap<char, Rc<[Vec2]>>>,
+  rasterizer: Box<dyn Rasterizer>,
 }
 
 impl<'a> Deref for Font<'a> {
@@ -135,55 +405,149 @@ impl<'a> Deref for Font<'a> {
 }
 
 impl<'a> Font<'a> {
-  /// Creates a new font with a given name, [fontdue::Font], and [ScalingMode]
-  fn new(name: &'a str, font: FontdueFont, mode: ScalingMode) -> Self {
+  /// Creates a new font with a given name, [fontdue::Font], the bytes and collection index
+  /// it was loaded from, [ScalingMode], [AtlasConfig] and the [Rasterizer] of its glyphs
+  fn new(
+    name: &'a str,
+    font: FontdueFont,
+    data: Arc<[u8]>,
+    collection_index: u32,
+    mode: ScalingMode,
+    atlas_config: AtlasConfig,
+    rasterizer: Box<dyn Rasterizer>,
+  ) -> Self {
     Self {
       name,
+      family: None,
+      face: FontFace::REGULAR,
+      baseline_offset: 0.0,
+      size_multiplier: 1.0,
       font,
-      atlas: RefCell::new(Atlas::new(mode)),
+      gamma_table: None,
+      atlases: RefCell::new(Atlases::new(mode, atlas_config)),
       chars: RefCell::default(),
+      bitmaps: RefCell::default(),
+      blurred: RefCell::default(),
+      data,
+      collection_index,
+      outlines: RefCell::default(),
+      rasterizer,
     }
   }
 
+  /// Rasterizes a character at a given size with the [Rasterizer] of this font
+  pub fn rasterize(&self, c: char, size: f32) -> (Metrics, Vec<u8>) {
+    self.rasterizer.rasterize(&self.font, c, size)
+  }
+
   /// Checks if this font contains a given character
   pub fn contains(&self, c: char) -> bool {
     self.lookup_glyph_index(c) != 0
   }
 
-  fn _cache_glyph(&self, c: char, size: u16) -> CharacterInfo {
+  /// Returns every character this font has a glyph for, in order,
+  /// useful for showing coverage in tools or checking localization files against the font
+  ///
+  /// **Example**
+  /// ```rs
+  /// let font = fonts.get_font_by_name("Noto Sans").unwrap();
+  /// let covered = font.supported_chars();
+  ///
+  /// let missing = translation.chars().filter(|c| !covered.contains(c)).collect::<Vec<_>>();
+  /// ```
+  pub fn supported_chars(&self) -> BTreeSet<char> {
+    self.font.chars().keys().copied().collect()
+  }
+
+  /// Returns the size glyphs of this font are rasterized at for a given text size
+  ///
+  /// **See** [Self::size_multiplier]
+  pub fn adjusted_size(&self, size: f32) -> f32 {
+    size * self.size_multiplier
+  }
+
+  /// Sets the gamma coverage gets corrected with, glyphs have to be cached again after this
+  ///
+  /// **See** [Fonts::set_gamma]
+  fn set_gamma(&mut self, gamma: f32) {
+    self.gamma_table = (gamma != 1.0).then(|| {
+      std::array::from_fn(|coverage| ((coverage as f32 / 255.0).powf(1.0 / gamma) * 255.0) as u8)
+    });
+  }
+
+  /// How far glyphs of this font get moved up in pixels
+  fn baseline_shift(&self, params: &TextParams) -> f32 {
+    self.baseline_offset * params.size * params.vertical_scale()
+  }
+
+  fn _cache_glyph(&self, c: char, size: u16) -> Option<CharacterInfo> {
+    let color = self.rasterizer.rasterize_color(&self.font, c, size as f32);
+
+    if let Some((matrix, pixels)) = color {
+      return self.insert_pixels(size, matrix, pixels);
+    }
+
     let (matrix, bitmap) = self.rasterize(c, size as f32);
-    let (width, height) = (matrix.width as u16, matrix.height as u16);
 
-    let id = self.atlas.borrow_mut().new_unique_id();
-    let bytes = bitmap
-      .iter()
-      .flat_map(|coverage| vec![255, 255, 255, *coverage])
-      .collect::<Vec<_>>();
+    self.insert_bitmap(size, matrix, &bitmap)
+  }
 
-    self.atlas.borrow_mut().cache_sprite(
-      id,
+  /// Packs a glyph rasterized at a given size into the atlas for that size,
+  /// `None` if the atlas is full
+  pub(crate) fn insert_bitmap(
+    &self,
+    size: u16,
+    matrix: Metrics,
+    bitmap: &[u8],
+  ) -> Option<CharacterInfo> {
+    let mut bytes = Vec::with_capacity(bitmap.len() * 4);
+
+    for coverage in bitmap {
+      let alpha = self
+        .gamma_table
+        .map_or(*coverage, |it| it[*coverage as usize]);
+
+      bytes.extend_from_slice(&[255, 255, 255, alpha]);
+    }
+
+    self.insert_pixels(size, matrix, bytes)
+  }
+
+  /// Packs the RGBA pixels of a glyph rasterized at a given size into the atlas for that size,
+  /// like color glyphs, `None` if the atlas is full
+  pub(crate) fn insert_pixels(
+    &self,
+    size: u16,
+    matrix: Metrics,
+    bytes: Vec<u8>,
+  ) -> Option<CharacterInfo> {
+    let (width, height) = (matrix.width as u16, matrix.height as u16);
+    let id = self.atlases.borrow_mut().cache_sprite(
+      size,
       Image {
         width,
         height,
         bytes,
       },
-    );
+    )?;
 
-    CharacterInfo {
+    Some(CharacterInfo {
       id,
       offset_x: matrix.xmin as f32,
       offset_y: matrix.ymin as f32,
       advance: matrix.advance_width,
-    }
+    })
   }
 
   /// Caches a glyph for a given character with a given font size
   ///
   /// You don't really need to call this function since caching happens automatically
   pub fn cache_glyph(&self, c: char, size: u16) {
-    if !self.chars.borrow().contains_key(&(c, size)) {
-      let info = self._cache_glyph(c, size);
+    if self.chars.borrow().contains_key(&(c, size)) {
+      return;
+    }
 
+    if let Some(info) = self._cache_glyph(c, size) {
       self.chars.borrow_mut().insert((c, size), info);
     }
   }
@@ -192,8 +556,15 @@ impl<'a> Font<'a> {
   ///
   /// normally you wouldn't need to call this
   pub fn recache_glyphs(&self) {
-    for ((c, size), info) in self.chars.borrow_mut().iter_mut() {
-      *info = self._cache_glyph(*c, *size);
+    let keys = self
+      .chars
+      .borrow_mut()
+      .drain()
+      .map(|(key, _)| key)
+      .collect::<Vec<_>>();
+
+    for (c, size) in keys {
+      self.cache_glyph(c, size);
     }
   }
 }
@@ -202,7 +573,39 @@ impl<'a> Font<'a> {
 pub struct Fonts<'a> {
   fonts: Vec<Font<'a>>,
   index_by_name: HashMap<&'a str, usize>,
+  /// Other names fonts and families can be looked up by
+  aliases: HashMap<&'a str, &'a str>,
+  faces_by_family: HashMap<(&'a str, FontFace), usize>,
+  range_fallbacks: Vec<(RangeInclusive<char>, Vec<&'a str>)>,
+  emoji_font: Option<&'a str>,
+  missing_glyph_handler: Option<MissingGlyphHandler<'a>>,
+  /// Characters the missing glyph handler was already called for
+  reported_missing: RefCell<HashSet<char>>,
   default_sm: ScalingMode,
+  atlas_config: AtlasConfig,
+  raster_backend: RasterBackend,
+  raster_budget: Option<usize>,
+  rasterized_this_frame: Cell<usize>,
+  skipped_this_frame: Cell<usize>,
+  rasterize_downscaled: bool,
+  gamma: f32,
+  control_chars: ControlChars,
+  locale: Locale,
+  styles: HashMap<String, TextStyle>,
+  format_buffer: RefCell<String>,
+  layout_cache: RefCell<LayoutCache>,
+  interner: RefCell<Interner>,
+  queue: RefCell<Option<Vec<GlyphQuad>>>,
+  #[cfg(feature = "system-fonts")]
+  system_fonts: Option<Box<fontdb::Database>>,
+  #[cfg(feature = "hot-reload")]
+  watched: Vec<reload::WatchedFont<'a>>,
+  #[cfg(feature = "hot-reload")]
+  hot_reload_interval: std::time::Duration,
+  #[cfg(feature = "hot-reload")]
+  last_hot_reload: Option<std::time::Instant>,
+  #[cfg(feature = "normalize")]
+  normalize: bool,
 }
 
 impl<'a> Default for Fonts<'a> {
@@ -233,13 +636,56 @@ impl<'a> Fonts<'a> {
     Self {
       fonts: Vec::default(),
       index_by_name: HashMap::default(),
+      aliases: HashMap::default(),
+      faces_by_family: HashMap::default(),
+      range_fallbacks: Vec::default(),
+      emoji_font: None,
+      missing_glyph_handler: None,
+      reported_missing: RefCell::default(),
       default_sm,
+      atlas_config: AtlasConfig::default(),
+      raster_backend: RasterBackend::default(),
+      raster_budget: None,
+      rasterized_this_frame: Cell::new(0),
+      skipped_this_frame: Cell::new(0),
+      rasterize_downscaled: false,
+      gamma: 1.0,
+      control_chars: ControlChars::default(),
+      locale: Locale::default(),
+      styles: HashMap::default(),
+      format_buffer: RefCell::default(),
+      layout_cache: RefCell::default(),
+      interner: RefCell::default(),
+      queue: RefCell::default(),
+      #[cfg(feature = "system-fonts")]
+      system_fonts: None,
+      #[cfg(feature = "hot-reload")]
+      watched: Vec::new(),
+      #[cfg(feature = "hot-reload")]
+      hot_reload_interval: reload::DEFAULT_HOT_RELOAD_INTERVAL,
+      #[cfg(feature = "hot-reload")]
+      last_hot_reload: None,
+      #[cfg(feature = "normalize")]
+      normalize: false,
     }
   }
 
+  /// Creates a new [FontsBuilder] to set up fonts in a single expression
+  ///
+  /// **Example**
+  /// ```rs
+  /// let fonts = Fonts::builder()
+  ///   .with_font("Noto Sans", NOTO_SANS)
+  ///   .with_font("Noto Sans JP", NOTO_SANS_JP)
+  ///   .build()?;
+  /// ```
+  pub fn builder() -> FontsBuilder<'a> {
+    FontsBuilder::new()
+  }
+
   /// Returns an immutable reference to the
   /// list of fonts that are currently loaded
-  pub fn fonts(&self) -> &Vec<Font> {
+  pub fn fonts(&self) -> &Vec<Font<'a>> {
     &self.fonts
   }
 
@@ -252,8 +698,170 @@ impl<'a> Fonts<'a> {
     }
   }
 
+  /// Sets how many glyphs the automatic caching is allowed to rasterize per frame,
+  /// `None` means there is no limit, which is the default
+  ///
+  /// Once the budget runs out, glyphs that still need to be rasterized are skipped
+  /// and only take up space until a later frame caches them,
+  /// this trades one frame of missing glyphs for consistent frame times
+  ///
+  /// **You need to call [Self::new_frame] once every frame when using a budget**
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_raster_budget(Some(32));
+  ///
+  /// loop {
+  ///   fonts.new_frame();
+  ///   fonts.draw_text("良い", 20.0, 20.0, 69.0, WHITE);
+  ///
+  ///   next_frame().await;
+  /// }
+  /// ```
+  pub fn set_raster_budget(&mut self, budget: Option<usize>) {
+    self.raster_budget = budget;
+  }
+
+  /// Returns how many glyphs the automatic caching is allowed to rasterize per frame
+  ///
+  /// **See** [Self::set_raster_budget]
+  pub fn raster_budget(&self) -> Option<usize> {
+    self.raster_budget
+  }
+
+  /// Returns how many times a glyph was skipped this frame because the raster budget ran out,
+  /// if this isn't 0 then some text is still missing glyphs
+  ///
+  /// **See** [Self::set_raster_budget]
+  pub fn skipped_glyphs(&self) -> usize {
+    self.skipped_this_frame.get()
+  }
+
+  /// Sets how big atlases start and how they grow, a bigger initial size avoids
+  /// copying the atlas every time it grows when lots of glyphs get cached, like full CJK sets
+  ///
+  /// Fonts that are already loaded get a new empty atlas, so their glyphs are cached again
+  ///
+  /// **Default** [AtlasConfig::default]
+  ///
+  /// **See** [AtlasConfig]
+  pub fn set_atlas_config(&mut self, config: AtlasConfig) {
+    self.atlas_config = config;
+
+    for font in &mut self.fonts {
+      *font.atlases.get_mut() = Atlases::new(self.default_sm, config);
+      font.chars.get_mut().clear();
+      font.blurred.get_mut().clear();
+    }
+
+    self.layout_cache.get_mut().clear();
+  }
+
+  /// Returns how big atlases start and how they grow
+  ///
+  /// **See** [Self::set_atlas_config]
+  pub fn atlas_config(&self) -> AtlasConfig {
+    self.atlas_config
+  }
+
+  /// Sets if text drawn with a [TextParams::scale] below 1 gets rasterized at the size
+  /// it ends up on screen instead of scaling down glyphs rasterized at [TextParams::size],
+  /// which aliases badly with linear filtering when the scale is small
+  ///
+  /// Every scale text is drawn with caches its own glyphs, so this uses more atlas space
+  ///
+  /// **Default** false
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_rasterize_downscaled(true);
+  ///
+  /// fonts.draw_text_ex("Tiny", &TextParams {
+  ///   size: 64.0,
+  ///   scale: 0.25,
+  ///   ..Default::default()
+  /// });
+  /// ```
+  pub fn set_rasterize_downscaled(&mut self, rasterize_downscaled: bool) {
+    self.rasterize_downscaled = rasterize_downscaled;
+    self.layout_cache.get_mut().clear();
+  }
+
+  /// Returns if downscaled text gets rasterized at the size it ends up on screen
+  ///
+  /// **See** [Self::set_rasterize_downscaled]
+  pub fn rasterize_downscaled(&self) -> bool {
+    self.rasterize_downscaled
+  }
+
+  /// Sets the gamma glyph coverage gets corrected with, which changes how heavy text looks,
+  /// coverage is blended as is, so light text on dark backgrounds looks too thin
+  /// and dark text on light backgrounds looks too heavy
+  ///
+  /// Values above 1 make text heavier and values below 1 make it thinner,
+  /// around 1.8 works well for light text on dark backgrounds
+  ///
+  /// This clears the glyph cache since every glyph has to be rasterized again
+  ///
+  /// **Default** 1.0, which doesn't change anything
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_gamma(1.8);
+  /// ```
+  pub fn set_gamma(&mut self, gamma: f32) {
+    self.gamma = gamma;
+
+    for font in &mut self.fonts {
+      font.set_gamma(gamma);
+    }
+
+    self.clear_cache();
+  }
+
+  /// Returns the gamma glyph coverage gets corrected with
+  ///
+  /// **See** [Self::set_gamma]
+  pub fn gamma(&self) -> f32 {
+    self.gamma
+  }
+
+  /// Resets the raster budget for a new frame
+  ///
+  /// **See** [Self::set_raster_budget]
+  pub fn new_frame(&self) {
+    self.rasterized_this_frame.set(0);
+    self.skipped_this_frame.set(0);
+  }
+
+  /// Caches a glyph if the raster budget allows it,
+  /// returns false if the glyph isn't cached and had to be skipped
+  fn try_cache_glyph(&self, font: &Font, c: char, size: u16) -> bool {
+    if font.chars.borrow().contains_key(&(c, size)) {
+      return true;
+    }
+
+    if let Some(budget) = self.raster_budget {
+      let rasterized = self.rasterized_this_frame.get();
+
+      if rasterized >= budget {
+        self
+          .skipped_this_frame
+          .set(self.skipped_this_frame.get() + 1);
+        return false;
+      }
+
+      self.rasterized_this_frame.set(rasterized + 1);
+    }
+
+    font.cache_glyph(c, size);
+    font.chars.borrow().contains_key(&(c, size))
+  }
+
   /// Loads font from bytes with a given name and scale
   ///
+  /// Bytes can be borrowed or owned, like a font downloaded at runtime,
+  /// the font keeps its own copy of them, so they don't have to outlive [Fonts]
   ///
   /// What Scale does
   /// ---------------
@@ -264,20 +872,98 @@ impl<'a> Fonts<'a> {
   /// rendered smaller than this scale will look the same but perform slightly worse, while
   /// glyphs rendered larger than this will looks worse but perform slightly better. The units of
   /// the scale are pixels per Em unit.
-  pub fn load_font_from_bytes_with_scale(
+  pub fn load_font_from_bytes_with_scale<'b>(
+    &mut self,
+    name: &'a str,
+    bytes: impl Into<Cow<'b, [u8]>>,
+    scale: f32,
+  ) -> FontResult<()> {
+    self.load_font_from_bytes_with_index(name, bytes, scale, 0)
+  }
+
+  /// Loads a face from a font collection (.ttc/.otc) from bytes
+  /// with a given name, scale and collection index
+  ///
+  /// Use [collection_faces] to find out which faces a collection contains
+  ///
+  /// **Example**
+  /// ```rs
+  /// let index = collection_faces(SOURCE_HAN_SANS)
+  ///   .into_iter()
+  ///   .find(|face| face.family.as_deref() == Some("Source Han Sans JP"))
+  ///   .unwrap()
+  ///   .index;
+  ///
+  /// fonts.load_font_from_bytes_with_index("Source Han Sans JP", SOURCE_HAN_SANS, 100.0, index)?;
+  /// ```
+  ///
+  /// **See** [Self::load_font_from_bytes_with_scale]
+  pub fn load_font_from_bytes_with_index<'b>(
     &mut self,
     name: &'a str,
-    bytes: &[u8],
+    bytes: impl Into<Cow<'b, [u8]>>,
     scale: f32,
+    collection_index: u32,
   ) -> FontResult<()> {
     let settings = FontSettings {
-      collection_index: 0,
+      collection_index,
       scale,
     };
-    let font = FontdueFont::from_bytes(bytes, settings)?;
+    let bytes = bytes.into();
+    FontError::check_format(&bytes)?;
+    let font = FontdueFont::from_bytes(&*bytes, settings).map_err(FontError::Parse)?;
+    let data = Arc::<[u8]>::from(&*bytes);
+    let rasterizer = self.raster_backend.create(data.clone(), collection_index)?;
+
+    let mut font = Font::new(
+      name,
+      font,
+      data,
+      collection_index,
+      self.default_sm,
+      self.atlas_config,
+      rasterizer,
+    );
+    font.set_gamma(self.gamma);
 
     self.index_by_name.insert(name, self.fonts.len());
-    self.fonts.push(Font::new(name, font, self.default_sm));
+    self.fonts.push(font);
+    self.layout_cache.get_mut().clear();
+
+    Ok(())
+  }
+
+  /// Loads font from bytes as a face of a font family with a given name and scale
+  ///
+  /// Only the regular face of a family is used for fallback lookups,
+  /// the other faces get picked by [TextParams::weight] and [TextParams::style]
+  /// when the regular face is the one that got picked,
+  /// if a family doesn't have the requested face it gets synthesized
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.load_family_face_from_bytes("Noto Sans", "Noto Sans", FontFace::REGULAR, NOTO_SANS, 100.0)?;
+  /// fonts.load_family_face_from_bytes("Noto Sans", "Noto Sans Bold", FontFace::BOLD, NOTO_SANS_BOLD, 100.0)?;
+  /// ```
+  ///
+  /// **See** [Self::load_font_from_bytes_with_scale]
+  pub fn load_family_face_from_bytes<'b>(
+    &mut self,
+    family: &'a str,
+    name: &'a str,
+    face: FontFace,
+    bytes: impl Into<Cow<'b, [u8]>>,
+    scale: f32,
+  ) -> FontResult<()> {
+    self.load_font_from_bytes_with_scale(name, bytes, scale)?;
+
+    let index = self.fonts.len() - 1;
+    let font = &mut self.fonts[index];
+
+    font.family = Some(family);
+    font.face = face;
+    self.faces_by_family.insert((family, face), index);
+    self.layout_cache.get_mut().clear();
 
     Ok(())
   }
@@ -285,14 +971,18 @@ impl<'a> Fonts<'a> {
   /// Loads font from bytes with a given name and a default scale of 100.0
   ///
   /// **See** [Self::load_font_from_bytes_with_scale]
-  pub fn load_font_from_bytes(&mut self, name: &'a str, bytes: &[u8]) -> FontResult<()> {
+  pub fn load_font_from_bytes<'b>(
+    &mut self,
+    name: &'a str,
+    bytes: impl Into<Cow<'b, [u8]>>,
+  ) -> FontResult<()> {
     self.load_font_from_bytes_with_scale(name, bytes, 100.0)
   }
 
   /// Loads font from a file with a given name and path and a default scale of 100.0
   ///
   /// **See** [Self::load_font_from_bytes_with_scale]
-  pub fn load_font_from_file(&mut self, name: &'a str, path: impl AsRef<Path>) -> IoResult<()> {
+  pub fn load_font_from_file(&mut self, name: &'a str, path: impl AsRef<Path>) -> FontResult<()> {
     self.load_font_from_file_with_scale(name, path, 100.0)
   }
 
@@ -304,12 +994,64 @@ impl<'a> Fonts<'a> {
     name: &'a str,
     path: impl AsRef<Path>,
     scale: f32,
-  ) -> IoResult<()> {
-    let bytes = read_file(path)?;
+  ) -> FontResult<()> {
+    self.load_font_from_file_with_index(name, path, scale, 0)
+  }
 
-    self
-      .load_font_from_bytes_with_scale(name, &bytes, scale)
-      .map_err(|err| IoError::new(IoErrorKind::InvalidData, err))
+  /// Loads a face from a font collection (.ttc/.otc) file
+  /// with a given name, path, scale and collection index
+  ///
+  /// **See** [Self::load_font_from_bytes_with_index]
+  pub fn load_font_from_file_with_index(
+    &mut self,
+    name: &'a str,
+    path: impl AsRef<Path>,
+    scale: f32,
+    collection_index: u32,
+  ) -> FontResult<()> {
+    let bytes = read_file(path.as_ref())?;
+
+    self.load_font_from_bytes_with_index(name, bytes, scale, collection_index)?;
+
+    #[cfg(feature = "hot-reload")]
+    self.watch_font(
+      name,
+      path.as_ref(),
+      FontSettings {
+        collection_index,
+        scale,
+      },
+    );
+
+    Ok(())
+  }
+
+  /// Loads font from a url with a given name and a default scale of 100.0,
+  /// this uses macroquad's [load_file] so it works on wasm32 where there is no filesystem,
+  /// on other platforms the url is treated as a path
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.load_font_from_url("Noto Sans", "assets/fonts/NotoSans-Regular.ttf").await?;
+  /// ```
+  ///
+  /// **See** [Self::load_font_from_bytes_with_scale]
+  pub async fn load_font_from_url(&mut self, name: &'a str, url: &str) -> FontResult<()> {
+    self.load_font_from_url_with_scale(name, url, 100.0).await
+  }
+
+  /// Loads font from a url with a given name, url and scale
+  ///
+  /// **See** [Self::load_font_from_url]
+  pub async fn load_font_from_url_with_scale(
+    &mut self,
+    name: &'a str,
+    url: &str,
+    scale: f32,
+  ) -> FontResult<()> {
+    let bytes = load_file(url).await?;
+
+    self.load_font_from_bytes_with_scale(name, bytes, scale)
   }
 
   /// Unloads a currently loaded font by its index
@@ -320,11 +1062,20 @@ impl<'a> Fonts<'a> {
       return;
     }
 
+    #[cfg(feature = "hot-reload")]
+    self.unwatch_font(self.fonts[index].name);
+
     self.fonts.remove(index);
+    self.layout_cache.get_mut().clear();
     self.index_by_name.clear();
+    self.faces_by_family.clear();
 
     for (index, font) in self.fonts.iter().enumerate() {
       self.index_by_name.insert(font.name, index);
+
+      if let Some(family) = font.family {
+        self.faces_by_family.insert((family, font.face), index);
+      }
     }
   }
 
@@ -336,91 +1087,356 @@ impl<'a> Fonts<'a> {
   }
 
   /// Gets a currently loaded font by its index
-  pub fn get_font_by_index(&self, index: usize) -> Option<&Font> {
+  pub fn get_font_by_index(&self, index: usize) -> Option<&Font<'a>> {
     self.fonts.get(index)
   }
 
-  /// Gets the first currently loaded font if it contains this character
-  pub fn get_index_by_char(&self, c: char) -> Option<usize> {
-    self.fonts.iter().position(|it| it.contains(c))
-  }
-
-  /// Gets a currently loaded font index by its name
-  pub fn get_index_by_name(&self, name: &str) -> Option<usize> {
-    self.index_by_name.get(name).copied()
+  /// Sets which fonts are tried first, in order, for characters inside a given range,
+  /// if none of them contain a character it falls back to the load order
+  ///
+  /// Ranges set later take priority over ranges set earlier
+  ///
+  /// **Example**
+  /// ```rs
+  /// // Use Noto Sans JP for Han even though Noto Sans KR also covers it
+  /// fonts.set_range_fallback('\u{4E00}'..='\u{9FFF}', &["Noto Sans JP", "Noto Sans KR"]);
+  /// ```
+  pub fn set_range_fallback(&mut self, range: RangeInclusive<char>, fonts: &[&'a str]) {
+    self.range_fallbacks.retain(|(it, _)| *it != range);
+    self.range_fallbacks.push((range, fonts.to_vec()));
+    self.layout_cache.get_mut().clear();
   }
 
-  /// Gets a currently loaded font by its name
-  pub fn get_font_by_name(&self, name: &str) -> Option<&Font> {
-    self.get_font_by_index(self.get_index_by_name(name)?)
+  /// Sets which fonts are tried first, in order, for every character of a [Script]
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_script_fallback(Script::Han, &["Noto Sans JP", "Noto Sans KR"]);
+  /// ```
+  ///
+  /// **See** [Self::set_range_fallback]
+  pub fn set_script_fallback(&mut self, script: Script, fonts: &[&'a str]) {
+    for range in script.ranges() {
+      self.set_range_fallback(range.clone(), fonts);
+    }
   }
 
-  /// Gets the first currently loaded font if it contains this character
-  pub fn get_font_by_char(&self, c: char) -> Option<&Font> {
-    self.get_font_by_index(self.get_index_by_char(c)?)
+  /// Removes all fallbacks set by [Self::set_range_fallback] and [Self::set_script_fallback],
+  /// going back to strictly using the load order
+  pub fn clear_range_fallbacks(&mut self) {
+    self.range_fallbacks.clear();
+    self.layout_cache.get_mut().clear();
   }
 
-  /// Gets the first currently loaded font if it contains this character,
-  /// if no font that contains this character is found, it will return the first loaded font,
-  /// **if no fonts are loaded then it will panic**
-  pub fn get_font_by_char_or_panic(&self, c: char) -> &Font {
-    self
-      .get_font_by_char(c)
-      .or_else(|| self.fonts.first())
-      .expect("There is no font currently loaded")
+  /// Sets the font that's only used for emoji, see [Script::Emoji],
+  /// it's tried before the load order for emoji and never used for other characters,
+  /// so loading an emoji font doesn't take over symbols like arrows from the main font,
+  /// `None` treats every font the same
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.load_font_from_file("Noto Emoji", "./assets/fonts/NotoEmoji-Regular.ttf")?;
+  /// fonts.set_emoji_font(Some("Noto Emoji"));
+  /// ```
+  pub fn set_emoji_font(&mut self, name: Option<&'a str>) {
+    self.emoji_font = name;
+    self.layout_cache.get_mut().clear();
   }
 
-  /// Checks if any fonts supports this character
-  pub fn contains(&self, c: char) -> bool {
-    self.fonts.iter().any(|f| f.contains(c))
+  /// Returns the name of the font that's only used for emoji
+  ///
+  /// **See** [Self::set_emoji_font]
+  pub fn emoji_font(&self) -> Option<&'a str> {
+    self.emoji_font
   }
 
-  /// Measures text with a given font size
+  /// Gets the first currently loaded font if it contains this character,
+  /// fonts set by [Self::set_range_fallback] are tried before the load order,
+  /// then the font set by [Self::set_emoji_font] for emoji
+  pub fn get_index_by_char(&self, c: char) -> Option<usize> {
+    let ranged = self
+      .range_fallbacks
+      .iter()
+      .rev()
+      .filter(|(range, _)| range.contains(&c))
+      .flat_map(|(_, names)| names.iter())
+      .filter_map(|name| self.get_index_by_name(name))
+      .find(|index| self.fonts[*index].contains(c));
+
+    if ranged.is_some() {
+      return ranged;
+    }
+
+    let emoji = self
+      .emoji_font
+      .and_then(|name| self.get_index_by_name(name));
+
+    if let Some(index) = emoji.filter(|it| Script::Emoji.contains(c) && self.fonts[*it].contains(c))
+    {
+      return Some(index);
+    }
+
+    self
+      .fonts
+      .iter()
+      .enumerate()
+      .position(|(index, it)| Some(index) != emoji && it.face.is_regular() && it.contains(c))
+  }
+
+  /// Gets a currently loaded font index by its family or an alias of it and face
+  pub fn get_index_by_family(&self, family: &str, face: FontFace) -> Option<usize> {
+    let index = self.faces_by_family.get(&(family, face));
+    let aliased = || {
+      let family = self.get_alias(family)?;
+      self.faces_by_family.get(&(family, face))
+    };
+
+    index.or_else(aliased).copied()
+  }
+
+  /// Gets a currently loaded font by its family and face
+  pub fn get_font_by_family(&self, family: &str, face: FontFace) -> Option<&Font<'a>> {
+    self.get_font_by_index(self.get_index_by_family(family, face)?)
+  }
+
+  /// Gets the font that should render this character with a given [FontFace],
+  /// also returns what has to be synthesized if the family doesn't have that face
+  ///
+  /// **if no fonts are loaded then it will panic**
+  pub fn get_font_by_char_and_face(&self, c: char, face: FontFace) -> (&Font<'a>, FontFace) {
+    let (index, synthesized) = self.get_index_by_char_and_face(c, face);
+
+    (&self.fonts[index], synthesized)
+  }
+
+  /// Gets the index of the font that should render this character with given [TextParams],
+  /// respecting [TextParams::font], also returns what has to be synthesized
+  ///
+  /// **if no fonts are loaded then it will panic**
+  pub(crate) fn get_index_by_params(&self, c: char, params: &TextParams) -> (usize, FontFace) {
+    self
+      .try_get_index_by_params(c, params)
+      .expect("There is no font currently loaded")
+  }
+
+  /// Gets the index of the font that should render this character with given [TextParams]
+  /// like [Self::get_index_by_params], returns [FontError::NoFontsLoaded] if no fonts are loaded
+  pub(crate) fn try_get_index_by_params(
+    &self,
+    c: char,
+    params: &TextParams,
+  ) -> FontResult<(usize, FontFace)> {
+    let forced = params
+      .font
+      .filter(|it| self.fonts.get(*it).is_some_and(|font| font.contains(c)));
+
+    match forced {
+      Some(index) => Ok((index, params.face().synthesize_from(self.fonts[index].face))),
+      None => self.try_get_index_by_char_and_face(c, params.face()),
+    }
+  }
+
+  /// Gets the index of the font that should render this character with a given [FontFace],
+  /// also returns what has to be synthesized if the family doesn't have that face
+  ///
+  /// **if no fonts are loaded then it will panic**
+  pub fn get_index_by_char_and_face(&self, c: char, face: FontFace) -> (usize, FontFace) {
+    self
+      .try_get_index_by_char_and_face(c, face)
+      .expect("There is no font currently loaded")
+  }
+
+  /// Gets the index of the font that should render this character with a given [FontFace],
+  /// also returns what has to be synthesized if the family doesn't have that face,
+  /// if no fonts are loaded then it will return [FontError::NoFontsLoaded]
+  pub fn try_get_index_by_char_and_face(
+    &self,
+    c: char,
+    face: FontFace,
+  ) -> FontResult<(usize, FontFace)> {
+    if self.fonts.is_empty() {
+      return Err(FontError::NoFontsLoaded);
+    }
+
+    let index = self.get_index_by_char(c).unwrap_or_else(|| {
+      self.report_missing(c);
+      0
+    });
+    let font = &self.fonts[index];
+
+    if face.is_regular() {
+      return Ok((index, FontFace::REGULAR));
+    }
+
+    // the closest face the family has, only what it's missing gets synthesized
+    let found = font.family.and_then(|family| {
+      face.closest_faces().find_map(|closest| {
+        self
+          .get_index_by_family(family, closest)
+          .filter(|it| self.fonts[*it].contains(c))
+      })
+    });
+
+    Ok(match found {
+      Some(found) => (found, face.synthesize_from(self.fonts[found].face)),
+      None => (index, face.synthesize_from(font.face)),
+    })
+  }
+
+  /// Gets a currently loaded font index by its name or an alias of it
+  pub fn get_index_by_name(&self, name: &str) -> Option<usize> {
+    let index = self.index_by_name.get(name);
+    let aliased = || self.index_by_name.get(self.get_alias(name)?);
+
+    index.or_else(aliased).copied()
+  }
+
+  /// Registers another name a font or family can be looked up by,
+  /// so styles can refer to names like "body" and the actual font
+  /// can be swapped by changing a single alias
+  ///
+  /// Aliases are resolved when fonts are looked up, so the font doesn't have to be loaded yet,
+  /// names of loaded fonts take priority over aliases
   ///
   /// **Example**
   /// ```rs
-  /// let dimensions = fonts.measure_text("Some Text", 22);
+  /// fonts.load_font_from_file("Noto Sans", "./assets/fonts/NotoSans-Regular.ttf")?;
+  /// fonts.set_alias("body", "Noto Sans");
   ///
-  /// println!("width: {}, height: {}, offset_y: {}",
-  ///   dimensions.width,
-  ///   dimensions.height,
-  ///   dimensions.offset_y
-  /// )
+  /// let body = fonts.get_font_by_name("body").unwrap();
   /// ```
+  pub fn set_alias(&mut self, alias: &'a str, name: &'a str) {
+    self.aliases.insert(alias, name);
+    self.layout_cache.get_mut().clear();
+  }
+
+  /// Removes an alias, returns the name it stood for
+  pub fn remove_alias(&mut self, alias: &str) -> Option<&'a str> {
+    self.layout_cache.get_mut().clear();
+    self.aliases.remove(alias)
+  }
+
+  /// Returns the name an alias stands for
   ///
-  /// **See** [TextDimensions]
-  pub fn measure_text(&self, text: &str, size: f32) -> TextDimensions {
-    let mut width = 0f32;
-    let mut min_y = f32::MAX;
-    let mut max_y = f32::MIN;
+  /// **See** [Self::set_alias]
+  pub fn get_alias(&self, alias: &str) -> Option<&'a str> {
+    self.aliases.get(alias).copied()
+  }
 
-    for c in text.chars() {
-      let font = self.get_font_by_char_or_panic(c);
+  /// Gets a currently loaded font by its name
+  pub fn get_font_by_name(&self, name: &str) -> Option<&Font<'a>> {
+    self.get_font_by_index(self.get_index_by_name(name)?)
+  }
 
-      font.cache_glyph(c, size as u16);
+  /// Gets a mutable reference to a currently loaded font by its index,
+  /// used to change per font settings like [Font::baseline_offset]
+  pub fn get_font_by_index_mut(&mut self, index: usize) -> Option<&mut Font<'a>> {
+    self.layout_cache.get_mut().clear();
+    self.fonts.get_mut(index)
+  }
 
-      let info = font.chars.borrow()[&(c, size as u16)];
-      let glyph = font.atlas.borrow().get(info.id).unwrap().rect;
+  /// Gets a mutable reference to a currently loaded font by its name,
+  /// used to change per font settings like [Font::baseline_offset]
+  ///
+  /// **Example**
+  /// ```rs
+  /// let jp = fonts.get_font_by_name_mut("Noto Sans JP").unwrap();
+  /// jp.baseline_offset = 0.05;
+  /// jp.size_multiplier = 0.95;
+  /// ```
+  pub fn get_font_by_name_mut(&mut self, name: &str) -> Option<&mut Font<'a>> {
+    self.get_font_by_index_mut(self.get_index_by_name(name)?)
+  }
 
-      width += info.advance;
+  /// Gets the first currently loaded font if it contains this character
+  pub fn get_font_by_char(&self, c: char) -> Option<&Font<'a>> {
+    self.get_font_by_index(self.get_index_by_char(c)?)
+  }
 
-      if min_y > info.offset_y {
-        min_y = info.offset_y;
-      }
+  /// Gets the first currently loaded font if it contains this character,
+  /// if no font that contains this character is found, it will return the first loaded font,
+  /// **if no fonts are loaded then it will panic**
+  pub fn get_font_by_char_or_panic(&self, c: char) -> &Font<'a> {
+    self
+      .try_get_font_by_char(c)
+      .expect("There is no font currently loaded")
+  }
 
-      if max_y < glyph.h + info.offset_y {
-        max_y = glyph.h + info.offset_y;
-      }
-    }
+  /// Gets the first currently loaded font if it contains this character,
+  /// if no font that contains this character is found, it will return the first loaded font,
+  /// if no fonts are loaded then it will return [FontError::NoFontsLoaded]
+  pub fn try_get_font_by_char(&self, c: char) -> FontResult<&Font<'a>> {
+    self
+      .get_font_by_char(c)
+      .or_else(|| self.fonts.first())
+      .ok_or(FontError::NoFontsLoaded)
+  }
 
-    TextDimensions {
-      width,
-      height: max_y - min_y,
-      offset_y: max_y,
-    }
+  /// Reports which font each character of the text is drawn with using given [TextParams],
+  /// going through [TextParams::font], the requested face of the family and range fallbacks
+  /// like drawing does, `None` means the font doesn't contain that character
+  /// and it would be drawn as a missing glyph, or that no fonts are loaded
+  ///
+  /// Characters are reported like they're laid out, so characters that are skipped,
+  /// like control characters and composed combining marks, aren't reported
+  ///
+  /// Useful for debugging the load order of fallback fonts
+  ///
+  /// **Example**
+  /// ```rs
+  /// for (c, font) in fonts.resolve("Nice 良い、", &TextParams::default()) {
+  ///   println!("{c:?} -> {:?}", font.map(|it| it.name));
+  /// }
+  /// ```
+  pub fn resolve<'s>(
+    &'s self,
+    text: &'s str,
+    params: &TextParams,
+  ) -> impl Iterator<Item = (char, Option<&'s Font<'a>>)> + 's {
+    let params = *params;
+
+    self
+      .layout_chars(text, &params)
+      .filter(|(_, _, skipped)| !skipped)
+      .filter_map(|(_, c, _)| self.control_chars.apply(c))
+      .map(move |c| {
+        let font = self
+          .try_get_index_by_params(c, &params)
+          .ok()
+          .and_then(|(index, _)| self.fonts.get(index))
+          .filter(|it| it.contains(c));
+
+        (c, font)
+      })
+  }
+
+  /// Checks if any fonts supports this character
+  pub fn contains(&self, c: char) -> bool {
+    self.fonts.iter().any(|f| f.contains(c))
   }
 
-  /// Measures text with a given font size and scale
+  /// Measures text with a given font size,
+  /// '\n' starts a new line, the width is the width of the widest line
+  /// and the height goes from the top of the first line to the bottom of the last line
+  ///
+  /// **Example**
+  /// ```rs
+  /// let dimensions = fonts.measure_text("Some Text", 22);
+  ///
+  /// println!("width: {}, height: {}, offset_y: {}",
+  ///   dimensions.width,
+  ///   dimensions.height,
+  ///   dimensions.offset_y
+  /// )
+  /// ```
+  ///
+  /// **See** [MeasuredText]
+  pub fn measure_text(&self, text: &str, size: f32) -> MeasuredText {
+    self.measure_scaled_text(text, size, 1.0)
+  }
+
+  /// Measures text with a given font size and scale,
+  /// '\n' starts a new line like in [Self::measure_text]
   ///
   /// **Example**
   /// ```rs
@@ -433,38 +1449,177 @@ impl<'a> Fonts<'a> {
   /// )
   /// ```
   ///
-  /// **See** [TextDimensions]
-  pub fn measure_scaled_text(&self, text: &str, size: f32, scale: f32) -> TextDimensions {
+  /// **See** [MeasuredText]
+  pub fn measure_scaled_text(&self, text: &str, size: f32, scale: f32) -> MeasuredText {
+    let params = TextParams {
+      size,
+      scale,
+      ..Default::default()
+    };
+
+    if !text.contains('\n') {
+      return self.measure_params(text, &params);
+    }
+
+    let lines = self.split_lines(text, &params).map(|(line, line_params)| {
+      (
+        line,
+        line_params.y - params.y,
+        self.measure_params(line, &line_params),
+      )
+    });
+
+    Self::stack_lines(lines, &params)
+  }
+
+  /// Splits text at '\n' into lines, each with the params it's drawn with,
+  /// every line is moved down by the line height of the first loaded font,
+  /// measuring and drawing both use this so multi-line text is measured like it's drawn
+  pub(crate) fn split_lines<'t>(
+    &self,
+    text: &'t str,
+    params: &TextParams,
+  ) -> impl Iterator<Item = (&'t str, TextParams)> + 't {
+    let line_height = self.line_height(params);
+    let params = *params;
+
+    text.split('\n').enumerate().map(move |(i, line)| {
+      let params = TextParams {
+        y: params.y + i as f32 * line_height,
+        ..params
+      };
+
+      (line, params)
+    })
+  }
+
+  /// Returns the measurements of lines stacked on top of each other,
+  /// from each line, how far it's moved down from the first line and its measurements,
+  /// the width is the width of the widest line and the height goes from the top
+  /// of the first line to the bottom of the last line
+  pub(crate) fn stack_lines<'t>(
+    lines: impl IntoIterator<Item = (&'t str, f32, MeasuredText)>,
+    params: &TextParams,
+  ) -> MeasuredText {
+    let mut measured = MeasuredText {
+      lines: 0,
+      ..MeasuredText::empty(params)
+    };
     let mut width = 0f32;
-    let mut min_y = f32::MAX;
-    let mut max_y = f32::MIN;
+    let mut top = f32::MAX;
+    let mut bottom = f32::MIN;
+
+    for (line, dy, dimensions) in lines {
+      measured.add(&dimensions);
+      width = width.max(dimensions.width);
+
+      // empty lines still take up a line, but don't have a top or bottom
+      if !line.is_empty() {
+        top = top.min(dy - dimensions.offset_y);
+        bottom = bottom.max(dy - dimensions.offset_y + dimensions.height);
+      }
+    }
+
+    let dimensions = match top > bottom {
+      true => TextDimensions::default(),
+      false => TextDimensions {
+        width,
+        height: bottom - top,
+        offset_y: -top,
+      },
+    };
+
+    measured.with_dimensions(dimensions, params)
+  }
 
-    for c in text.chars() {
-      let font = self.get_font_by_char_or_panic(c);
+  /// Measures text the way it would be drawn with the given [TextParams]
+  pub(crate) fn measure_params(&self, text: &str, params: &TextParams) -> MeasuredText {
+    self.layout_measured(text, params, true, |_, _, _| {})
+  }
 
-      font.cache_glyph(c, size as u16);
+  /// Measures text with given [TextParams] using only font metrics,
+  /// glyphs that aren't cached yet don't get rasterized or added to the atlas,
+  /// useful for layout passes that might never draw the text, like trying out where to wrap
+  ///
+  /// **Example**
+  /// ```rs
+  /// let fits = fonts.measure_text_metrics(&candidate, &params).width <= max_width;
+  /// ```
+  pub fn measure_text_metrics(&self, text: &str, params: &TextParams) -> MeasuredText {
+    self.layout_measured(text, params, false, |_, _, _| {})
+  }
 
-      let info = font.chars.borrow()[&(c, size as u16)];
-      let glyph = font.atlas.borrow().get(info.id).unwrap().rect;
-      let h = glyph.h * scale;
-      let offset_y = info.offset_y * scale;
+  /// Places every glyph of the text, calling `f` with the byte index,
+  /// character and placement of each glyph, returns the dimensions of the whole text,
+  /// reuses the placements of recently laid out text,
+  /// glyphs that aren't cached only get cached if `rasterize` is true
+  pub(crate) fn layout_glyphs(
+    &self,
+    text: &str,
+    params: &TextParams,
+    rasterize: bool,
+    mut f: impl FnMut(usize, char, &GlyphPlacement<'_, 'a>),
+  ) -> TextDimensions {
+    // nothing can be laid out without fonts, the try_ functions report it as an error instead
+    if self.fonts.is_empty() {
+      return TextDimensions::default();
+    }
 
-      width += info.advance * scale;
+    let key = LayoutKey::new(text, params);
 
-      if min_y > offset_y {
-        min_y = offset_y;
+    if self.layout_cache_capacity() > 0 {
+      if let Some(dimensions) = self.replay_layout(&key, text, params, &mut f) {
+        return dimensions;
       }
+    }
+
+    let remember = self.layout_cache_capacity() > 0 && self.should_remember_layout(key);
 
-      if max_y < h + offset_y {
-        max_y = h + offset_y;
+    let baseline = self.baseline_y(params);
+    let mut width = 0f32;
+    let mut min_y = f32::MAX;
+    let mut max_y = f32::MIN;
+    let mut glyphs = remember.then(Vec::new);
+
+    for (index, c, skipped) in self.layout_chars(text, params) {
+      let placement = match skipped {
+        true => self.empty_placement(c, width, params),
+        false => self.place_glyph(c, width, params, rasterize),
+      };
+
+      if let Some(list) = &mut glyphs {
+        match CachedGlyph::new(index, c, &placement, params, baseline) {
+          Some(glyph) => list.push(glyph),
+          None => glyphs = None,
+        }
+      }
+
+      let top = baseline - placement.rect.y;
+      let bottom = baseline - placement.rect.bottom();
+
+      if min_y > bottom {
+        min_y = bottom;
+      }
+
+      if max_y < top {
+        max_y = top;
       }
+
+      f(index, c, &placement);
+      width += placement.advance;
     }
 
-    TextDimensions {
+    let dimensions = TextDimensions {
       width,
       height: max_y - min_y,
       offset_y: max_y,
+    };
+
+    if remember {
+      self.remember_layout(key, text, glyphs, dimensions);
     }
+
+    dimensions
   }
 
   /// Draws text with a given font size, draws from TopLeft
@@ -475,18 +1630,25 @@ impl<'a> Fonts<'a> {
   /// ```
   ///
   /// **See** [Self::draw_text_ex]
-  pub fn draw_text(&self, text: &str, x: f32, y: f32, size: f32, color: Color) -> TextDimensions {
-    self.draw_text_ex(text, &TextParams {
-      x,
-      y,
-      size,
-      scale: 1.0,
-      color,
-      draw: Default::default(),
-    })
+  pub fn draw_text(&self, text: &str, x: f32, y: f32, size: f32, color: Color) -> MeasuredText {
+    self.draw_text_ex(
+      text,
+      &TextParams {
+        x,
+        y,
+        size,
+        scale: 1.0,
+        color,
+        ..Default::default()
+      },
+    )
   }
 
-  /// Draws text with given [TextParams]
+  /// Draws text with given [TextParams], draws nothing if no fonts are loaded,
+  /// [Self::try_draw_text_ex] returns an error for that instead
+  ///
+  /// '\n' starts a new line one line height further down,
+  /// the dimensions are the same as [Self::measure_text] returns
   ///
   /// **Example**
   /// ```rs
@@ -500,7 +1662,11 @@ impl<'a> Fonts<'a> {
   ///   // Default Color
   ///   color: Color::from_rgba(255, 255, 255, 255),
   ///   // Default Draw method
-  ///   draw: DrawFrom::TopLeft
+  ///   draw: DrawFrom::TopLeft,
+  ///   // Default Weight
+  ///   weight: FontWeight::Regular,
+  ///   // Default Style
+  ///   style: FontStyle::Normal,
   /// });
   ///
   /// // Does the same as above
@@ -512,53 +1678,665 @@ impl<'a> Fonts<'a> {
   /// ```
   ///
   /// **See** [Self::draw_text]
-  pub fn draw_text_ex(&self, text: &str, params: &TextParams) -> TextDimensions {
-    let mut total_width = 0f32;
+  pub fn draw_text_ex(&self, text: &str, params: &TextParams) -> MeasuredText {
+    if !text.contains('\n') {
+      return self.draw_line_ex(text, params);
+    }
+
+    let lines = self.split_lines(text, params).map(|(line, line_params)| {
+      (
+        line,
+        line_params.y - params.y,
+        self.draw_line_ex(line, &line_params),
+      )
+    });
+
+    Self::stack_lines(lines, params)
+  }
 
-    for c in text.chars() {
-      let font = self.get_font_by_char_or_panic(c);
-      font.cache_glyph(c, params.size as u16);
+  /// Draws a single line of text with given [TextParams]
+  fn draw_line_ex(&self, text: &str, params: &TextParams) -> MeasuredText {
+    if self.is_text_culled(params) {
+      return self.measure_text_metrics(text, params);
     }
 
-    for c in text.chars() {
-      let advance = self.draw_char(c, total_width, params);
+    self.cache_text_glyphs(text, params);
+    self.write_text(text, params, &mut self.draw_sink(params), |_, _| true)
+  }
+
+  /// Builds meshes of text with given [TextParams] without drawing them,
+  /// with atlas UVs and vertex colors, submit them with [draw_mesh](macroquad::prelude::draw_mesh)
+  /// whenever and however you like, like with your own camera, transform or batching
+  ///
+  /// There's a mesh for every atlas the glyphs are in, which is one unless fallback fonts are used,
+  /// meshes reference the atlas textures, so build them again after the atlases change,
+  /// like after [Self::clear_cache]
+  ///
+  /// **Example**
+  /// ```rs
+  /// let meshes = fonts.build_mesh("Game Over", &TextParams::default());
+  ///
+  /// loop {
+  ///   for mesh in &meshes {
+  ///     draw_mesh(mesh);
+  ///   }
+  ///
+  ///   next_frame().await;
+  /// }
+  /// ```
+  ///
+  /// **See** [Self::draw_text_to_sink]
+  pub fn build_mesh(&self, text: &str, params: &TextParams) -> Vec<Mesh> {
+    let mut sink = MeshSink::new();
+
+    self.cache_text_glyphs(text, params);
+    self.draw_text_to_sink(text, params, &mut sink);
 
-      total_width += advance;
+    sink.into_meshes()
+  }
+
+  /// Draws text with given [TextParams] with every vertex moved by a transform,
+  /// which can be affine, like rotating text with a game object,
+  /// or projective, like tilting text away into a perspective crawl
+  ///
+  /// Glyphs are drawn right away, even if queuing,
+  /// returns the dimensions of the text before it's transformed
+  ///
+  /// Projective transforms don't correct texture coordinates for perspective,
+  /// so glyphs that are tilted a lot can look bent along their diagonal
+  ///
+  /// **Example**
+  /// ```rs
+  /// // rotate a label around the center of a spinning crate
+  /// let transform = Mat4::from_translation(vec3(crate_x, crate_y, 0.0))
+  ///   * Mat4::from_rotation_z(crate_angle)
+  ///   * Mat4::from_translation(vec3(-crate_x, -crate_y, 0.0));
+  ///
+  /// fonts.draw_text_transformed("Fragile", &params, transform);
+  /// ```
+  pub fn draw_text_transformed(
+    &self,
+    text: &str,
+    params: &TextParams,
+    transform: Mat4,
+  ) -> MeasuredText {
+    let mut sink = MeshSink::new();
+
+    self.cache_text_glyphs(text, params);
+    let dimensions = self.draw_text_to_sink(text, params, &mut sink);
+    let _screen = ScreenSpace::new(params.space);
+
+    for mut mesh in sink.into_meshes() {
+      for vertex in &mut mesh.vertices {
+        vertex.position = transform.project_point3(vertex.position);
+      }
+
+      draw_mesh(&mesh);
     }
 
-    self.measure_scaled_text(text, params.size, params.scale)
+    dimensions
   }
 
-  pub fn draw_char(&self, c: char, current_width: f32, params: &TextParams) -> f32 {
-    let font = self.get_font_by_char_or_panic(c);
-    font.cache_glyph(c, params.size as u16);
-    let mut atlas = font.atlas.borrow_mut();
-    let info = &font.chars.borrow()[&(c, params.size as u16)];
-    let glyph = atlas.get(info.id).unwrap().rect;
-    let w = glyph.w * params.scale;
-    let h = glyph.h * params.scale;
-    let offset_x = info.offset_x * params.scale;
-    let offset_y = info.offset_y * params.scale;
-    let advance = info.advance * params.scale;
+  /// Caches the glyphs of text before drawing it, so the atlas doesn't change halfway through,
+  /// text with a cached layout already has every glyph cached
+  pub(crate) fn cache_text_glyphs(&self, text: &str, params: &TextParams) {
+    if text.contains('\n') {
+      for (line, params) in self.split_lines(text, params) {
+        self.cache_text_glyphs(line, &params);
+      }
 
-    let mut y = 0.0 - h - offset_y + params.y;
+      return;
+    }
 
-    if let DrawFrom::TopLeft = params.draw {
-      y += params.size * params.scale;
+    if self.fonts.is_empty() || self.is_layout_cached(text, params) {
+      return;
     }
 
-    draw_texture_ex(
-      atlas.texture(),
-      offset_x + current_width + params.x,
-      y,
-      params.color,
-      DrawTextureParams {
-        dest_size: Some(vec2(w, h)),
-        source: Some(glyph),
-        ..Default::default()
-      },
+    let shown = self
+      .layout_chars(text, params)
+      .filter(|(_, _, skipped)| !skipped)
+      .filter_map(|(_, c, _)| self.control_chars.apply(c));
+
+    for c in shown.filter(|c| !is_blank(*c)) {
+      let (index, size) = self.raster_target(c, params);
+      self.try_cache_glyph(&self.fonts[index], c, size);
+    }
+  }
+
+  /// Returns the index of the font a character is drawn with and the size it's rasterized at
+  /// with given [TextParams], drawing and caching ahead of time both pick glyphs with this
+  pub(crate) fn raster_target(&self, c: char, params: &TextParams) -> (usize, u16) {
+    let index = self.get_index_by_params(c, params).0;
+
+    (index, self.raster_size(&self.fonts[index], params).0)
+  }
+
+  /// Draws text with given [TextParams] like [Self::draw_text_ex],
+  /// but returns [FontError::NoFontsLoaded] instead of drawing nothing when no fonts are loaded
+  pub fn try_draw_text_ex(&self, text: &str, params: &TextParams) -> FontResult<MeasuredText> {
+    if self.fonts.is_empty() && !text.is_empty() {
+      return Err(FontError::NoFontsLoaded);
+    }
+
+    Ok(self.draw_text_ex(text, params))
+  }
+
+  /// Draws text with given [TextParams] into a [QuadSink] instead of drawing it,
+  /// useful for writing glyphs into your own vertex format,
+  /// '\n' starts a new line like in [Self::draw_text_ex]
+  ///
+  /// **Example**
+  /// ```rs
+  /// let mut sink = MeshSink::new();
+  ///
+  /// fonts.draw_text_to_sink("Some Text", &TextParams::default(), &mut sink);
+  /// sink.draw();
+  /// ```
+  ///
+  /// **See** [Self::draw_text_ex]
+  pub fn draw_text_to_sink(
+    &self,
+    text: &str,
+    params: &TextParams,
+    sink: &mut impl QuadSink,
+  ) -> MeasuredText {
+    self.write_text(text, params, sink, |_, _| true)
+  }
+
+  /// Writes the quads of text into a sink, `paint` gets the number of every character
+  /// in the text and can change the params it's drawn with, characters it returns false for
+  /// aren't drawn, '\n' starts a new line like in [Self::draw_text_ex]
+  pub(crate) fn write_text(
+    &self,
+    text: &str,
+    params: &TextParams,
+    sink: &mut impl QuadSink,
+    mut paint: impl FnMut(usize, &mut TextParams) -> bool,
+  ) -> MeasuredText {
+    if !text.contains('\n') {
+      return self.write_line(text, params, sink, &mut paint);
+    }
+
+    let mut first = 0;
+    let lines = self.split_lines(text, params).map(|(line, line_params)| {
+      let start = first;
+      // the line break is a character of the text too
+      first += line.chars().count() + 1;
+
+      (
+        line,
+        line_params.y - params.y,
+        self.write_line(line, &line_params, sink, &mut |number, params| {
+          paint(start + number, params)
+        }),
+      )
+    });
+
+    Self::stack_lines(lines, params)
+  }
+
+  /// Writes the quads of a single line of text into a sink like [Self::write_text]
+  fn write_line(
+    &self,
+    text: &str,
+    params: &TextParams,
+    sink: &mut impl QuadSink,
+    paint: &mut impl FnMut(usize, &mut TextParams) -> bool,
+  ) -> MeasuredText {
+    if self.is_text_culled(params) {
+      return self.measure_text_metrics(text, params);
+    }
+
+    // the middle of the text glyphs get mirrored around
+    let center_x = match params.flip_x {
+      true => params.x + self.measure_text_metrics(text, params).width / 2.0,
+      false => params.x,
+    };
+    let mut number = 0;
+
+    self.layout_measured(text, params, true, |_, _, placement| {
+      let mut params = *params;
+      let shown = paint(number, &mut params);
+      number += 1;
+
+      if shown {
+        self.write_placement(placement, &params, center_x, sink);
+      }
+    })
+  }
+
+  /// Returns true if the line box of the text is above or below [TextParams::visible],
+  /// so nothing of it has to be drawn
+  fn is_text_culled(&self, params: &TextParams) -> bool {
+    if params.visible.is_none() {
+      return false;
+    }
+
+    let (top, bottom) = self.line_box(params);
+    params.is_line_culled(top, bottom)
+  }
+
+  /// Draws a single character with the pen `current_width` pixels right of [TextParams::x],
+  /// useful for laying out characters yourself, like bouncing damage numbers,
+  /// set [TextParams::font] to draw it with a specific font
+  ///
+  /// **Example**
+  /// ```rs
+  /// let mut x = 0.0;
+  ///
+  /// for (i, c) in "1337".chars().enumerate() {
+  ///   let params = TextParams {
+  ///     y: y - (time * 8.0 + i as f32).sin().abs() * 6.0,
+  ///     ..params
+  ///   };
+  ///
+  ///   x += fonts.draw_char(c, x, &params).advance;
+  /// }
+  /// ```
+  pub fn draw_char(&self, c: char, current_width: f32, params: &TextParams) -> DrawnChar {
+    self.write_glyph(c, current_width, params, &mut self.draw_sink(params))
+  }
+
+  /// Returns where the baseline is on screen for the given [TextParams]
+  pub(crate) fn baseline_y(&self, params: &TextParams) -> f32 {
+    match params.draw {
+      DrawFrom::TopLeft => params.y + params.size * params.vertical_scale(),
+      DrawFrom::BottomLeft => {
+        let descent = self
+          .fonts
+          .first()
+          .and_then(|font| font.horizontal_line_metrics(params.size))
+          .map_or(0.0, |metrics| metrics.descent);
+
+        params.y + descent * params.vertical_scale()
+      }
+      DrawFrom::Baseline => params.y,
+    }
+  }
+
+  /// Draws a line under text of a given width starting at [TextParams::x]
+  pub(crate) fn draw_underline(&self, params: &TextParams, width: f32) {
+    let (top, bottom) = self.line_box(params);
+    let y = self.baseline_y(params) + (bottom - top) * 0.1;
+    let thickness = (params.size * params.vertical_scale() / 16.0).max(1.0);
+
+    let _screen = ScreenSpace::new(params.space);
+
+    draw_line(params.x, y, params.x + width, y, thickness, params.color);
+  }
+
+  /// How far a synthesized bold glyph gets smeared, 0 if bold wasn't synthesized
+  fn synthetic_bold_offset(synthesized: FontFace, params: &TextParams) -> f32 {
+    match synthesized.weight {
+      FontWeight::Bold => (params.size * params.horizontal_scale() / 24.0).max(1.0),
+      FontWeight::Regular => 0.0,
+    }
+  }
+
+  /// Returns the size glyphs of a font get rasterized at for the given [TextParams]
+  /// and what the rasterized glyphs have to be scaled by,
+  /// before [TextParams::scale_x] and [TextParams::scale_y]
+  ///
+  /// **See** [Self::set_rasterize_downscaled]
+  fn raster_size(&self, font: &Font, params: &TextParams) -> (u16, f32) {
+    let size = font.adjusted_size(params.size);
+
+    if self.rasterize_downscaled && params.scale < 1.0 {
+      // round up and scale the rest of the way down so the text stays the same width
+      let raster = (size * params.scale).ceil().max(1.0);
+
+      (raster as u16, size * params.scale / raster)
+    } else {
+      (size as u16, params.scale)
+    }
+  }
+
+  /// Places a character that takes up no space and draws nothing with the pen at `pen_x`
+  fn empty_placement(&self, c: char, pen_x: f32, params: &TextParams) -> GlyphPlacement<'_, 'a> {
+    let (font_index, synthesized) = self.get_index_by_params(c, params);
+
+    GlyphPlacement {
+      font: &self.fonts[font_index],
+      font_index,
+      synthesized,
+      sprite: None,
+      id: 0,
+      blank: true,
+      advance: 0.0,
+      rect: Rect::new(params.x + pen_x, self.baseline_y(params), 0.0, 0.0),
+    }
+  }
+
+  /// Places a single glyph with the pen at `pen_x`, caching it if needed and `rasterize` is true
+  pub(crate) fn place_glyph(
+    &self,
+    c: char,
+    pen_x: f32,
+    params: &TextParams,
+    rasterize: bool,
+  ) -> GlyphPlacement<'_, 'a> {
+    let Some(c) = self.control_chars.apply(c) else {
+      return self.empty_placement(c, pen_x, params);
+    };
+
+    let (font_index, synthesized) = self.get_index_by_params(c, params);
+    let font = &self.fonts[font_index];
+
+    let (size, glyph_scale) = self.raster_size(font, params);
+    let blank = is_blank(c);
+    // blank characters only move the pen, so they never take up space in the atlas
+    let cached = if blank {
+      false
+    } else if rasterize {
+      self.try_cache_glyph(font, c, size)
+    } else {
+      font.chars.borrow().contains_key(&(c, size))
+    };
+
+    let (id, sprite, offset_x, offset_y, advance, w, h) = if cached {
+      let info = font.chars.borrow()[&(c, size)];
+      let sprite = font.atlases.borrow().get(info.id).unwrap().rect;
+
+      let CharacterInfo {
+        offset_x,
+        offset_y,
+        advance,
+        ..
+      } = info;
+
+      (
+        info.id,
+        Some(sprite),
+        offset_x,
+        offset_y,
+        advance,
+        sprite.w,
+        sprite.h,
+      )
+    } else {
+      let metrics = font.metrics(c, size as f32);
+
+      (
+        0,
+        None,
+        metrics.xmin as f32,
+        metrics.ymin as f32,
+        metrics.advance_width,
+        metrics.width as f32,
+        metrics.height as f32,
+      )
+    };
+
+    let w = w * glyph_scale * params.scale_x;
+    let h = h * glyph_scale * params.scale_y;
+    let offset_x = offset_x * glyph_scale * params.scale_x;
+    let offset_y = offset_y * glyph_scale * params.scale_y;
+    let y = self.baseline_y(params) - h - offset_y - font.baseline_shift(params);
+
+    GlyphPlacement {
+      font,
+      font_index,
+      synthesized,
+      sprite,
+      id,
+      blank,
+      advance: advance * glyph_scale * params.scale_x
+        + params.spacing * params.horizontal_scale()
+        + Self::synthetic_bold_offset(synthesized, params),
+      rect: Rect::new(params.x + pen_x + offset_x, y, w, h),
+    }
+  }
+
+  /// Writes the quads of a single glyph into a sink
+  fn write_glyph(
+    &self,
+    c: char,
+    current_width: f32,
+    params: &TextParams,
+    sink: &mut impl QuadSink,
+  ) -> DrawnChar {
+    let placement = self.place_glyph(c, current_width, params, true);
+    let center_x = params.x + current_width + placement.advance / 2.0;
+
+    self.write_placement(&placement, params, center_x, sink);
+
+    DrawnChar {
+      advance: placement.advance,
+      rect: placement.rect,
+      font_index: placement.font_index,
+    }
+  }
+
+  /// Writes the quads of an already placed glyph into a sink,
+  /// mirroring it around `center_x` if [TextParams::flip_x] is set
+  fn write_placement(
+    &self,
+    placement: &GlyphPlacement<'_, 'a>,
+    params: &TextParams,
+    center_x: f32,
+    sink: &mut impl QuadSink,
+  ) {
+    let Some(mut glyph) = placement.sprite else {
+      return;
+    };
+
+    let mut full = placement.rect;
+
+    if params.flip_x {
+      full.x = center_x * 2.0 - full.right();
+    }
+
+    if params.flip_y {
+      let (top, bottom) = self.line_box(params);
+      full.y = top + bottom - full.bottom();
+    }
+
+    if params.visible.is_some_and(|it| !it.overlaps(&full)) {
+      return;
+    }
+
+    let mut dest = full;
+
+    if let Some(clip) = params.clip {
+      let Some(clipped) = dest.intersect(clip) else {
+        return;
+      };
+
+      // shrink the source by the same fraction the destination got clipped by,
+      // from the other side if the glyph is mirrored
+      let (sx, sy) = (glyph.w / dest.w, glyph.h / dest.h);
+      let left = match params.flip_x {
+        true => dest.right() - clipped.right(),
+        false => clipped.x - dest.x,
+      };
+      let top = match params.flip_y {
+        true => dest.bottom() - clipped.bottom(),
+        false => clipped.y - dest.y,
+      };
+
+      glyph = Rect::new(
+        glyph.x + left * sx,
+        glyph.y + top * sy,
+        clipped.w * sx,
+        clipped.h * sy,
+      );
+      dest = clipped;
+    }
+
+    // the colors at the top and bottom of what's left of the glyph after clipping,
+    // the top of the glyph is at the bottom if it's mirrored vertically
+    let (color, bottom_color) = match params.bottom_color {
+      Some(bottom) if full.h > 0.0 => {
+        let fraction = |y: f32| match params.flip_y {
+          true => (full.bottom() - y) / full.h,
+          false => (y - full.y) / full.h,
+        };
+
+        (
+          mix_color(params.color, bottom, fraction(dest.y)),
+          mix_color(params.color, bottom, fraction(dest.bottom())),
+        )
+      }
+      bottom => (params.color, bottom.unwrap_or(params.color)),
+    };
+
+    let mut atlases = placement.font.atlases.borrow_mut();
+    let atlas = atlases.atlas_mut(placement.id);
+    let mut bold_offset = Self::synthetic_bold_offset(placement.synthesized, params);
+    let mut skew = match placement.synthesized.style {
+      FontStyle::Italic => dest.h * 0.2,
+      FontStyle::Normal => 0.0,
+    };
+
+    // mirrored italics lean the other way
+    if params.flip_x {
+      skew = -skew;
+      bold_offset = -bold_offset;
+    }
+
+    // the top of the glyph is at the bottom, so the bottom edge is the one that's shifted
+    if params.flip_y {
+      dest.x += skew;
+      skew = -skew;
+    }
+
+    let (atlas_w, atlas_h) = (atlas.width() as f32, atlas.height() as f32);
+    let quad = GlyphQuad {
+      texture: atlas.texture().clone(),
+      dest,
+      source: glyph,
+      uv: Rect::new(
+        glyph.x / atlas_w,
+        glyph.y / atlas_h,
+        glyph.w / atlas_w,
+        glyph.h / atlas_h,
+      ),
+      color,
+      bottom_color,
+      z: params.z,
+      skew,
+      flip_x: params.flip_x,
+      flip_y: params.flip_y,
+      rotation: params.rotation,
+    };
+
+    if bold_offset != 0.0 {
+      let mut bold = quad.clone();
+      bold.dest.x += bold_offset;
+      sink.push_quad(bold);
+    }
+
+    sink.push_quad(quad);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+  const NOTO_SANS_JP: &[u8] = include_bytes!("../assets/fonts/NotoSansJP-Regular.otf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+      .load_font_from_bytes("Noto Sans JP", NOTO_SANS_JP)
+      .unwrap();
+    fonts
+  }
+
+  fn resolved_names(
+    fonts: &Fonts<'static>,
+    text: &str,
+    params: &TextParams,
+  ) -> Vec<Option<&'static str>> {
+    fonts
+      .resolve(text, params)
+      .map(|(_, font)| font.map(|it| it.name))
+      .collect()
+  }
+
+  #[test]
+  fn resolve_follows_load_order() {
+    let fonts = fonts();
+    let names = resolved_names(&fonts, "A良\u{10FFFD}", &TextParams::default());
+
+    assert_eq!(names, [Some("Noto Sans"), Some("Noto Sans JP"), None]);
+  }
+
+  #[test]
+  fn resolve_respects_params_font() {
+    let fonts = fonts();
+    let params = TextParams {
+      font: fonts.get_index_by_name("Noto Sans JP"),
+      ..Default::default()
+    };
+
+    assert_eq!(resolved_names(&fonts, "A", &params), [Some("Noto Sans JP")]);
+  }
+
+  #[test]
+  fn resolve_respects_range_fallbacks() {
+    let mut fonts = fonts();
+    fonts.set_range_fallback('A'..='Z', &["Noto Sans JP"]);
+
+    let names = resolved_names(&fonts, "Aa", &TextParams::default());
+
+    assert_eq!(names, [Some("Noto Sans JP"), Some("Noto Sans")]);
+  }
+
+  #[test]
+  fn split_lines_moves_lines_down() {
+    let fonts = fonts();
+    let params = TextParams {
+      y: 10.0,
+      ..Default::default()
+    };
+    let line_height = fonts.line_height(&params);
+    let lines = fonts.split_lines("a\n\nb", &params).collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].1.y, 10.0);
+    assert_eq!(
+      lines[2],
+      (
+        "b",
+        TextParams {
+          y: 10.0 + line_height * 2.0,
+          ..params
+        }
+      )
     );
+  }
+
+  #[test]
+  fn measure_text_stacks_lines() {
+    let fonts = fonts();
+    let wide = fonts.measure_text("wide line", 20.0);
+    let short = fonts.measure_text("short", 20.0);
+    let both = fonts.measure_text("short\nwide line", 20.0);
+    let line_height = fonts.line_height(&TextParams {
+      size: 20.0,
+      ..Default::default()
+    });
+
+    assert_eq!(both.width, wide.width);
+    assert_eq!(both.offset_y, short.offset_y);
+    assert!(both.height > line_height);
+    assert_eq!(fonts.measure_text("short\n", 20.0).height, short.height);
+  }
+
+  #[test]
+  fn resolve_without_fonts() {
+    let fonts = Fonts::default();
 
-    advance
+    assert_eq!(resolved_names(&fonts, "A", &TextParams::default()), [None]);
   }
 }
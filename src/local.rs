@@ -0,0 +1,68 @@
+use std::{
+  cell::{Ref, RefCell, RefMut},
+  rc::Rc,
+};
+
+use crate::Fonts;
+
+/// A cheaply cloneable handle to [Fonts] for a single thread, every clone shares
+/// the same fonts and glyph caches, so systems like the UI, world labels and debug overlays
+/// can each keep a handle instead of passing `&Fonts` through every function
+///
+/// Handles can't be sent to other threads, they use [Rc] and [RefCell] since glyph caches
+/// and atlas textures aren't thread safe and macroquad draws on a single thread anyway
+///
+/// **Example**
+/// ```rs
+/// let fonts = Fonts::from_fonts([("Noto Sans", NOTO_SANS)])?.into_local();
+///
+/// let hud = Hud { fonts: fonts.clone() };
+/// let labels = WorldLabels { fonts: fonts.clone() };
+///
+/// // inside of Hud
+/// self.fonts.borrow().draw_text("Score: 10", 10.0, 10.0, 22.0, WHITE);
+///
+/// // changing settings affects every handle
+/// fonts.borrow_mut().set_gamma(1.8);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LocalFonts<'a>(Rc<RefCell<Fonts<'a>>>);
+
+impl<'a> LocalFonts<'a> {
+  /// Shares a [Fonts] instance
+  pub fn new(fonts: Fonts<'a>) -> Self {
+    Self(Rc::new(RefCell::new(fonts)))
+  }
+
+  /// Borrows the fonts for drawing and measuring
+  ///
+  /// **Panics** if the fonts are mutably borrowed
+  pub fn borrow(&self) -> Ref<'_, Fonts<'a>> {
+    self.0.borrow()
+  }
+
+  /// Borrows the fonts for loading fonts and changing settings
+  ///
+  /// **Panics** if the fonts are borrowed
+  pub fn borrow_mut(&self) -> RefMut<'_, Fonts<'a>> {
+    self.0.borrow_mut()
+  }
+
+  /// Returns true if both handles share the same fonts
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.0, &other.0)
+  }
+}
+
+impl<'a> From<Fonts<'a>> for LocalFonts<'a> {
+  fn from(fonts: Fonts<'a>) -> Self {
+    Self::new(fonts)
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Turns these fonts into a [LocalFonts] handle that can be cloned cheaply
+  pub fn into_local(self) -> LocalFonts<'a> {
+    LocalFonts::new(self)
+  }
+}
@@ -0,0 +1,189 @@
+use crate::{Fonts, TextParams};
+
+/// Which digits '0' to '9' are drawn as
+///
+/// **Default** [Digits::Latin]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Digits {
+  /// 0123456789, digits are drawn as they are
+  #[default]
+  Latin,
+  /// ٠١٢٣٤٥٦٧٨٩
+  ArabicIndic,
+  /// ۰۱۲۳۴۵۶۷۸۹, used for Persian and Urdu
+  ExtendedArabicIndic,
+  /// ०१२३४५६७८९
+  Devanagari,
+  /// ০১২৩৪৫৬৭৮৯
+  Bengali,
+  /// ๐๑๒๓๔๕๖๗๘๙
+  Thai,
+}
+
+impl Digits {
+  /// Returns the character a character is drawn as, only digits change
+  pub fn apply(&self, c: char) -> char {
+    let zero = match self {
+      Self::Latin => return c,
+      Self::ArabicIndic => 0x0660,
+      Self::ExtendedArabicIndic => 0x06F0,
+      Self::Devanagari => 0x0966,
+      Self::Bengali => 0x09E6,
+      Self::Thai => 0x0E50,
+    };
+
+    match c.is_ascii_digit() {
+      true => char::from_u32(zero + (c as u32 - '0' as u32)).unwrap_or(c),
+      false => c,
+    }
+  }
+}
+
+/// Language rules for drawing text, like how case changes and which digits are drawn
+///
+/// **See** [Fonts::set_locale], [TextParams::locale]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Locale {
+  /// Cases 'i' and 'ı' like Turkish and Azerbaijani, 'i' uppercases to 'İ'
+  /// and 'I' lowercases to 'ı', only matters for [TextTransform](crate::TextTransform)
+  ///
+  /// **Default** `false`
+  pub turkic_casing: bool,
+  /// Which digits are drawn
+  ///
+  /// **Default** [Digits::Latin]
+  pub digits: Digits,
+}
+
+impl Locale {
+  pub const TURKISH: Self = Self {
+    turkic_casing: true,
+    digits: Digits::Latin,
+  };
+
+  /// Cases 'i' and 'ı' the same way as [Self::TURKISH]
+  pub const AZERBAIJANI: Self = Self::TURKISH;
+
+  /// Uppercases text with the casing rules of this locale
+  pub fn to_uppercase(&self, text: &str) -> String {
+    match self.turkic_casing {
+      true => text.replace('i', "İ").to_uppercase(),
+      false => text.to_uppercase(),
+    }
+  }
+
+  /// Lowercases text with the casing rules of this locale
+  pub fn to_lowercase(&self, text: &str) -> String {
+    match self.turkic_casing {
+      true => text.replace('I', "ı").replace('İ', "i").to_lowercase(),
+      false => text.to_lowercase(),
+    }
+  }
+
+  /// Uppercases a single character with the casing rules of this locale
+  pub(crate) fn uppercase_char(&self, c: char, into: &mut String) {
+    match c {
+      'i' if self.turkic_casing => into.push('İ'),
+      _ => into.extend(c.to_uppercase()),
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Sets the [Locale] text is drawn with when [TextParams::locale] is `None`
+  ///
+  /// **Default** [Locale::default]
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_locale(Locale {
+  ///   digits: Digits::ArabicIndic,
+  ///   ..Default::default()
+  /// });
+  ///
+  /// // drawn as "النقاط: ١٢٣"
+  /// fonts.draw_text("النقاط: 123", 20.0, 20.0, 22.0, WHITE);
+  /// ```
+  pub fn set_locale(&mut self, locale: Locale) {
+    self.locale = locale;
+    self.layout_cache.get_mut().clear();
+  }
+
+  /// Returns the [Locale] text is drawn with when [TextParams::locale] is `None`
+  ///
+  /// **See** [Self::set_locale]
+  pub fn locale(&self) -> Locale {
+    self.locale
+  }
+
+  /// Returns the [Locale] text is drawn with for given [TextParams]
+  pub(crate) fn params_locale(&self, params: &TextParams) -> Locale {
+    params.locale.unwrap_or(self.locale)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn turkic_casing() {
+    for locale in [Locale::TURKISH, Locale::AZERBAIJANI] {
+      assert_eq!(locale.to_uppercase("istanbul ılık"), "İSTANBUL ILIK");
+      assert_eq!(locale.to_lowercase("İSTANBUL ILIK"), "istanbul ılık");
+
+      let mut upper = String::new();
+      "iı"
+        .chars()
+        .for_each(|c| locale.uppercase_char(c, &mut upper));
+      assert_eq!(upper, "İI");
+    }
+  }
+
+  #[test]
+  fn default_casing() {
+    let locale = Locale::default();
+
+    assert_eq!(locale.to_uppercase("istanbul ılık"), "ISTANBUL ILIK");
+    assert_eq!(locale.to_lowercase("ILIK"), "ilik");
+    assert_eq!(locale.to_lowercase("İ"), "i\u{307}");
+  }
+
+  #[test]
+  fn sharp_s_uppercases_to_two_letters() {
+    for locale in [Locale::default(), Locale::TURKISH] {
+      assert_eq!(locale.to_uppercase("straße"), "STRASSE");
+
+      let mut upper = String::new();
+      locale.uppercase_char('ß', &mut upper);
+      assert_eq!(upper, "SS");
+    }
+  }
+
+  #[test]
+  fn digits() {
+    let cases = [
+      (Digits::Latin, "0123456789"),
+      (Digits::ArabicIndic, "٠١٢٣٤٥٦٧٨٩"),
+      (Digits::ExtendedArabicIndic, "۰۱۲۳۴۵۶۷۸۹"),
+      (Digits::Devanagari, "०१२३४५६७८९"),
+      (Digits::Bengali, "০১২৩৪৫৬৭৮৯"),
+      (Digits::Thai, "๐๑๒๓๔๕๖๗๘๙"),
+    ];
+
+    for (digits, expected) in cases {
+      let applied = "0123456789"
+        .chars()
+        .map(|c| digits.apply(c))
+        .collect::<String>();
+
+      assert_eq!(applied, expected, "{digits:?}");
+      // only ascii digits change
+      assert_eq!(digits.apply('a'), 'a');
+      assert_eq!(digits.apply('٣'), '٣');
+    }
+  }
+}
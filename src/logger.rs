@@ -0,0 +1,232 @@
+use std::{
+  collections::VecDeque,
+  sync::{Mutex, MutexGuard, OnceLock},
+};
+
+use ::log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use macroquad::prelude::{Color, Rect, GRAY, ORANGE, RED, SKYBLUE, WHITE};
+
+use crate::{ColoredStr, Component, DrawFrom, Fonts, TextParams};
+
+static LOGGER: OnceLock<ScreenLogger> = OnceLock::new();
+
+/// A record captured by [ScreenLogger]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+  pub level: Level,
+  pub target: String,
+  pub message: String,
+}
+
+/// A [Log] implementation that keeps the most recent records in a ring buffer,
+/// so they can be drawn on screen with [Fonts::draw_log],
+/// useful for debugging on targets without a console
+///
+/// **Example**
+/// ```rs
+/// ScreenLogger::init(100, LevelFilter::Info).unwrap();
+///
+/// log::warn!("low on memory");
+///
+/// loop {
+///   fonts.draw_log(Rect::new(10.0, 10.0, 400.0, 200.0), &TextParams::default());
+///
+///   next_frame().await;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ScreenLogger {
+  records: Mutex<VecDeque<LogRecord>>,
+  capacity: usize,
+}
+
+impl ScreenLogger {
+  /// Sets the logger of the log crate to a logger that keeps the last `capacity` records
+  /// up to `level`, fails if a logger was already set
+  pub fn init(capacity: usize, level: LevelFilter) -> Result<&'static Self, SetLoggerError> {
+    let logger = LOGGER.get_or_init(|| Self {
+      records: Mutex::new(VecDeque::with_capacity(capacity)),
+      capacity,
+    });
+
+    ::log::set_logger(logger)?;
+    ::log::set_max_level(level);
+
+    Ok(logger)
+  }
+
+  /// Returns the logger set by [Self::init]
+  pub fn get() -> Option<&'static Self> {
+    LOGGER.get()
+  }
+
+  /// Returns a copy of the captured records, oldest first
+  pub fn records(&self) -> Vec<LogRecord> {
+    self.lock().iter().cloned().collect()
+  }
+
+  /// Removes every captured record
+  pub fn clear(&self) {
+    self.lock().clear();
+  }
+
+  fn lock(&self) -> MutexGuard<'_, VecDeque<LogRecord>> {
+    // a panic while holding the lock can't leave the buffer in a broken state
+    self.records.lock().unwrap_or_else(|it| it.into_inner())
+  }
+}
+
+impl Log for ScreenLogger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    self.capacity > 0 && metadata.level() <= ::log::max_level()
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+
+    let mut records = self.lock();
+
+    while records.len() >= self.capacity {
+      records.pop_front();
+    }
+
+    records.push_back(LogRecord {
+      level: record.level(),
+      target: record.target().to_string(),
+      message: record.args().to_string(),
+    });
+  }
+
+  fn flush(&self) {}
+}
+
+/// The name the level of a record is drawn with in front of its message
+fn level_name(level: Level) -> &'static str {
+  match level {
+    Level::Error => "[ERROR] ",
+    Level::Warn => "[WARN] ",
+    Level::Info => "[INFO] ",
+    Level::Debug => "[DEBUG] ",
+    Level::Trace => "[TRACE] ",
+  }
+}
+
+/// The color the level of a record is drawn with
+fn level_color(level: Level) -> Color {
+  match level {
+    Level::Error => RED,
+    Level::Warn => ORANGE,
+    Level::Info => WHITE,
+    Level::Debug => SKYBLUE,
+    Level::Trace => GRAY,
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws the records captured by [ScreenLogger] inside of a rect, newest at the bottom,
+  /// with the level of every record in its own color,
+  /// records that don't fit are cut off at the top
+  ///
+  /// Nothing is drawn if [ScreenLogger::init] wasn't called
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.draw_log(Rect::new(10.0, 10.0, 400.0, 200.0), &TextParams {
+  ///   size: 14.0,
+  ///   ..Default::default()
+  /// });
+  /// ```
+  pub fn draw_log(&self, rect: Rect, params: &TextParams) {
+    let Some(logger) = ScreenLogger::get() else {
+      return;
+    };
+
+    // drawing can log, like a missing glyph handler does, so the lock isn't held while drawing
+    let records = logger.records();
+    let line_height = self.line_height(params);
+    let params = TextParams {
+      x: rect.x,
+      draw: DrawFrom::TopLeft,
+      clip: Some(params.clip.map_or(rect, |it| {
+        it.intersect(rect)
+          .unwrap_or(Rect::new(rect.x, rect.y, 0.0, 0.0))
+      })),
+      ..*params
+    };
+    // every line of every record with the level in front of the first line, newest first
+    let lines = records.iter().rev().flat_map(|record| {
+      let lines = record.message.split('\n').collect::<Vec<_>>();
+
+      lines
+        .into_iter()
+        .enumerate()
+        .rev()
+        .map(move |(i, line)| ((i == 0).then_some(record.level), line))
+    });
+
+    let mut y = rect.bottom();
+
+    for (level, line) in lines {
+      y -= line_height;
+
+      if y + line_height <= rect.y {
+        break;
+      }
+
+      let mut text = ColoredStr::new();
+
+      if let Some(level) = level {
+        text.push(Component::Color(level_color(level)));
+        text.push(Component::Str(level_name(level)));
+        text.push(Component::Color(params.color));
+      }
+
+      text.push(Component::Str(line));
+
+      self.draw_colored_text_ex(&text, &TextParams { y, ..params });
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  #[test]
+  fn drawing_the_log_can_log() {
+    // the only test that sets the logger of the log crate
+    let logger = ScreenLogger::init(8, LevelFilter::Warn).unwrap();
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts.set_missing_glyph_handler(Some(Box::new(|c| ::log::warn!("missing glyph {c:?}"))));
+
+    ::log::warn!("no glyph for \u{E000}");
+
+    // clipped away, so no quad needs an atlas texture
+    fonts.draw_log(
+      Rect::new(0.0, 0.0, 400.0, 200.0),
+      &TextParams {
+        clip: Some(Rect::new(-100.0, -100.0, 1.0, 1.0)),
+        ..Default::default()
+      },
+    );
+
+    let records = logger.records();
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].message, "missing glyph '\\u{e000}'");
+    assert_eq!(level_name(records[1].level), "[WARN] ");
+  }
+}
@@ -0,0 +1,29 @@
+/// Builds a [ColoredStr](crate::ColoredStr) from strings, characters, colors,
+/// [FontWeight](crate::FontWeight)s, [FontStyle](crate::FontStyle)s and [Component](crate::Component)s
+///
+/// **Example**
+/// ```rs
+/// let text = colored_str!["Press ", RED, 'R', WHITE, " to restart"];
+///
+/// fonts.draw_colored_text_ex(&text, &TextParams::default());
+/// ```
+#[macro_export]
+macro_rules! colored_str {
+  [$($component:expr),* $(,)?] => {
+    $crate::ColoredStr::from(::std::vec![$($crate::Component::from($component)),*])
+  };
+}
+
+/// Formats text like [format!] and draws it with [Fonts::draw_fmt](crate::Fonts::draw_fmt),
+/// reusing a buffer instead of allocating a new string every call
+///
+/// **Example**
+/// ```rs
+/// draw_text!(fonts, &params, "score: {}", score);
+/// ```
+#[macro_export]
+macro_rules! draw_text {
+  ($fonts:expr, $params:expr, $($arg:tt)*) => {
+    $fonts.draw_fmt($params, ::std::format_args!($($arg)*))
+  };
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{FontResult, Fonts};
+
+/// A list of fonts to load, meant to be deserialized from a config file
+///
+/// **Example**
+/// ```rs
+/// // fonts.json
+/// // { "fonts": [
+/// //   { "name": "Noto Sans", "path": "assets/fonts/NotoSans-Regular.ttf" },
+/// //   { "name": "Noto Sans JP", "path": "assets/fonts/NotoSansJP-Regular.otf", "scale": 80.0 }
+/// // ] }
+///
+/// let manifest: FontManifest = serde_json::from_str(&load_string("fonts.json").await?)?;
+/// let mut fonts = Fonts::default();
+///
+/// fonts.load_from_manifest(&manifest)?;
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontManifest {
+  /// The fonts in the order they're loaded, which is the order they're used as fallbacks
+  pub fonts: Vec<FontManifestEntry>,
+}
+
+/// A single font of a [FontManifest]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FontManifestEntry {
+  pub name: String,
+  /// The path of the font file
+  pub path: String,
+  /// **Default** `100.0`
+  #[serde(default = "FontManifestEntry::default_scale")]
+  pub scale: f32,
+  /// Which face of a font collection to load
+  ///
+  /// **Default** `0`
+  #[serde(default)]
+  pub collection_index: u32,
+}
+
+impl FontManifestEntry {
+  fn default_scale() -> f32 {
+    100.0
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Loads every font of a [FontManifest] from files,
+  /// stops at the first font that fails to load
+  ///
+  /// **See** [Self::load_font_from_file_with_index]
+  pub fn load_from_manifest(&mut self, manifest: &'a FontManifest) -> FontResult<()> {
+    for entry in &manifest.fonts {
+      self.load_font_from_file_with_index(
+        &entry.name,
+        &entry.path,
+        entry.scale,
+        entry.collection_index,
+      )?;
+    }
+
+    Ok(())
+  }
+}
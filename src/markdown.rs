@@ -0,0 +1,203 @@
+use macroquad::prelude::{Color, TextDimensions};
+
+use crate::{
+  ColoredStr, Component, DrawFrom, FontStyle, FontWeight, Fonts, MeasuredText, TextParams,
+};
+
+/// How markdown is drawn by [Fonts::draw_markdown]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MarkdownStyle {
+  /// How normal text is drawn, text is always drawn from the top left
+  pub params: TextParams,
+  /// What the size of `#`, `##` and `###` headings is multiplied by
+  ///
+  /// **Default** `[2.0, 1.5, 1.25]`
+  pub heading_scale: [f32; 3],
+  /// The color of `inline code`
+  pub code_color: Color,
+  /// The character drawn in front of list items
+  ///
+  /// **Default** '•'
+  pub bullet: char,
+  /// How far list items are indented, as a fraction of the text size
+  ///
+  /// **Default** `1.5`
+  pub indent: f32,
+}
+
+impl Default for MarkdownStyle {
+  fn default() -> Self {
+    Self {
+      params: TextParams::default(),
+      heading_scale: [2.0, 1.5, 1.25],
+      code_color: Color::from_rgba(230, 160, 90, 255),
+      bullet: '•',
+      indent: 1.5,
+    }
+  }
+}
+
+/// An inline marker that switches something on and off
+#[derive(Copy, Clone, PartialEq)]
+enum Marker {
+  Bold,
+  Italic,
+  Code,
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws a small subset of markdown, meant for help screens and changelogs
+  ///
+  /// Supports `# headings` up to three levels, `- ` and `* ` list items,
+  /// `**bold**`, `*italic*` or `_italic_` and `` `inline code` ``,
+  /// markers that are never closed are drawn as text
+  ///
+  /// Returns the dimensions of the whole block of text
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.draw_markdown("# Changelog\n- **Fixed** the `jump` key\n- Added *more* levels", &MarkdownStyle {
+  ///   params: TextParams {
+  ///     x: 20.0,
+  ///     y: 20.0,
+  ///     ..Default::default()
+  ///   },
+  ///   ..Default::default()
+  /// });
+  /// ```
+  pub fn draw_markdown(&self, markdown: &str, style: &MarkdownStyle) -> MeasuredText {
+    let base = TextParams {
+      draw: DrawFrom::TopLeft,
+      ..style.params
+    };
+    let mut measured = MeasuredText::empty(&base);
+    let mut y = base.y;
+    let mut width = 0f32;
+
+    for line in markdown.lines() {
+      let (params, line, bullet) = Self::markdown_block(line, &base, style);
+      let mut params = TextParams { y, ..params };
+
+      if bullet {
+        let bullet = self.draw_text_ex(style.bullet.encode_utf8(&mut [0; 4]), &params);
+
+        measured.add(&bullet);
+        params.x += style.indent * params.size * params.horizontal_scale();
+      }
+
+      let text = Self::markdown_inline(line, &params, style.code_color);
+      let dimensions = self.draw_colored_text_ex(&text, &params);
+
+      measured.add(&dimensions);
+      width = width.max(params.x - base.x + dimensions.width);
+      y += self.line_height(&params);
+    }
+
+    let dimensions = TextDimensions {
+      width,
+      height: y - base.y,
+      offset_y: 0.0,
+    };
+
+    MeasuredText {
+      lines: markdown.lines().count().max(1),
+      ..measured
+    }
+    .with_dimensions(dimensions, &base)
+  }
+
+  /// Returns the params, the text without the block marker,
+  /// and if the line is a list item
+  fn markdown_block<'l>(
+    line: &'l str,
+    base: &TextParams,
+    style: &MarkdownStyle,
+  ) -> (TextParams, &'l str, bool) {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+
+    if (1..=3).contains(&level) {
+      if let Some(heading) = trimmed[level..].strip_prefix(' ') {
+        let params = TextParams {
+          size: base.size * style.heading_scale[level - 1],
+          weight: FontWeight::Bold,
+          ..*base
+        };
+
+        return (params, heading.trim(), false);
+      }
+    }
+
+    match trimmed
+      .strip_prefix("- ")
+      .or_else(|| trimmed.strip_prefix("* "))
+    {
+      Some(item) => (*base, item, true),
+      None => (*base, line, false),
+    }
+  }
+
+  /// Parses the inline markers of a line into a [ColoredStr],
+  /// closing a marker switches back to the face of `params`
+  fn markdown_inline<'l>(line: &'l str, params: &TextParams, code_color: Color) -> ColoredStr<'l> {
+    let mut text = ColoredStr::new();
+    let mut open: Vec<Marker> = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < line.len() {
+      let rest = &line[i..];
+      let (marker, len) = if rest.starts_with('`') {
+        (Marker::Code, 1)
+      } else if open.contains(&Marker::Code) {
+        i += rest.chars().next().map_or(1, char::len_utf8);
+        continue;
+      } else if rest.starts_with("**") {
+        (Marker::Bold, 2)
+      } else if rest.starts_with('*') || rest.starts_with('_') {
+        (Marker::Italic, 1)
+      } else {
+        i += rest.chars().next().map_or(1, char::len_utf8);
+        continue;
+      };
+
+      let closing = open.last() == Some(&marker);
+
+      // markers are only opened if they're closed later on the line
+      if !closing && (open.contains(&marker) || !rest[len..].contains(&rest[..len])) {
+        i += len;
+        continue;
+      }
+
+      if start < i {
+        text.push(Component::Str(&line[start..i]));
+      }
+
+      if closing {
+        open.pop();
+      } else {
+        open.push(marker);
+      }
+
+      let on = open.contains(&marker);
+
+      text.push(match marker {
+        Marker::Bold if on => Component::Weight(FontWeight::Bold),
+        Marker::Bold => Component::Weight(params.weight),
+        Marker::Italic if on => Component::Style(FontStyle::Italic),
+        Marker::Italic => Component::Style(params.style),
+        Marker::Code if on => Component::Color(code_color),
+        Marker::Code => Component::ResetColor,
+      });
+
+      i += len;
+      start = i;
+    }
+
+    if start < line.len() {
+      text.push(Component::Str(&line[start..]));
+    }
+
+    text
+  }
+}
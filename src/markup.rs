@@ -0,0 +1,324 @@
+use macroquad::{color::colors, prelude::Color};
+
+use crate::{ColoredStr, Component};
+
+/// Open tags while parsing markup, used to know what closing a tag switches back to
+#[derive(Default)]
+struct OpenTags<'a> {
+  colors: Vec<Color>,
+  sizes: Vec<f32>,
+  fonts: Vec<&'a str>,
+}
+
+impl<'a> OpenTags<'a> {
+  /// Opens or closes a tag, returns `None` if the tag isn't known
+  fn apply(&mut self, tag: &'a str) -> Option<Component<'a>> {
+    let component = match tag.split_once('=') {
+      Some(("color", value)) => {
+        let color = parse_color(value)?;
+
+        self.colors.push(color);
+        Component::Color(color)
+      }
+      Some(("size", value)) => {
+        let size = parse_size(value)?;
+
+        self.sizes.push(size);
+        Component::Size(size)
+      }
+      Some(("font", value)) => {
+        let font = unquote(value);
+
+        if font.is_empty() {
+          return None;
+        }
+
+        self.fonts.push(font);
+        Component::Font(font)
+      }
+      None if tag == "/color" => {
+        self.colors.pop();
+        self
+          .colors
+          .last()
+          .map_or(Component::ResetColor, |it| Component::Color(*it))
+      }
+      None if tag == "/size" => {
+        self.sizes.pop();
+        self
+          .sizes
+          .last()
+          .map_or(Component::ResetSize, |it| Component::Size(*it))
+      }
+      None if tag == "/font" => {
+        self.fonts.pop();
+        self
+          .fonts
+          .last()
+          .map_or(Component::ResetFont, |it| Component::Font(it))
+      }
+      _ => return None,
+    };
+
+    Some(component)
+  }
+}
+
+impl<'a> ColoredStr<'a> {
+  /// Parses text with inline tags into a [ColoredStr]
+  ///
+  /// - `<color=VALUE>` colors the text until `</color>`, values are hex colors
+  ///   like `#f04`, `#ff0044` or `#ff004480`, or names like `red` and `light_gray`
+  /// - `<size=VALUE>` changes the size of the text until `</size>`
+  /// - `<font=NAME>` draws the text with the font loaded with this name until `</font>`
+  ///
+  /// Tags can be nested, closing one switches back to the tag around it
+  ///
+  /// Tags that can't be parsed are kept as text, an unbalanced closing tag
+  /// switches back to the color or size of the [TextParams](crate::TextParams)
+  /// or to looking up fonts by character
+  ///
+  /// **Example**
+  /// ```rs
+  /// let text = ColoredStr::from_markup("<color=#ff0044>danger</color> <size=48>ahead</size>");
+  ///
+  /// fonts.draw_colored_text_ex(&text, &TextParams::default());
+  /// ```
+  pub fn from_markup(markup: &'a str) -> Self {
+    let mut text = ColoredStr::new();
+    let mut tags = OpenTags::default();
+    let mut rest = markup;
+
+    while let Some(start) = rest.find('<') {
+      let Some(end) = rest[start..].find('>').map(|it| start + it) else {
+        break;
+      };
+
+      // unknown tags stay part of the text, only skip past the '<'
+      let Some(component) = tags.apply(&rest[start + 1..end]) else {
+        text.push(Component::Str(&rest[..=start]));
+        rest = &rest[start + 1..];
+        continue;
+      };
+
+      if start > 0 {
+        text.push(Component::Str(&rest[..start]));
+      }
+
+      text.push(component);
+      rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+      text.push(Component::Str(rest));
+    }
+
+    text
+  }
+}
+
+/// Removes whitespace and quotes around a value
+fn unquote(value: &str) -> &str {
+  value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Parses a size, sizes have to be positive
+fn parse_size(value: &str) -> Option<f32> {
+  unquote(value)
+    .parse::<f32>()
+    .ok()
+    .filter(|it| it.is_finite() && *it > 0.0)
+}
+
+/// Parses a hex color (`#rgb`, `#rrggbb` or `#rrggbbaa`) or the name of a macroquad color
+pub(crate) fn parse_color(value: &str) -> Option<Color> {
+  let value = unquote(value);
+
+  let Some(hex) = value.strip_prefix('#') else {
+    return named_color(value);
+  };
+
+  if !hex.is_ascii() {
+    return None;
+  }
+
+  let channels: Vec<u8> = match hex.len() {
+    3 => hex
+      .chars()
+      .map(|c| c.to_digit(16).map(|it| it as u8 * 17))
+      .collect::<Option<_>>()?,
+    6 | 8 => (0..hex.len())
+      .step_by(2)
+      .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+      .collect::<Option<_>>()?,
+    _ => return None,
+  };
+
+  Some(Color::from_rgba(
+    channels[0],
+    channels[1],
+    channels[2],
+    channels.get(3).copied().unwrap_or(255),
+  ))
+}
+
+/// Returns the macroquad color with this name, ignoring case
+fn named_color(name: &str) -> Option<Color> {
+  let color = match name.to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+    "beige" => colors::BEIGE,
+    "black" => colors::BLACK,
+    "blank" | "transparent" => colors::BLANK,
+    "blue" => colors::BLUE,
+    "brown" => colors::BROWN,
+    "dark_blue" => colors::DARKBLUE,
+    "dark_brown" => colors::DARKBROWN,
+    "dark_gray" | "dark_grey" => colors::DARKGRAY,
+    "dark_green" => colors::DARKGREEN,
+    "dark_purple" => colors::DARKPURPLE,
+    "gold" => colors::GOLD,
+    "gray" | "grey" => colors::GRAY,
+    "green" => colors::GREEN,
+    "light_gray" | "light_grey" => colors::LIGHTGRAY,
+    "lime" => colors::LIME,
+    "magenta" => colors::MAGENTA,
+    "maroon" => colors::MAROON,
+    "orange" => colors::ORANGE,
+    "pink" => colors::PINK,
+    "purple" => colors::PURPLE,
+    "red" => colors::RED,
+    "sky_blue" => colors::SKYBLUE,
+    "violet" => colors::VIOLET,
+    "white" => colors::WHITE,
+    "yellow" => colors::YELLOW,
+    _ => return None,
+  };
+
+  Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::color::colors::{BLUE, RED};
+
+  use super::*;
+
+  fn components(markup: &str) -> Vec<Component<'_>> {
+    ColoredStr::from_markup(markup).components().to_vec()
+  }
+
+  #[test]
+  fn nested_tags_restore_the_outer_color() {
+    assert_eq!(
+      components("<color=red>a<color=blue>b</color>c</color>d"),
+      [
+        Component::Color(RED),
+        Component::Str("a"),
+        Component::Color(BLUE),
+        Component::Str("b"),
+        Component::Color(RED),
+        Component::Str("c"),
+        Component::ResetColor,
+        Component::Str("d"),
+      ]
+    );
+  }
+
+  #[test]
+  fn unclosed_tags_color_the_rest() {
+    let text = ColoredStr::from_markup("<color=red>red");
+
+    assert_eq!(
+      text.components(),
+      [Component::Color(RED), Component::Str("red")]
+    );
+    assert_eq!(text.text(), "red");
+  }
+
+  #[test]
+  fn unbalanced_close_resets_the_color() {
+    assert_eq!(
+      components("a</color>b"),
+      [
+        Component::Str("a"),
+        Component::ResetColor,
+        Component::Str("b")
+      ]
+    );
+  }
+
+  #[test]
+  fn unknown_tags_stay_text() {
+    assert_eq!(ColoredStr::from_markup("<b>bold</b>").text(), "<b>bold</b>");
+    assert_eq!(
+      ColoredStr::from_markup("<color=nope>x").text(),
+      "<color=nope>x"
+    );
+    assert_eq!(ColoredStr::from_markup("1 < 2 > 0").text(), "1 < 2 > 0");
+    assert_eq!(ColoredStr::from_markup("a <").text(), "a <");
+  }
+
+  #[test]
+  fn size_and_font_tags() {
+    assert_eq!(
+      components("<size=32>a<font='Noto Sans'>b</font></size>c"),
+      [
+        Component::Size(32.0),
+        Component::Str("a"),
+        Component::Font("Noto Sans"),
+        Component::Str("b"),
+        Component::ResetFont,
+        Component::ResetSize,
+        Component::Str("c"),
+      ]
+    );
+  }
+
+  #[test]
+  fn nested_sizes_and_fonts_restore_the_outer_one() {
+    assert_eq!(
+      components("<size=32><size=16>a</size>b<font=x><font=y>c</font>d"),
+      [
+        Component::Size(32.0),
+        Component::Size(16.0),
+        Component::Str("a"),
+        Component::Size(32.0),
+        Component::Str("b"),
+        Component::Font("x"),
+        Component::Font("y"),
+        Component::Str("c"),
+        Component::Font("x"),
+        Component::Str("d"),
+      ]
+    );
+  }
+
+  #[test]
+  fn invalid_sizes_and_fonts_stay_text() {
+    for markup in [
+      "<size=big>x",
+      "<size=-4>x",
+      "<size=NaN>x",
+      "<font=>x",
+      "<font=''>x",
+    ] {
+      assert_eq!(ColoredStr::from_markup(markup).text(), markup);
+    }
+  }
+
+  #[test]
+  fn parses_colors() {
+    let pure_red = Color::from_rgba(255, 0, 0, 255);
+
+    assert_eq!(parse_color("#ff0000"), Some(pure_red));
+    assert_eq!(parse_color("#f00"), Some(pure_red));
+    assert_eq!(parse_color("'Red'"), Some(RED));
+    assert_eq!(parse_color("sky-blue"), Some(colors::SKYBLUE));
+    assert_eq!(
+      parse_color("#ff000080"),
+      Some(Color::from_rgba(255, 0, 0, 128))
+    );
+    assert_eq!(parse_color("#ff00"), None);
+    assert_eq!(parse_color("#ggg"), None);
+    assert_eq!(parse_color("#ä0"), None);
+  }
+}
@@ -0,0 +1,208 @@
+use macroquad::prelude::{Rect, TextDimensions};
+
+use crate::{layout::GlyphPlacement, Fonts, TextParams};
+
+/// The measurements of text, everything [TextDimensions] has and more
+///
+/// [TextDimensions] starts at the pen, so a glyph that sticks out to the left of it,
+/// like an italic 'j', isn't included, [Self::bounds] includes every glyph
+///
+/// Converts into [TextDimensions] for code that only needs the old fields
+///
+/// **See** [Fonts::measure_text_detailed]
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct MeasuredText {
+  /// How far the pen moves, the width of the widest line
+  pub width: f32,
+  /// The height of the text
+  pub height: f32,
+  /// How far the left edge of [Self::bounds] is from [TextParams::x],
+  /// negative if the first glyph sticks out to the left of the pen
+  pub offset_x: f32,
+  /// How far the baseline of the first line is below the top of the text
+  pub offset_y: f32,
+  /// How many lines the text has, always at least 1
+  pub lines: usize,
+  /// How many glyphs draw something, spaces and line breaks don't count
+  pub glyphs: usize,
+  /// The tight bounding box of every glyph on screen, an empty rect at the position
+  /// the text is drawn at if no glyph draws something
+  pub bounds: Rect,
+}
+
+impl From<MeasuredText> for TextDimensions {
+  fn from(measured: MeasuredText) -> Self {
+    Self {
+      width: measured.width,
+      height: measured.height,
+      offset_y: measured.offset_y,
+    }
+  }
+}
+
+impl MeasuredText {
+  /// Measures nothing drawn at the position of given [TextParams], a single empty line
+  pub(crate) fn empty(params: &TextParams) -> Self {
+    Self {
+      lines: 1,
+      bounds: Rect::new(params.x, params.y, 0.0, 0.0),
+      ..Default::default()
+    }
+  }
+
+  /// Adds the rect of a glyph to the bounds, glyphs that don't draw anything are skipped
+  pub(crate) fn add_glyph(&mut self, rect: Rect) {
+    if rect.w <= 0.0 || rect.h <= 0.0 {
+      return;
+    }
+
+    self.bounds = match self.glyphs {
+      0 => rect,
+      _ => self.bounds.combine_with(rect),
+    };
+    self.glyphs += 1;
+  }
+
+  /// Adds the glyphs and lines of other text, like the next line or span
+  pub(crate) fn add(&mut self, other: &MeasuredText) {
+    if other.glyphs > 0 {
+      self.add_glyph(other.bounds);
+      self.glyphs += other.glyphs - 1;
+    }
+
+    self.lines += other.lines;
+  }
+
+  /// Sets the old [TextDimensions] fields, and how far the bounds are from the pen
+  pub(crate) fn with_dimensions(self, dimensions: TextDimensions, params: &TextParams) -> Self {
+    Self {
+      width: dimensions.width,
+      height: dimensions.height,
+      offset_x: self.bounds.x - params.x,
+      offset_y: dimensions.offset_y,
+      ..self
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Measures text with given [TextParams], line breaks start new lines like
+  /// [Self::measure_multiline], returning more than [TextDimensions]
+  /// like the line count and tight bounding box
+  ///
+  /// **Example**
+  /// ```rs
+  /// let measured = fonts.measure_text_detailed("jump", &params);
+  ///
+  /// // the tail of the 'j' isn't cut off
+  /// draw_rectangle(measured.bounds.x, measured.bounds.y, measured.bounds.w, measured.bounds.h, DARKGRAY);
+  /// fonts.draw_text_ex("jump", &params);
+  /// ```
+  pub fn measure_text_detailed(&self, text: &str, params: &TextParams) -> MeasuredText {
+    self.measure_multiline(text, params, None).dimensions
+  }
+
+  /// Places every glyph of the text like [Self::layout_glyphs],
+  /// also measuring the bounds of every glyph of the line
+  pub(crate) fn layout_measured(
+    &self,
+    text: &str,
+    params: &TextParams,
+    rasterize: bool,
+    mut f: impl FnMut(usize, char, &GlyphPlacement<'_, 'a>),
+  ) -> MeasuredText {
+    let mut measured = MeasuredText::empty(params);
+    let dimensions = self.layout_glyphs(text, params, rasterize, |index, c, placement| {
+      measured.add_glyph(placement.rect);
+      f(index, c, placement)
+    });
+
+    measured.with_dimensions(dimensions, params)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::prelude::RED;
+
+  use crate::{AtlasConfig, ColoredStr, Component};
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  #[test]
+  fn measure_text_counts_lines_and_glyphs() {
+    let fonts = fonts();
+    let measured = fonts.measure_text("ab c\n\nd", 20.0);
+    let dimensions = TextDimensions::from(measured);
+
+    assert_eq!(measured.lines, 3);
+    assert_eq!(measured.glyphs, 4);
+    assert_eq!(dimensions.width, measured.width);
+    assert_eq!(dimensions.height, measured.height);
+    assert_eq!(dimensions.offset_y, measured.offset_y);
+  }
+
+  #[test]
+  fn bounds_start_at_offset_x() {
+    let fonts = fonts();
+    let params = TextParams {
+      x: 30.0,
+      y: 10.0,
+      size: 24.0,
+      ..Default::default()
+    };
+    let measured = fonts.measure_text_detailed("jump", &params);
+
+    assert_eq!(measured.bounds.x, params.x + measured.offset_x);
+    assert!(measured.bounds.w > 0.0 && measured.bounds.h > 0.0);
+    assert_eq!(fonts.measure_text_ex("jump", &params).dimensions, measured);
+  }
+
+  #[test]
+  fn empty_text_has_empty_bounds_at_the_pen() {
+    let fonts = fonts();
+    let params = TextParams {
+      x: 30.0,
+      y: 10.0,
+      ..Default::default()
+    };
+    let measured = fonts.measure_text_detailed(" ", &params);
+
+    assert_eq!(measured.lines, 1);
+    assert_eq!(measured.glyphs, 0);
+    assert_eq!(measured.offset_x, 0.0);
+    assert_eq!(measured.bounds, Rect::new(30.0, 10.0, 0.0, 0.0));
+  }
+
+  #[test]
+  fn colored_text_bounds_cover_every_span() {
+    let fonts = fonts();
+    let params = TextParams::default();
+    let text = ColoredStr::from(vec![
+      Component::Str("ab"),
+      Component::Color(RED),
+      Component::Str("cd"),
+    ]);
+    let first = fonts.measure_text_detailed("ab", &params);
+    let measured = fonts.measure_colored_text(&text, &params);
+
+    assert_eq!(measured.lines, 1);
+    assert_eq!(measured.glyphs, 4);
+    assert_eq!(measured.bounds.x, first.bounds.x);
+    assert!(measured.bounds.right() > first.width);
+  }
+}
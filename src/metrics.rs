@@ -0,0 +1,71 @@
+use crate::{Font, Fonts};
+
+/// Vertical metrics of a font at a given size in pixels
+///
+/// **See** [Font::vertical_metrics]
+#[derive(Default, Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct VerticalMetrics {
+  /// How far the font goes above the baseline, positive
+  pub ascent: f32,
+  /// How far the font goes below the baseline, negative
+  pub descent: f32,
+  /// The gap between the descent of a line and the ascent of the next line
+  pub line_gap: f32,
+  /// The height of capital letters above the baseline,
+  /// measured from the outline of 'H'
+  pub cap_height: f32,
+}
+
+impl VerticalMetrics {
+  /// The distance between the baselines of two lines,
+  /// `ascent - descent + line_gap`
+  pub fn line_height(&self) -> f32 {
+    self.ascent - self.descent + self.line_gap
+  }
+}
+
+impl<'a> Font<'a> {
+  /// Returns the vertical metrics (ascent, descent, line gap and cap height) of this font
+  /// at a given size in pixels, use these to compute line heights and baseline positions
+  ///
+  /// Fonts without horizontal metrics fall back to an ascent of 80% and a descent of 20%
+  ///
+  /// **Example**
+  /// ```rs
+  /// let metrics = fonts.get_font_by_name("Noto Sans").unwrap().vertical_metrics(22.0);
+  ///
+  /// println!("line height: {}", metrics.line_height());
+  /// ```
+  pub fn vertical_metrics(&self, size: f32) -> VerticalMetrics {
+    let cap_height = if self.contains('H') {
+      self.font.metrics('H', size).height as f32
+    } else {
+      0.0
+    };
+
+    match self.horizontal_line_metrics(size) {
+      Some(metrics) => VerticalMetrics {
+        ascent: metrics.ascent,
+        descent: metrics.descent,
+        line_gap: metrics.line_gap,
+        cap_height,
+      },
+      None => VerticalMetrics {
+        ascent: size * 0.8,
+        descent: size * -0.2,
+        line_gap: 0.0,
+        cap_height,
+      },
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Returns the vertical metrics of the first loaded font at a given size in pixels,
+  /// `None` if no fonts are loaded
+  ///
+  /// **See** [Font::vertical_metrics]
+  pub fn vertical_metrics(&self, size: f32) -> Option<VerticalMetrics> {
+    self.fonts.first().map(|font| font.vertical_metrics(size))
+  }
+}
@@ -1,5 +1,7 @@
-pub use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
-use std::{fs::File, io::Read, path::Path};
+pub use std::io::{Error as IoError, Result as IoResult};
+use std::{f32::consts::FRAC_PI_2, fs::File, io::Read, path::Path};
+
+use macroquad::prelude::{draw_mesh, vec2, Color, Mesh, Rect, Vertex};
 
 pub fn read_file(path: impl AsRef<Path>) -> IoResult<Vec<u8>> {
   let mut file = File::open(path)?;
@@ -10,3 +12,63 @@ pub fn read_file(path: impl AsRef<Path>) -> IoResult<Vec<u8>> {
 
   Ok(bytes)
 }
+
+/// Blends from `a` to `b`, `t` goes from 0 to 1
+pub fn mix_color(a: Color, b: Color, t: f32) -> Color {
+  Color::new(
+    a.r + (b.r - a.r) * t,
+    a.g + (b.g - a.g) * t,
+    a.b + (b.b - a.b) * t,
+    a.a + (b.a - a.a) * t,
+  )
+}
+
+/// Multiplies the alpha of a color by `alpha`
+pub fn fade_color(color: Color, alpha: f32) -> Color {
+  Color::new(color.r, color.g, color.b, color.a * alpha)
+}
+
+/// Checks if a character never has anything to draw, like spaces and zero width characters
+pub fn is_blank(c: char) -> bool {
+  c.is_whitespace() || matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')
+}
+
+/// Fills a rect with rounded corners as a triangle fan around its center,
+/// so transparent colors don't overlap themselves
+pub fn draw_rounded_rect(rect: Rect, radius: f32, z: f32, color: Color) {
+  // segments per corner
+  const SEGMENTS: usize = 8;
+
+  let radius = radius.clamp(0.0, rect.w.min(rect.h) / 2.0);
+  let corners = [
+    (vec2(rect.right() - radius, rect.bottom() - radius), 0.0),
+    (vec2(rect.x + radius, rect.bottom() - radius), FRAC_PI_2),
+    (vec2(rect.x + radius, rect.y + radius), FRAC_PI_2 * 2.0),
+    (
+      vec2(rect.right() - radius, rect.y + radius),
+      FRAC_PI_2 * 3.0,
+    ),
+  ];
+  let center = rect.center();
+  let mut vertices = vec![Vertex::new(center.x, center.y, z, 0.0, 0.0, color)];
+
+  for (corner, start) in corners {
+    for i in 0..=SEGMENTS {
+      let angle = start + FRAC_PI_2 * i as f32 / SEGMENTS as f32;
+      let point = corner + vec2(angle.cos(), angle.sin()) * radius;
+
+      vertices.push(Vertex::new(point.x, point.y, z, 0.0, 0.0, color));
+    }
+  }
+
+  let outline = vertices.len() as u16 - 1;
+  let indices = (0..outline)
+    .flat_map(|i| [0, i + 1, (i + 1) % outline + 1])
+    .collect();
+
+  draw_mesh(&Mesh {
+    vertices,
+    indices,
+    texture: None,
+  });
+}
@@ -0,0 +1,123 @@
+use std::str::CharIndices;
+
+#[cfg(feature = "normalize")]
+use unicode_normalization::{char::canonical_combining_class, UnicodeNormalization};
+
+use crate::{Digits, Fonts, TextParams};
+
+/// Characters of text as they're laid out, `(index, character, skipped)`,
+/// with [Fonts::set_normalize] characters followed by combining marks are replaced
+/// by their composed character if a font has it, the marks are then skipped,
+/// digits are replaced by the digits of the locale
+pub(crate) struct LayoutChars<'f, 't, 'a> {
+  #[cfg(feature = "normalize")]
+  fonts: &'f Fonts<'a>,
+  #[cfg(feature = "normalize")]
+  text: &'t str,
+  #[cfg(not(feature = "normalize"))]
+  fonts: std::marker::PhantomData<&'f Fonts<'a>>,
+  chars: CharIndices<'t>,
+  digits: Digits,
+  /// How many of the next characters were composed into the previous one
+  skip: usize,
+}
+
+impl<'f, 't, 'a> LayoutChars<'f, 't, 'a> {
+  /// Returns the composed form of the character at `index` and the marks after it,
+  /// and how many marks it replaces, `None` if it doesn't change or no font has it
+  #[cfg(feature = "normalize")]
+  fn compose(&self, index: usize, c: char) -> Option<(char, usize)> {
+    let start = index + c.len_utf8();
+    let rest = &self.text[start..];
+    let end = start
+      + rest
+        .char_indices()
+        .find(|(_, it)| canonical_combining_class(*it) == 0)
+        .map_or(rest.len(), |(i, _)| i);
+    let marks = self.text[start..end].chars().count();
+
+    let mut composed = self.text[index..end].nfc();
+    let first = composed.next()?;
+
+    if composed.next().is_some() || (marks == 0 && first == c) {
+      return None;
+    }
+
+    self.fonts.contains(first).then_some((first, marks))
+  }
+}
+
+impl Iterator for LayoutChars<'_, '_, '_> {
+  type Item = (usize, char, bool);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (index, c) = self.chars.next()?;
+
+    if self.skip > 0 {
+      self.skip -= 1;
+      return Some((index, c, true));
+    }
+
+    #[cfg(feature = "normalize")]
+    if self.fonts.normalize {
+      if let Some((composed, skip)) = self.compose(index, c) {
+        self.skip = skip;
+        return Some((index, composed, false));
+      }
+    }
+
+    Some((index, self.digits.apply(c), false))
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Returns the characters of text as they're laid out
+  pub(crate) fn layout_chars<'f, 't>(
+    &'f self,
+    text: &'t str,
+    params: &TextParams,
+  ) -> LayoutChars<'f, 't, 'a> {
+    LayoutChars {
+      #[cfg(feature = "normalize")]
+      fonts: self,
+      #[cfg(feature = "normalize")]
+      text,
+      #[cfg(not(feature = "normalize"))]
+      fonts: std::marker::PhantomData,
+      chars: text.char_indices(),
+      digits: self.params_locale(params).digits,
+      skip: 0,
+    }
+  }
+
+  /// Sets if text is normalized to NFC before glyphs are looked up,
+  /// so decomposed text from user input or files, like "e" followed by a combining acute accent,
+  /// is drawn with the precomposed glyph "é" of the font instead of two separate glyphs
+  ///
+  /// Characters are only composed if a font contains the composed character,
+  /// byte indices of laid out glyphs still point into the text that was given,
+  /// the combining marks that got composed take up no space
+  ///
+  /// **Default** false
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_normalize(true);
+  ///
+  /// // drawn with the glyph for 'é'
+  /// fonts.draw_text("Cafe\u{301}", 20.0, 20.0, 22.0, WHITE);
+  /// ```
+  #[cfg(feature = "normalize")]
+  pub fn set_normalize(&mut self, normalize: bool) {
+    self.normalize = normalize;
+    self.layout_cache.get_mut().clear();
+  }
+
+  /// Returns if text is normalized before glyphs are looked up
+  ///
+  /// **See** [Self::set_normalize]
+  #[cfg(feature = "normalize")]
+  pub fn normalize(&self) -> bool {
+    self.normalize
+  }
+}
@@ -0,0 +1,210 @@
+use macroquad::prelude::{
+  draw_mesh, draw_texture_ex, Color, DrawTextureParams, Mesh, Quat, Rect, Texture2D, Vertex,
+};
+
+/// A single textured glyph quad, produced by [Fonts::draw_text_to_sink](crate::Fonts::draw_text_to_sink)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphQuad {
+  /// The atlas texture the glyph lives in
+  pub texture: Texture2D,
+  /// Where the glyph should be drawn on screen
+  pub dest: Rect,
+  /// Where the glyph is inside the atlas texture in pixels
+  pub source: Rect,
+  /// Where the glyph is inside the atlas texture in normalized (0..1) coordinates
+  pub uv: Rect,
+  /// The color of the glyph, the color of the top of the glyph if [Self::bottom_color] is different
+  pub color: Color,
+  /// The color of the bottom of the glyph
+  pub bottom_color: Color,
+  /// The z-coordinate of every vertex
+  pub z: f32,
+  /// How far the top edge is shifted to the right relative to the bottom edge in pixels,
+  /// used for synthesized italics
+  pub skew: f32,
+  /// If the glyph is mirrored horizontally inside of [Self::dest]
+  pub flip_x: bool,
+  /// If the glyph is mirrored vertically inside of [Self::dest]
+  pub flip_y: bool,
+  /// The angle in radians the quad is rotated by around the center of [Self::dest]
+  pub rotation: f32,
+}
+
+impl GlyphQuad {
+  /// Returns the 4 corners of this quad as macroquad [Vertex]es,
+  /// starting from the top left going clockwise
+  pub fn vertices(&self) -> [Vertex; 4] {
+    let GlyphQuad {
+      dest,
+      uv,
+      color,
+      bottom_color,
+      z,
+      skew,
+      flip_x,
+      flip_y,
+      rotation,
+      ..
+    } = *self;
+    let (left, right) = match flip_x {
+      true => (uv.right(), uv.x),
+      false => (uv.x, uv.right()),
+    };
+    let (top, bottom) = match flip_y {
+      true => (uv.bottom(), uv.y),
+      false => (uv.y, uv.bottom()),
+    };
+
+    let mut vertices = [
+      Vertex::new(dest.x + skew, dest.y, z, left, top, color),
+      Vertex::new(dest.right() + skew, dest.y, z, right, top, color),
+      Vertex::new(dest.right(), dest.bottom(), z, right, bottom, bottom_color),
+      Vertex::new(dest.x, dest.bottom(), z, left, bottom, bottom_color),
+    ];
+
+    if rotation != 0.0 {
+      let center = dest.center().extend(z);
+      let rotation = Quat::from_rotation_z(rotation);
+
+      for vertex in &mut vertices {
+        vertex.position = center + rotation * (vertex.position - center);
+      }
+    }
+
+    vertices
+  }
+}
+
+/// Receives glyph quads from [Fonts](crate::Fonts),
+/// implement this to write glyphs into your own vertex format
+///
+/// **Example**
+/// ```rs
+/// struct MySink(Vec<MyVertex>);
+///
+/// impl QuadSink for MySink {
+///   fn push_quad(&mut self, quad: GlyphQuad) {
+///     self.0.extend(MyVertex::quad(quad.dest, quad.uv, quad.color));
+///   }
+/// }
+/// ```
+pub trait QuadSink {
+  /// Called once for every glyph quad in draw order
+  fn push_quad(&mut self, quad: GlyphQuad);
+}
+
+impl<F: FnMut(GlyphQuad)> QuadSink for F {
+  fn push_quad(&mut self, quad: GlyphQuad) {
+    self(quad)
+  }
+}
+
+impl QuadSink for Vec<GlyphQuad> {
+  fn push_quad(&mut self, quad: GlyphQuad) {
+    self.push(quad)
+  }
+}
+
+/// The default [QuadSink], writes glyph quads into macroquad [Mesh]es,
+/// one mesh per atlas texture (split when a mesh gets too big)
+#[derive(Default)]
+pub struct MeshSink {
+  meshes: Vec<Mesh>,
+}
+
+impl std::fmt::Debug for MeshSink {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("MeshSink")
+      .field("meshes", &self.meshes.len())
+      .finish()
+  }
+}
+
+impl MeshSink {
+  // mesh indices are u16, so a mesh can only hold this many quads
+  const MAX_QUADS: usize = u16::MAX as usize / 4;
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the meshes that have been built so far
+  pub fn meshes(&self) -> &[Mesh] {
+    &self.meshes
+  }
+
+  /// Consumes the sink and returns the meshes that have been built
+  pub fn into_meshes(self) -> Vec<Mesh> {
+    self.meshes
+  }
+
+  /// Removes all meshes, keeping the sink reusable
+  pub fn clear(&mut self) {
+    self.meshes.clear();
+  }
+
+  /// Draws all meshes with [draw_mesh]
+  pub fn draw(&self) {
+    for mesh in &self.meshes {
+      draw_mesh(mesh);
+    }
+  }
+
+  fn mesh_for(&mut self, texture: &Texture2D) -> &mut Mesh {
+    let reuse = self.meshes.last().is_some_and(|mesh| {
+      mesh.texture.as_ref() == Some(texture) && mesh.vertices.len() / 4 < Self::MAX_QUADS
+    });
+
+    if !reuse {
+      self.meshes.push(Mesh {
+        vertices: Vec::new(),
+        indices: Vec::new(),
+        texture: Some(texture.clone()),
+      });
+    }
+
+    self.meshes.last_mut().unwrap()
+  }
+}
+
+impl QuadSink for MeshSink {
+  fn push_quad(&mut self, quad: GlyphQuad) {
+    let mesh = self.mesh_for(&quad.texture);
+    let i = mesh.vertices.len() as u16;
+
+    mesh.vertices.extend(quad.vertices());
+    mesh.indices.extend([i, i + 1, i + 2, i, i + 2, i + 3]);
+  }
+}
+
+/// Draws every quad right away, used by [Fonts](crate::Fonts) when it isn't queuing
+pub(crate) struct DrawSink;
+
+impl QuadSink for DrawSink {
+  fn push_quad(&mut self, quad: GlyphQuad) {
+    if quad.skew != 0.0 || quad.bottom_color != quad.color || quad.z != 0.0 {
+      draw_mesh(&Mesh {
+        vertices: quad.vertices().to_vec(),
+        indices: vec![0, 1, 2, 0, 2, 3],
+        texture: Some(quad.texture),
+      });
+
+      return;
+    }
+
+    draw_texture_ex(
+      &quad.texture,
+      quad.dest.x,
+      quad.dest.y,
+      quad.color,
+      DrawTextureParams {
+        dest_size: Some(quad.dest.size()),
+        source: Some(quad.source),
+        flip_x: quad.flip_x,
+        flip_y: quad.flip_y,
+        rotation: quad.rotation,
+        ..Default::default()
+      },
+    );
+  }
+}
@@ -0,0 +1,328 @@
+use std::cell::RefMut;
+
+use macroquad::prelude::Texture2D;
+
+use crate::{
+  camera::{DrawSpace, ScreenSpace},
+  quad::{DrawSink, GlyphQuad, MeshSink, QuadSink},
+  FontError, FontResult, Fonts, MeasuredText, TextParams,
+};
+
+/// Draws quads right away, or queues them if [Fonts] is queuing
+/// and the text isn't drawn in screen space
+pub(crate) struct FontsSink<'f> {
+  queue: RefMut<'f, Option<Vec<GlyphQuad>>>,
+  screen: Option<ScreenSpace>,
+}
+
+impl QuadSink for FontsSink<'_> {
+  fn push_quad(&mut self, quad: GlyphQuad) {
+    match &mut *self.queue {
+      Some(queue) if self.screen.is_none() => queue.push(quad),
+      _ => DrawSink.push_quad(quad),
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Sets if drawing text only records its glyphs until [Self::flush] is called,
+  /// instead of drawing them right away
+  ///
+  /// Flushing draws the recorded glyphs sorted by [TextParams::z](crate::TextParams::z)
+  /// and grouped by atlas, so many draw calls with different fonts
+  /// become a few large batches, glyphs at the same z drawn with different fonts
+  /// can overlap in a different order than they were drawn in
+  ///
+  /// Underlines and other lines are still drawn right away,
+  /// so is text drawn in [DrawSpace::Screen]
+  ///
+  /// Queued glyphs are drawn with the camera that's active when flushing,
+  /// not the one that was active when they were drawn, so flush before changing cameras
+  ///
+  /// Turning queuing off flushes what was recorded
+  ///
+  /// **Default** false
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_queued(true);
+  ///
+  /// loop {
+  ///   for enemy in &enemies {
+  ///     fonts.draw_text(&enemy.name, enemy.x, enemy.y, 16.0, WHITE);
+  ///   }
+  ///
+  ///   fonts.flush();
+  ///   next_frame().await;
+  /// }
+  /// ```
+  pub fn set_queued(&mut self, queued: bool) {
+    if queued {
+      self.queue.get_mut().get_or_insert_with(Vec::new);
+    } else {
+      self.flush();
+      *self.queue.get_mut() = None;
+    }
+  }
+
+  /// Returns if drawing text only records its glyphs until [Self::flush] is called
+  ///
+  /// **See** [Self::set_queued]
+  pub fn is_queued(&self) -> bool {
+    self.queue.borrow().is_some()
+  }
+
+  /// Draws every glyph recorded since the last flush, does nothing if not queuing,
+  /// with the camera that's active now
+  ///
+  /// **See** [Self::set_queued]
+  pub fn flush(&self) {
+    self.take_queued().draw();
+  }
+
+  /// Takes every recorded glyph out of the queue, batched like [Self::flush] draws them
+  fn take_queued(&self) -> MeshSink {
+    let mut queue = self.queue.borrow_mut();
+    let Some(quads) = queue.as_mut() else {
+      return MeshSink::new();
+    };
+
+    let batch = Self::batch_quads(quads);
+    quads.clear();
+    batch
+  }
+
+  /// Draws many strings at once, every string is laid out first and their glyphs are drawn
+  /// sorted by [TextParams::z](crate::TextParams::z) and grouped by atlas
+  /// like [Self::flush] does, so hundreds of small strings become a few large batches,
+  /// returns the dimensions of every string in order
+  ///
+  /// Strings drawn in [DrawSpace::Screen] are batched separately from the others
+  /// and drawn on top of them, if queuing the other glyphs are recorded until the next flush,
+  /// unless the batch has strings drawn in screen space, then the whole batch is drawn right away
+  ///
+  /// **Example**
+  /// ```rs
+  /// let batch = damage_numbers
+  ///   .iter()
+  ///   .map(|it| (it.amount.to_string(), TextParams { x: it.x, y: it.y, color: RED, ..params }))
+  ///   .collect::<Vec<_>>();
+  ///
+  /// fonts.draw_batch(&batch);
+  /// ```
+  pub fn draw_batch<T: AsRef<str>>(&self, batch: &[(T, TextParams)]) -> Vec<MeasuredText> {
+    // every glyph is cached before any quads are made, so the atlases don't change in between
+    for (text, params) in batch {
+      self.cache_text_glyphs(text.as_ref(), params);
+    }
+
+    let mut camera = Vec::new();
+    let mut screen = Vec::new();
+    let dimensions = batch
+      .iter()
+      .map(|(text, params)| {
+        let quads = match params.space {
+          DrawSpace::Camera => &mut camera,
+          DrawSpace::Screen => &mut screen,
+        };
+
+        self.draw_text_to_sink(text.as_ref(), params, &mut |quad| quads.push(quad))
+      })
+      .collect();
+
+    // queuing only the camera glyphs would draw them on top of the screen glyphs at flush
+    match &mut *self.queue.borrow_mut() {
+      Some(queue) if screen.is_empty() => queue.append(&mut camera),
+      _ => Self::batch_quads(&mut camera).draw(),
+    }
+
+    if !screen.is_empty() {
+      let _screen = ScreenSpace::new(DrawSpace::Screen);
+
+      Self::batch_quads(&mut screen).draw();
+    }
+
+    dimensions
+  }
+
+  /// Draws many strings at once like [Self::draw_batch],
+  /// but returns [FontError::NoFontsLoaded] instead of drawing nothing when no fonts are loaded
+  pub fn try_draw_batch<T: AsRef<str>>(
+    &self,
+    batch: &[(T, TextParams)],
+  ) -> FontResult<Vec<MeasuredText>> {
+    if self.fonts().is_empty() && batch.iter().any(|(text, _)| !text.as_ref().is_empty()) {
+      return Err(FontError::NoFontsLoaded);
+    }
+
+    Ok(self.draw_batch(batch))
+  }
+
+  /// Batches quads into meshes sorted by z and grouped by texture
+  fn batch_quads(quads: &mut [GlyphQuad]) -> MeshSink {
+    quads.sort_by(|a, b| a.z.total_cmp(&b.z));
+
+    let mut sink = MeshSink::new();
+
+    for layer in quads.chunk_by(|a, b| a.z == b.z) {
+      let mut textures = Vec::<&Texture2D>::new();
+
+      for quad in layer {
+        if !textures.contains(&&quad.texture) {
+          textures.push(&quad.texture);
+        }
+      }
+
+      for texture in textures {
+        for quad in layer.iter().filter(|it| it.texture == *texture) {
+          sink.push_quad(quad.clone());
+        }
+      }
+    }
+
+    sink
+  }
+
+  /// Returns the sink glyphs of text with given [TextParams] are drawn into,
+  /// which queues them if queuing, the default camera is set while it's alive
+  /// if the text is drawn in screen space
+  pub(crate) fn draw_sink(&self, params: &TextParams) -> FontsSink<'_> {
+    FontsSink {
+      queue: self.queue.borrow_mut(),
+      screen: ScreenSpace::new(params.space),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::{
+    miniquad::{RawId, TextureId},
+    prelude::{Rect, WHITE},
+  };
+
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts.set_queued(true);
+    fonts
+  }
+
+  /// Params whose glyphs are all clipped away, so no quad needs an atlas texture
+  fn clipped(size: f32) -> TextParams {
+    TextParams {
+      size,
+      clip: Some(Rect::new(-100.0, -100.0, 1.0, 1.0)),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn batched_lines_match_measure_text() {
+    let fonts = fonts();
+    let batch = fonts.draw_batch(&[("12\ncrit", clipped(20.0)), ("7", clipped(20.0))]);
+    let measured = fonts.measure_text("12\ncrit", 20.0);
+
+    assert_eq!(batch[0].lines, 2);
+    assert_eq!(batch[0].lines, measured.lines);
+    assert_eq!(batch[0].glyphs, measured.glyphs);
+    assert_eq!(batch[0].width, measured.width);
+    assert_eq!(batch[0].height, measured.height);
+    assert_eq!(batch[1].lines, 1);
+  }
+
+  /// A quad at a given z with a texture that's only an id, so it can't be drawn
+  fn quad(texture: u32, z: f32) -> GlyphQuad {
+    GlyphQuad {
+      texture: Texture2D::from_miniquad_texture(TextureId::from_raw_id(RawId::OpenGl(texture))),
+      dest: Rect::new(0.0, 0.0, 1.0, 1.0),
+      source: Rect::new(0.0, 0.0, 1.0, 1.0),
+      uv: Rect::new(0.0, 0.0, 1.0, 1.0),
+      color: WHITE,
+      bottom_color: WHITE,
+      z,
+      skew: 0.0,
+      flip_x: false,
+      flip_y: false,
+      rotation: 0.0,
+    }
+  }
+
+  fn queue(fonts: &mut Fonts, quads: impl IntoIterator<Item = GlyphQuad>) {
+    fonts.queue.get_mut().as_mut().unwrap().extend(quads);
+  }
+
+  #[test]
+  fn flushing_sorts_by_z_then_groups_by_texture() {
+    let mut fonts = fonts();
+
+    queue(
+      &mut fonts,
+      [
+        quad(1, 1.0),
+        quad(2, 0.0),
+        quad(1, 0.0),
+        quad(2, 0.0),
+        quad(1, 1.0),
+      ],
+    );
+
+    let batch = fonts.take_queued();
+    // the texture of every mesh and the z of every quad in it
+    let meshes = batch
+      .meshes()
+      .iter()
+      .map(|it| {
+        let z = it.vertices.iter().step_by(4).map(|it| it.position.z);
+
+        (it.texture.clone().unwrap(), z.collect::<Vec<_>>())
+      })
+      .collect::<Vec<_>>();
+
+    // textures at the same z stay in the order they were first drawn with,
+    // quads with the same texture after each other share a mesh
+    assert_eq!(
+      meshes,
+      [
+        (quad(2, 0.0).texture, vec![0.0, 0.0]),
+        (quad(1, 0.0).texture, vec![0.0, 1.0, 1.0]),
+      ]
+    );
+
+    // flushing empties the queue, but keeps queuing
+    assert!(fonts.is_queued());
+    assert!(fonts.take_queued().meshes().is_empty());
+  }
+
+  // drawing needs a window, which tests don't have
+  #[test]
+  #[should_panic(expected = "THREAD_ID")]
+  fn turning_queuing_off_draws_what_was_queued() {
+    let mut fonts = fonts();
+
+    queue(&mut fonts, [quad(1, 0.0)]);
+    fonts.set_queued(false);
+  }
+
+  #[test]
+  fn turning_queuing_off_stops_queuing() {
+    let mut fonts = fonts();
+
+    fonts.set_queued(false);
+
+    assert!(!fonts.is_queued());
+    assert!(fonts.take_queued().meshes().is_empty());
+  }
+}
@@ -0,0 +1,502 @@
+use std::{fmt::Debug, sync::Arc};
+
+use fontdue::Metrics;
+
+#[cfg(feature = "swash")]
+use std::sync::Mutex;
+
+#[cfg(any(feature = "ab_glyph", feature = "swash"))]
+use crate::FontError;
+use crate::{atlas::Atlases, FontResult, FontdueFont, Fonts};
+
+/// Turns the glyphs of a single font into coverage bitmaps,
+/// every loaded font gets its own rasterizer, created by the [RasterBackend] of [Fonts]
+///
+/// Fonts are always parsed by fontdue for lookups and layout,
+/// a rasterizer only decides what the glyphs look like
+///
+/// **See** [Fonts::set_raster_backend]
+pub trait Rasterizer: Debug + Send + Sync {
+  /// Rasterizes a character at a size in pixels per em,
+  /// returns the metrics of the bitmap in the same coordinates fontdue uses
+  /// and one coverage byte per pixel, row by row from the top left
+  ///
+  /// `font` is the fontdue font the rasterizer was created for
+  fn rasterize(&self, font: &FontdueFont, c: char, size: f32) -> (Metrics, Vec<u8>);
+
+  /// Rasterizes a character as a color glyph, like an emoji from a COLR or bitmap table,
+  /// returns the metrics and four RGBA bytes per pixel, `None` if the glyph has no colors
+  /// and is rasterized with [Self::rasterize] instead
+  ///
+  /// Color glyphs are multiplied by the color text is drawn with, draw them with white
+  /// to keep their colors
+  fn rasterize_color(&self, font: &FontdueFont, c: char, size: f32) -> Option<(Metrics, Vec<u8>)> {
+    let _ = (font, c, size);
+    None
+  }
+}
+
+/// Rasterizes glyphs with fontdue, the default
+#[derive(Default, Debug, Copy, Clone)]
+pub struct FontdueRasterizer;
+
+impl Rasterizer for FontdueRasterizer {
+  fn rasterize(&self, font: &FontdueFont, c: char, size: f32) -> (Metrics, Vec<u8>) {
+    font.rasterize(c, size)
+  }
+}
+
+/// Rasterizes glyphs with ab_glyph, which renders some fonts closer to how browsers do
+///
+/// Needs the `ab_glyph` feature
+#[cfg(feature = "ab_glyph")]
+#[derive(Debug)]
+pub struct AbGlyphRasterizer {
+  /// The bytes of the font, shared with the [Font](crate::Font) it rasterizes
+  data: Arc<[u8]>,
+  collection_index: u32,
+  /// Converts a size in pixels per em into the scale ab_glyph uses,
+  /// which is the height from descent to ascent
+  em_to_scale: f32,
+}
+
+#[cfg(feature = "ab_glyph")]
+impl AbGlyphRasterizer {
+  /// Parses a font for rasterizing with ab_glyph
+  pub fn new(data: impl Into<Arc<[u8]>>, collection_index: u32) -> FontResult<Self> {
+    use ab_glyph::Font;
+
+    let data = data.into();
+    let font = ab_glyph::FontRef::try_from_slice_and_index(&data, collection_index)
+      .map_err(|_| FontError::Parse("ab_glyph couldn't parse the font"))?;
+    let em_to_scale = font.height_unscaled() / font.units_per_em().unwrap_or(1000.0);
+
+    Ok(Self {
+      data,
+      collection_index,
+      em_to_scale,
+    })
+  }
+
+  /// Parses the font again, which only reads its table directory,
+  /// so the bytes don't have to be copied into an owned font
+  fn font(&self) -> ab_glyph::FontRef<'_> {
+    ab_glyph::FontRef::try_from_slice_and_index(&self.data, self.collection_index)
+      .expect("the font was parsed when the rasterizer was created")
+  }
+}
+
+#[cfg(feature = "ab_glyph")]
+impl Rasterizer for AbGlyphRasterizer {
+  fn rasterize(&self, _: &FontdueFont, c: char, size: f32) -> (Metrics, Vec<u8>) {
+    use ab_glyph::{Font, ScaleFont};
+
+    let font = self.font();
+    let font = font.as_scaled(size * self.em_to_scale);
+    let glyph = font.scaled_glyph(c);
+    let advance_width = font.h_advance(glyph.id);
+
+    let Some(outline) = font.outline_glyph(glyph) else {
+      let metrics = Metrics {
+        advance_width,
+        ..Default::default()
+      };
+
+      return (metrics, Vec::new());
+    };
+
+    let bounds = outline.px_bounds();
+    let (width, height) = (bounds.width() as usize, bounds.height() as usize);
+    let mut bitmap = vec![0; width * height];
+
+    outline.draw(|x, y, coverage| {
+      bitmap[y as usize * width + x as usize] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+    });
+
+    let metrics = Metrics {
+      xmin: bounds.min.x as i32,
+      // ab_glyph has y going down from the baseline, fontdue has it going up
+      ymin: -bounds.max.y as i32,
+      width,
+      height,
+      advance_width,
+      ..Default::default()
+    };
+
+    (metrics, bitmap)
+  }
+}
+
+/// Rasterizes glyphs with swash, which can hint glyphs to the pixel grid,
+/// making small text sharper, and draws color glyphs from COLR and bitmap tables
+///
+/// Needs the `swash` feature
+#[cfg(feature = "swash")]
+pub struct SwashRasterizer {
+  /// The bytes of the font, shared with the [Font](crate::Font) it rasterizes
+  data: Arc<[u8]>,
+  offset: u32,
+  key: swash::CacheKey,
+  hinted: bool,
+  context: Mutex<swash::scale::ScaleContext>,
+}
+
+#[cfg(feature = "swash")]
+impl SwashRasterizer {
+  /// Parses a font for rasterizing with swash, `hinted` fits outlines to the pixel grid
+  pub fn new(data: impl Into<Arc<[u8]>>, collection_index: u32, hinted: bool) -> FontResult<Self> {
+    let data = data.into();
+    let font = swash::FontRef::from_index(&data, collection_index as usize)
+      .ok_or(FontError::Parse("swash couldn't parse the font"))?;
+    let (offset, key) = (font.offset, font.key);
+
+    Ok(Self {
+      data,
+      offset,
+      key,
+      hinted,
+      context: Mutex::default(),
+    })
+  }
+
+  fn font(&self) -> swash::FontRef<'_> {
+    swash::FontRef {
+      data: &self.data,
+      offset: self.offset,
+      key: self.key,
+    }
+  }
+
+  /// Renders a glyph from the first of `sources` it's in
+  fn render(
+    &self,
+    c: char,
+    size: f32,
+    sources: &[swash::scale::Source],
+  ) -> Option<swash::scale::image::Image> {
+    use swash::{scale::Render, zeno::Format};
+
+    let font = self.font();
+    let glyph = font.charmap().map(c);
+    let mut context = self.context.lock().unwrap_or_else(|it| it.into_inner());
+    let mut scaler = context.builder(font).size(size).hint(self.hinted).build();
+
+    Render::new(sources)
+      .format(Format::Alpha)
+      .render(&mut scaler, glyph)
+  }
+
+  /// Returns the metrics of a rendered glyph in fontdue's coordinates
+  fn metrics(&self, c: char, size: f32, image: &swash::scale::image::Image) -> Metrics {
+    let font = self.font();
+    let placement = image.placement;
+
+    Metrics {
+      xmin: placement.left,
+      // swash places the top of the image, fontdue the bottom
+      ymin: placement.top - placement.height as i32,
+      width: placement.width as usize,
+      height: placement.height as usize,
+      advance_width: font
+        .glyph_metrics(&[])
+        .scale(size)
+        .advance_width(font.charmap().map(c)),
+      ..Default::default()
+    }
+  }
+}
+
+#[cfg(feature = "swash")]
+impl Debug for SwashRasterizer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("SwashRasterizer")
+      .field("offset", &self.offset)
+      .field("hinted", &self.hinted)
+      .finish_non_exhaustive()
+  }
+}
+
+#[cfg(feature = "swash")]
+impl Rasterizer for SwashRasterizer {
+  fn rasterize(&self, _: &FontdueFont, c: char, size: f32) -> (Metrics, Vec<u8>) {
+    use swash::scale::{Source, StrikeWith};
+
+    let sources = [Source::Outline, Source::Bitmap(StrikeWith::BestFit)];
+
+    match self.render(c, size, &sources) {
+      Some(image) => (self.metrics(c, size, &image), image.data),
+      None => {
+        let metrics = Metrics {
+          advance_width: self.metrics(c, size, &Default::default()).advance_width,
+          ..Default::default()
+        };
+
+        (metrics, Vec::new())
+      }
+    }
+  }
+
+  fn rasterize_color(&self, _: &FontdueFont, c: char, size: f32) -> Option<(Metrics, Vec<u8>)> {
+    use swash::scale::{image::Content, Source, StrikeWith};
+
+    let sources = [
+      Source::ColorOutline(0),
+      Source::ColorBitmap(StrikeWith::BestFit),
+    ];
+    let image = self
+      .render(c, size, &sources)
+      .filter(|it| it.content == Content::Color)?;
+
+    Some((self.metrics(c, size, &image), image.data))
+  }
+}
+
+/// Which [Rasterizer] glyphs of fonts loaded into [Fonts] are rasterized with
+///
+/// **Default** [RasterBackend::Fontdue]
+///
+/// **See** [Fonts::set_raster_backend]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RasterBackend {
+  /// [FontdueRasterizer], fast and the same as every version before this
+  #[default]
+  Fontdue,
+  /// [AbGlyphRasterizer], needs the `ab_glyph` feature
+  #[cfg(feature = "ab_glyph")]
+  AbGlyph,
+  /// [SwashRasterizer], needs the `swash` feature
+  #[cfg(feature = "swash")]
+  Swash {
+    /// Fits outlines to the pixel grid, sharper at small sizes but less true to the design
+    hinted: bool,
+  },
+}
+
+impl RasterBackend {
+  /// Creates the rasterizer of a font loaded from bytes,
+  /// fails if the backend can't parse the font
+  ///
+  /// Rasterizers that read the font themselves share the bytes instead of copying them
+  #[cfg_attr(
+    not(any(feature = "ab_glyph", feature = "swash")),
+    allow(unused_variables)
+  )]
+  pub fn create(
+    &self,
+    data: impl Into<Arc<[u8]>>,
+    collection_index: u32,
+  ) -> FontResult<Box<dyn Rasterizer>> {
+    match self {
+      Self::Fontdue => Ok(Box::new(FontdueRasterizer)),
+      #[cfg(feature = "ab_glyph")]
+      Self::AbGlyph => Ok(Box::new(AbGlyphRasterizer::new(data, collection_index)?)),
+      #[cfg(feature = "swash")]
+      Self::Swash { hinted } => Ok(Box::new(SwashRasterizer::new(
+        data,
+        collection_index,
+        *hinted,
+      )?)),
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Sets which [Rasterizer] glyphs are rasterized with, fonts that are already loaded
+  /// get a new rasterizer and their glyphs are cached again
+  ///
+  /// Fails if a loaded font can't be parsed by the backend, nothing changes when it does
+  ///
+  /// **Default** [RasterBackend::Fontdue]
+  ///
+  /// **Example**
+  /// ```rs
+  /// // needs the ab_glyph feature
+  /// fonts.set_raster_backend(RasterBackend::AbGlyph)?;
+  /// ```
+  pub fn set_raster_backend(&mut self, backend: RasterBackend) -> FontResult<()> {
+    let rasterizers = self
+      .fonts
+      .iter()
+      .map(|font| backend.create(font.data.clone(), font.collection_index))
+      .collect::<FontResult<Vec<_>>>()?;
+
+    self.raster_backend = backend;
+
+    for (font, rasterizer) in self.fonts.iter_mut().zip(rasterizers) {
+      font.rasterizer = rasterizer;
+      *font.atlases.get_mut() = Atlases::new(self.default_sm, self.atlas_config);
+      font.bitmaps.get_mut().clear();
+      font.blurred.get_mut().clear();
+      font.recache_glyphs();
+    }
+
+    self.layout_cache.get_mut().clear();
+    Ok(())
+  }
+
+  /// Returns which [Rasterizer] glyphs are rasterized with
+  ///
+  /// **See** [Self::set_raster_backend]
+  pub fn raster_backend(&self) -> RasterBackend {
+    self.raster_backend
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fontdue::FontSettings;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  fn fontdue_font() -> FontdueFont {
+    FontdueFont::from_bytes(NOTO_SANS, FontSettings::default()).unwrap()
+  }
+
+  #[test]
+  fn fontdue_is_the_default() {
+    let font = fontdue_font();
+    let rasterizer = RasterBackend::default().create(NOTO_SANS, 0).unwrap();
+
+    assert_eq!(
+      rasterizer.rasterize(&font, 'g', 24.0),
+      font.rasterize('g', 24.0)
+    );
+  }
+
+  #[cfg(feature = "ab_glyph")]
+  #[test]
+  fn ab_glyph_places_glyphs_like_fontdue() {
+    let font = fontdue_font();
+    let rasterizer = RasterBackend::AbGlyph.create(NOTO_SANS, 0).unwrap();
+
+    for c in ['H', 'g', 'j', '良'] {
+      let (expected, _) = font.rasterize(c, 32.0);
+      let (metrics, bitmap) = rasterizer.rasterize(&font, c, 32.0);
+
+      assert!(
+        (metrics.advance_width - expected.advance_width).abs() < 0.01,
+        "{c}"
+      );
+      assert!(metrics.xmin.abs_diff(expected.xmin) <= 1, "{c}");
+      assert!(metrics.ymin.abs_diff(expected.ymin) <= 1, "{c}");
+      assert!(metrics.width.abs_diff(expected.width) <= 1, "{c}");
+      assert!(metrics.height.abs_diff(expected.height) <= 1, "{c}");
+      assert_eq!(bitmap.len(), metrics.width * metrics.height);
+    }
+
+    let (space, bitmap) = rasterizer.rasterize(&font, ' ', 32.0);
+
+    assert!(space.advance_width > 0.0);
+    assert!(bitmap.is_empty());
+  }
+
+  #[cfg(feature = "ab_glyph")]
+  #[test]
+  fn ab_glyph_rejects_garbage() {
+    assert!(RasterBackend::AbGlyph
+      .create(&b"not a font"[..], 0)
+      .is_err());
+  }
+
+  #[cfg(feature = "swash")]
+  #[test]
+  fn swash_places_glyphs_like_fontdue() {
+    let font = fontdue_font();
+
+    for hinted in [false, true] {
+      let rasterizer = RasterBackend::Swash { hinted }
+        .create(NOTO_SANS, 0)
+        .unwrap();
+
+      for c in ['H', 'g', 'j'] {
+        let (expected, _) = font.rasterize(c, 32.0);
+        let (metrics, bitmap) = rasterizer.rasterize(&font, c, 32.0);
+
+        assert!(
+          (metrics.advance_width - expected.advance_width).abs() < 0.01,
+          "{c}"
+        );
+
+        // swash leaves a pixel of padding around glyphs, so only the edges are compared
+        let edges = |it: Metrics| {
+          [
+            it.xmin,
+            it.ymin,
+            it.xmin + it.width as i32,
+            it.ymin + it.height as i32,
+          ]
+        };
+
+        for (edge, expected) in edges(metrics).into_iter().zip(edges(expected)) {
+          assert!(edge.abs_diff(expected) <= 1, "{c}");
+        }
+
+        assert_eq!(bitmap.len(), metrics.width * metrics.height);
+        assert!(bitmap.contains(&255));
+      }
+
+      let (space, bitmap) = rasterizer.rasterize(&font, ' ', 32.0);
+
+      assert!(space.advance_width > 0.0);
+      assert!(bitmap.is_empty());
+    }
+  }
+
+  #[cfg(feature = "swash")]
+  #[test]
+  fn swash_only_draws_color_glyphs_in_color() {
+    let font = fontdue_font();
+    let rasterizer = RasterBackend::Swash { hinted: false }
+      .create(NOTO_SANS, 0)
+      .unwrap();
+
+    assert!(rasterizer.rasterize_color(&font, 'A', 32.0).is_none());
+    assert!(RasterBackend::Swash { hinted: false }
+      .create(&b"not a font"[..], 0)
+      .is_err());
+  }
+
+  #[cfg(feature = "ab_glyph")]
+  #[test]
+  fn switching_backends_recaches_glyphs() {
+    let mut fonts = Fonts::default();
+
+    fonts.set_atlas_config(crate::AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts.cache_glyph('A', 32);
+    fonts.set_raster_backend(RasterBackend::AbGlyph).unwrap();
+
+    let font = &fonts.fonts()[0];
+
+    assert_eq!(fonts.raster_backend(), RasterBackend::AbGlyph);
+    assert!(font.chars.borrow().contains_key(&('A', 32)));
+  }
+
+  #[cfg(any(feature = "ab_glyph", feature = "swash"))]
+  #[test]
+  fn rasterizers_share_the_font_bytes() {
+    let mut fonts = Fonts::default();
+
+    fonts.set_atlas_config(crate::AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+
+    #[cfg(feature = "ab_glyph")]
+    fonts.set_raster_backend(RasterBackend::AbGlyph).unwrap();
+    #[cfg(not(feature = "ab_glyph"))]
+    fonts
+      .set_raster_backend(RasterBackend::Swash { hinted: false })
+      .unwrap();
+
+    // one for the font and one for its rasterizer
+    assert_eq!(Arc::strong_count(&fonts.fonts()[0].data), 2);
+  }
+}
@@ -0,0 +1,126 @@
+use std::{
+  fs::metadata,
+  path::{Path, PathBuf},
+  sync::Arc,
+  time::{Duration, Instant, SystemTime},
+};
+
+use fontdue::FontSettings;
+
+use crate::{atlas::Atlases, misc::read_file, FontError, FontResult, FontdueFont, Fonts};
+
+/// How often [Fonts::hot_reload] checks font files by default
+pub(crate) const DEFAULT_HOT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A font that was loaded from a file and gets reloaded when that file changes
+#[derive(Debug, Clone)]
+pub(crate) struct WatchedFont<'a> {
+  name: &'a str,
+  path: PathBuf,
+  settings: FontSettings,
+  modified: Option<SystemTime>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+  metadata(path).and_then(|it| it.modified()).ok()
+}
+
+impl<'a> Fonts<'a> {
+  pub(crate) fn watch_font(&mut self, name: &'a str, path: &Path, settings: FontSettings) {
+    self.watched.retain(|it| it.name != name);
+    self.watched.push(WatchedFont {
+      name,
+      path: path.to_path_buf(),
+      settings,
+      modified: modified(path),
+    });
+  }
+
+  pub(crate) fn unwatch_font(&mut self, name: &str) {
+    self.watched.retain(|it| it.name != name);
+  }
+
+  /// Reloads every font loaded with [Self::load_font_from_file]
+  /// whose file changed since it was loaded, and recaches all their glyphs,
+  /// returns how many fonts got reloaded
+  ///
+  /// Files aren't watched by the OS, their modification times are polled, at most once
+  /// every [Self::set_hot_reload_interval], calls in between don't touch the file system,
+  /// so this can be called once every frame during development
+  /// to iterate on fonts without restarting
+  ///
+  /// **Example**
+  /// ```rs
+  /// loop {
+  ///   fonts.hot_reload()?;
+  ///   fonts.draw_text("Nice", 20.0, 20.0, 69.0, WHITE);
+  ///
+  ///   next_frame().await;
+  /// }
+  /// ```
+  pub fn hot_reload(&mut self) -> FontResult<usize> {
+    let now = Instant::now();
+    let due = self
+      .last_hot_reload
+      .is_none_or(|it| now.duration_since(it) >= self.hot_reload_interval);
+
+    if !due {
+      return Ok(0);
+    }
+
+    self.last_hot_reload = Some(now);
+
+    let mut reloaded = 0;
+
+    for watched in self.watched.iter_mut() {
+      let modified = modified(&watched.path);
+
+      if modified == watched.modified {
+        continue;
+      }
+
+      let Some(index) = self.index_by_name.get(watched.name).copied() else {
+        continue;
+      };
+
+      let bytes = read_file(&watched.path)?;
+      FontError::check_format(&bytes)?;
+      let font = FontdueFont::from_bytes(&*bytes, watched.settings).map_err(FontError::Parse)?;
+      let data = Arc::<[u8]>::from(bytes);
+      let rasterizer = self
+        .raster_backend
+        .create(data.clone(), watched.settings.collection_index)?;
+
+      watched.modified = modified;
+
+      let font_ref = &mut self.fonts[index];
+      font_ref.font = font;
+      font_ref.rasterizer = rasterizer;
+      font_ref.data = data;
+      font_ref.outlines.get_mut().clear();
+      font_ref.blurred.get_mut().clear();
+      *font_ref.atlases.borrow_mut() = Atlases::new(self.default_sm, self.atlas_config);
+      font_ref.recache_glyphs();
+      self.layout_cache.get_mut().clear();
+
+      reloaded += 1;
+    }
+
+    Ok(reloaded)
+  }
+
+  /// Sets how often [Self::hot_reload] polls the modification times of font files,
+  /// [Duration::ZERO] polls on every call
+  ///
+  /// **Default** 500 milliseconds
+  pub fn set_hot_reload_interval(&mut self, interval: Duration) {
+    self.hot_reload_interval = interval;
+  }
+
+  /// Returns how often [Self::hot_reload] polls the modification times of font files
+  ///
+  /// **See** [Self::set_hot_reload_interval]
+  pub fn hot_reload_interval(&self) -> Duration {
+    self.hot_reload_interval
+  }
+}
@@ -0,0 +1,65 @@
+use crate::{Fonts, LocalFonts, MeasuredText, TextStyle};
+
+/// Something that can measure and draw styled text, implemented by [Fonts] and [LocalFonts],
+/// UI code can depend on this instead of [Fonts] so it can be tested with a mock
+/// or drawn with a different backend
+///
+/// **Example**
+/// ```rs
+/// fn draw_button(renderer: &impl TextRenderer, label: &str, rect: Rect, style: &TextStyle) {
+///   let size = renderer.measure_text_with_style(label, style);
+///
+///   renderer.draw_text_with_style(
+///     label,
+///     style,
+///     rect.x + (rect.w - size.width) / 2.0,
+///     rect.y + (rect.h - size.height) / 2.0,
+///   );
+/// }
+///
+/// struct MockRenderer;
+///
+/// impl TextRenderer for MockRenderer {
+///   fn measure_text_with_style(&self, text: &str, style: &TextStyle) -> MeasuredText {
+///     let width = text.chars().count() as f32 * style.size / 2.0;
+///     MeasuredText {
+///       width,
+///       height: style.size,
+///       offset_y: style.size,
+///       lines: 1,
+///       ..Default::default()
+///     }
+///   }
+///
+///   fn draw_text_with_style(&self, text: &str, style: &TextStyle, _x: f32, _y: f32) -> MeasuredText {
+///     self.measure_text_with_style(text, style)
+///   }
+/// }
+/// ```
+pub trait TextRenderer {
+  /// Measures text like it's drawn with [Self::draw_text_with_style]
+  fn measure_text_with_style(&self, text: &str, style: &TextStyle) -> MeasuredText;
+
+  /// Draws text with a [TextStyle], draws from TopLeft
+  fn draw_text_with_style(&self, text: &str, style: &TextStyle, x: f32, y: f32) -> MeasuredText;
+}
+
+impl TextRenderer for Fonts<'_> {
+  fn measure_text_with_style(&self, text: &str, style: &TextStyle) -> MeasuredText {
+    Fonts::measure_text_with_style(self, text, style)
+  }
+
+  fn draw_text_with_style(&self, text: &str, style: &TextStyle, x: f32, y: f32) -> MeasuredText {
+    Fonts::draw_text_with_style(self, text, style, x, y)
+  }
+}
+
+impl TextRenderer for LocalFonts<'_> {
+  fn measure_text_with_style(&self, text: &str, style: &TextStyle) -> MeasuredText {
+    self.borrow().measure_text_with_style(text, style)
+  }
+
+  fn draw_text_with_style(&self, text: &str, style: &TextStyle, x: f32, y: f32) -> MeasuredText {
+    self.borrow().draw_text_with_style(text, style, x, y)
+  }
+}
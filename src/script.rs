@@ -0,0 +1,69 @@
+use std::ops::RangeInclusive;
+
+/// Common Unicode scripts, used to configure fallback priority per script
+///
+/// **See** [Fonts::set_script_fallback](crate::Fonts::set_script_fallback)
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Script {
+  Latin,
+  Greek,
+  Cyrillic,
+  Arabic,
+  Hebrew,
+  Thai,
+  /// Chinese characters, also used in Japanese (kanji) and Korean (hanja)
+  Han,
+  Hiragana,
+  Katakana,
+  Hangul,
+  /// CJK punctuation and full width forms
+  CjkSymbols,
+  /// Emoticons, pictographs, flags, miscellaneous symbols and dingbats,
+  /// arrows and other symbols text fonts usually have aren't part of it
+  Emoji,
+}
+
+impl Script {
+  /// Returns the Unicode ranges that make up this script
+  pub fn ranges(&self) -> &'static [RangeInclusive<char>] {
+    match self {
+      Self::Latin => &[
+        '\u{0041}'..='\u{005A}',
+        '\u{0061}'..='\u{007A}',
+        '\u{00C0}'..='\u{024F}',
+        '\u{1E00}'..='\u{1EFF}',
+      ],
+      Self::Greek => &['\u{0370}'..='\u{03FF}', '\u{1F00}'..='\u{1FFF}'],
+      Self::Cyrillic => &['\u{0400}'..='\u{052F}'],
+      Self::Arabic => &[
+        '\u{0600}'..='\u{06FF}',
+        '\u{0750}'..='\u{077F}',
+        '\u{FB50}'..='\u{FDFF}',
+        '\u{FE70}'..='\u{FEFF}',
+      ],
+      Self::Hebrew => &['\u{0590}'..='\u{05FF}'],
+      Self::Thai => &['\u{0E00}'..='\u{0E7F}'],
+      Self::Han => &[
+        '\u{2E80}'..='\u{2FDF}',
+        '\u{3400}'..='\u{4DBF}',
+        '\u{4E00}'..='\u{9FFF}',
+        '\u{F900}'..='\u{FAFF}',
+        '\u{20000}'..='\u{2FA1F}',
+      ],
+      Self::Hiragana => &['\u{3040}'..='\u{309F}'],
+      Self::Katakana => &['\u{30A0}'..='\u{30FF}', '\u{31F0}'..='\u{31FF}'],
+      Self::Hangul => &[
+        '\u{1100}'..='\u{11FF}',
+        '\u{3130}'..='\u{318F}',
+        '\u{AC00}'..='\u{D7AF}',
+      ],
+      Self::CjkSymbols => &['\u{3000}'..='\u{303F}', '\u{FF00}'..='\u{FFEF}'],
+      Self::Emoji => &['\u{2600}'..='\u{27BF}', '\u{1F000}'..='\u{1FAFF}'],
+    }
+  }
+
+  /// Checks if a character is part of this script
+  pub fn contains(&self, c: char) -> bool {
+    self.ranges().iter().any(|range| range.contains(&c))
+  }
+}
@@ -0,0 +1,70 @@
+//! Serializes macroquad types that don't implement serde's traits,
+//! used with `#[serde(with = "...")]`
+
+/// Serializes a [Color](macroquad::prelude::Color) as `[r, g, b, a]` with values from 0 to 1
+pub(crate) mod color {
+  use macroquad::prelude::Color;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+    [color.r, color.g, color.b, color.a].serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let [r, g, b, a] = <[f32; 4]>::deserialize(deserializer)?;
+
+    Ok(Color::new(r, g, b, a))
+  }
+}
+
+/// Serializes an optional [Color](macroquad::prelude::Color) as `[r, g, b, a]` or none
+pub(crate) mod option_color {
+  use macroquad::prelude::Color;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub fn serialize<S: Serializer>(color: &Option<Color>, serializer: S) -> Result<S::Ok, S::Error> {
+    color
+      .map(|it| [it.r, it.g, it.b, it.a])
+      .serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<Option<Color>, D::Error> {
+    let color = Option::<[f32; 4]>::deserialize(deserializer)?;
+
+    Ok(color.map(|[r, g, b, a]| Color::new(r, g, b, a)))
+  }
+}
+
+/// Serializes a [Vec2](macroquad::prelude::Vec2) as `[x, y]`
+pub(crate) mod vec2 {
+  use macroquad::prelude::Vec2;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub fn serialize<S: Serializer>(vec: &Vec2, serializer: S) -> Result<S::Ok, S::Error> {
+    vec.to_array().serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+    <[f32; 2]>::deserialize(deserializer).map(Vec2::from)
+  }
+}
+
+/// Serializes an optional [Rect](macroquad::prelude::Rect) as `[x, y, w, h]` or none
+pub(crate) mod option_rect {
+  use macroquad::prelude::Rect;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub fn serialize<S: Serializer>(rect: &Option<Rect>, serializer: S) -> Result<S::Ok, S::Error> {
+    rect
+      .map(|it| [it.x, it.y, it.w, it.h])
+      .serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Rect>, D::Error> {
+    let rect = Option::<[f32; 4]>::deserialize(deserializer)?;
+
+    Ok(rect.map(|[x, y, w, h]| Rect::new(x, y, w, h)))
+  }
+}
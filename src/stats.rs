@@ -0,0 +1,92 @@
+use crate::{Font, Fonts};
+
+/// Statistics about the glyph cache of a single font
+///
+/// **See** [Fonts::cache_stats]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontCacheStats<'a> {
+  /// The name of the font
+  pub name: &'a str,
+  /// How many glyphs are cached, every size of a character counts as its own glyph
+  pub glyphs: usize,
+  /// How many atlases glyphs are split across
+  ///
+  /// **See** [AtlasConfig::large_glyph_size](crate::AtlasConfig::large_glyph_size)
+  pub atlases: usize,
+  /// The width of the atlas texture in pixels, the atlas of the smaller glyphs
+  /// if glyphs are split across atlases
+  pub atlas_width: u16,
+  /// The height of the atlas texture in pixels, the atlas of the smaller glyphs
+  /// if glyphs are split across atlases
+  pub atlas_height: u16,
+  /// How much of every atlas combined is covered by glyphs, from 0 to 1
+  pub occupancy: f32,
+  /// Roughly how many bytes every atlas texture takes up on the GPU,
+  /// the same amount is kept in memory to update the textures
+  pub gpu_bytes: usize,
+}
+
+/// Statistics about the glyph caches of every loaded font
+///
+/// **See** [Fonts::cache_stats]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CacheStats<'a> {
+  /// The statistics of every font in the order they were loaded
+  pub fonts: Vec<FontCacheStats<'a>>,
+}
+
+impl<'a> CacheStats<'a> {
+  /// Returns how many glyphs are cached across every font
+  pub fn glyphs(&self) -> usize {
+    self.fonts.iter().map(|it| it.glyphs).sum()
+  }
+
+  /// Returns roughly how many bytes every atlas takes up on the GPU
+  pub fn gpu_bytes(&self) -> usize {
+    self.fonts.iter().map(|it| it.gpu_bytes).sum()
+  }
+}
+
+impl<'a> Font<'a> {
+  /// Returns statistics about the glyph cache of this font
+  pub fn cache_stats(&self) -> FontCacheStats<'a> {
+    let atlases = self.atlases.borrow();
+    let (width, height) = self.atlas_size();
+    let area = atlases
+      .iter()
+      .map(|it| it.width() as u64 * it.height() as u64)
+      .sum::<u64>();
+    let used = atlases.iter().map(|it| it.used_area()).sum::<u64>();
+
+    FontCacheStats {
+      name: self.name,
+      glyphs: self.chars.borrow().len(),
+      atlases: atlases.len(),
+      atlas_width: width,
+      atlas_height: height,
+      occupancy: used as f32 / area.max(1) as f32,
+      gpu_bytes: area as usize * 4,
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Returns statistics about the glyph caches of every loaded font,
+  /// useful for keeping an eye on how much memory text takes up
+  ///
+  /// **Example**
+  /// ```rs
+  /// let stats = fonts.cache_stats();
+  ///
+  /// for font in &stats.fonts {
+  ///   println!("{}: {} glyphs, {:.1}% of the atlas used", font.name, font.glyphs, font.occupancy * 100.0);
+  /// }
+  ///
+  /// println!("{} MB total", stats.gpu_bytes() / 1024 / 1024);
+  /// ```
+  pub fn cache_stats(&self) -> CacheStats<'a> {
+    CacheStats {
+      fonts: self.fonts.iter().map(Font::cache_stats).collect(),
+    }
+  }
+}
@@ -0,0 +1,374 @@
+use std::borrow::Cow;
+
+use macroquad::prelude::{vec2, Color, Vec2};
+
+use crate::{FontFace, FontStyle, FontWeight, Fonts, Locale, MeasuredText, TextParams};
+
+/// A copy of text drawn behind it with an offset
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Shadow {
+  /// How far the shadow is moved from the text in pixels
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::vec2"))]
+  pub offset: Vec2,
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::color"))]
+  pub color: Color,
+  /// How far the shadow is blurred in pixels, 0 draws a hard shadow
+  ///
+  /// **Default** `0.0`
+  ///
+  /// **See** [Fonts::draw_text_blurred]
+  pub blur: f32,
+}
+
+impl Default for Shadow {
+  fn default() -> Self {
+    Self {
+      offset: vec2(2.0, 2.0),
+      color: Color::from_rgba(0, 0, 0, 160),
+      blur: 0.0,
+    }
+  }
+}
+
+/// Changes the case of text when it's drawn, so UI conventions like all caps buttons
+/// don't need the source strings to be changed
+///
+/// **Default** [TextTransform::None]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextTransform {
+  /// Text is drawn as it is
+  #[default]
+  None,
+  Uppercase,
+  Lowercase,
+  /// The first letter of every word is uppercased, the other letters are kept as they are
+  Titlecase,
+}
+
+impl TextTransform {
+  /// Returns the text with its case changed, borrowed if nothing changes
+  ///
+  /// **Example**
+  /// ```rs
+  /// assert_eq!(TextTransform::Titlecase.apply("new game"), "New Game");
+  /// ```
+  pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+    self.apply_in(text, &Locale::default())
+  }
+
+  /// Returns the text with its case changed with the casing rules of a [Locale],
+  /// borrowed if nothing changes
+  ///
+  /// **Example**
+  /// ```rs
+  /// assert_eq!(TextTransform::Uppercase.apply_in("istanbul", &Locale::TURKISH), "İSTANBUL");
+  /// ```
+  pub fn apply_in<'t>(&self, text: &'t str, locale: &Locale) -> Cow<'t, str> {
+    // most text is already in the right case, so it isn't copied every time it's drawn
+    if !self.changes(text) {
+      return Cow::Borrowed(text);
+    }
+
+    match self {
+      Self::None => Cow::Borrowed(text),
+      Self::Uppercase => Cow::Owned(locale.to_uppercase(text)),
+      Self::Lowercase => Cow::Owned(locale.to_lowercase(text)),
+      Self::Titlecase => {
+        let mut result = String::with_capacity(text.len());
+        // apostrophes don't start words, so "don't" doesn't become "Don'T"
+        let mut word_start = true;
+
+        for c in text.chars() {
+          if word_start && c.is_alphanumeric() {
+            locale.uppercase_char(c, &mut result);
+          } else {
+            result.push(c);
+          }
+
+          word_start = !c.is_alphanumeric() && !matches!(c, '\'' | '’');
+        }
+
+        Cow::Owned(result)
+      }
+    }
+  }
+
+  /// Returns true if applying this transform changes the text, the casing rules of
+  /// every [Locale] only change letters that change without them too
+  fn changes(&self, text: &str) -> bool {
+    let changes_upper = |c: char| !c.to_uppercase().eq([c]);
+
+    match self {
+      Self::None => false,
+      Self::Uppercase => text.chars().any(changes_upper),
+      Self::Lowercase => text.chars().any(|c| !c.to_lowercase().eq([c])),
+      Self::Titlecase => {
+        let mut word_start = true;
+
+        text.chars().any(|c| {
+          let changes = word_start && c.is_alphanumeric() && changes_upper(c);
+
+          word_start = !c.is_alphanumeric() && !matches!(c, '\'' | '’');
+          changes
+        })
+      }
+    }
+  }
+}
+
+/// A named look for text, registered on [Fonts] so the look of the whole game
+/// can be changed in one place
+///
+/// **See** [Fonts::set_style]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TextStyle {
+  /// The size of the text in pixels
+  pub size: f32,
+  /// What the text should be scaled by
+  pub scale: f32,
+  #[cfg_attr(feature = "serde", serde(with = "crate::serde_with::color"))]
+  pub color: Color,
+  /// The family of the font the text is drawn with,
+  /// `None` looks fonts up by character like [Fonts::draw_text_ex]
+  pub family: Option<String>,
+  pub weight: FontWeight,
+  pub style: FontStyle,
+  /// Extra space added after every character in pixels
+  pub spacing: f32,
+  /// Draws a shadow behind the text
+  pub shadow: Option<Shadow>,
+  /// Draws a line under the text
+  pub underline: bool,
+  /// Changes the case of the text when it's drawn
+  pub text_transform: TextTransform,
+  /// The language rules the text is drawn with, `None` uses the locale of [Fonts]
+  pub locale: Option<Locale>,
+}
+
+impl Default for TextStyle {
+  fn default() -> Self {
+    let params = TextParams::default();
+
+    Self {
+      size: params.size,
+      scale: params.scale,
+      color: params.color,
+      family: None,
+      weight: params.weight,
+      style: params.style,
+      spacing: params.spacing,
+      shadow: None,
+      underline: false,
+      text_transform: TextTransform::None,
+      locale: None,
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Registers a [TextStyle] under a name, replacing the style that had that name
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.set_style("heading", TextStyle {
+  ///   size: 48.0,
+  ///   weight: FontWeight::Bold,
+  ///   shadow: Some(Shadow::default()),
+  ///   ..Default::default()
+  /// });
+  ///
+  /// fonts.draw_styled("Game Over", "heading", 20.0, 20.0);
+  /// ```
+  pub fn set_style(&mut self, name: impl Into<String>, style: TextStyle) {
+    self.styles.insert(name.into(), style);
+  }
+
+  /// Gets the style registered under a name
+  pub fn get_style(&self, name: &str) -> Option<&TextStyle> {
+    self.styles.get(name)
+  }
+
+  /// Removes the style registered under a name and returns it
+  pub fn remove_style(&mut self, name: &str) -> Option<TextStyle> {
+    self.styles.remove(name)
+  }
+
+  /// Returns the [TextParams] text is drawn with using a [TextStyle] at a position
+  pub fn style_params(&self, style: &TextStyle, x: f32, y: f32) -> TextParams {
+    let face = FontFace::new(style.weight, style.style);
+    let font = style.family.as_deref().and_then(|family| {
+      face
+        .closest_faces()
+        .chain([FontFace::REGULAR])
+        .find_map(|closest| self.get_index_by_family(family, closest))
+    });
+
+    TextParams {
+      x,
+      y,
+      size: style.size,
+      scale: style.scale,
+      color: style.color,
+      weight: style.weight,
+      style: style.style,
+      spacing: style.spacing,
+      font,
+      locale: style.locale,
+      ..Default::default()
+    }
+  }
+
+  /// Draws text with the style registered under a name, draws from TopLeft,
+  /// uses the default [TextStyle] if no style has that name
+  ///
+  /// **See** [Self::set_style]
+  pub fn draw_styled(&self, text: &str, name: &str, x: f32, y: f32) -> MeasuredText {
+    match self.get_style(name) {
+      Some(style) => self.draw_text_with_style(text, style, x, y),
+      None => self.draw_text_with_style(text, &TextStyle::default(), x, y),
+    }
+  }
+
+  /// Measures text like it's drawn with a [TextStyle], the shadow isn't included,
+  /// '\n' starts a new line like in [Self::draw_text_with_style]
+  pub fn measure_text_with_style(&self, text: &str, style: &TextStyle) -> MeasuredText {
+    let params = self.style_params(style, 0.0, 0.0);
+    let text = style
+      .text_transform
+      .apply_in(text, &self.params_locale(&params));
+
+    self.measure_text_detailed(&text, &params)
+  }
+
+  /// Draws text with a [TextStyle], draws from TopLeft
+  pub fn draw_text_with_style(
+    &self,
+    text: &str,
+    style: &TextStyle,
+    x: f32,
+    y: f32,
+  ) -> MeasuredText {
+    let params = self.style_params(style, x, y);
+    let text = &*style
+      .text_transform
+      .apply_in(text, &self.params_locale(&params));
+
+    if let Some(shadow) = style.shadow {
+      self.draw_text_blurred(
+        text,
+        &TextParams {
+          x: x + shadow.offset.x,
+          y: y + shadow.offset.y,
+          color: shadow.color,
+          ..params
+        },
+        shadow.blur,
+      );
+    }
+
+    let dimensions = self.draw_text_ex(text, &params);
+
+    if style.underline {
+      self.draw_underline(&params, dimensions.width);
+    }
+
+    dimensions
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use macroquad::prelude::Rect;
+
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+
+  #[test]
+  fn styled_text_is_measured_like_its_drawn() {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+
+    let style = TextStyle {
+      text_transform: TextTransform::Uppercase,
+      ..Default::default()
+    };
+    let measured = fonts.measure_text_with_style("continue\nquit game", &style);
+    // clipped away, so no quad needs an atlas texture
+    let drawn = fonts.draw_text_ex(
+      "CONTINUE\nQUIT GAME",
+      &TextParams {
+        clip: Some(Rect::new(-100.0, -100.0, 1.0, 1.0)),
+        ..fonts.style_params(&style, 0.0, 0.0)
+      },
+    );
+
+    assert_eq!(measured.lines, 2);
+    assert_eq!(measured.width, drawn.width);
+    assert_eq!(measured.height, drawn.height);
+    assert_eq!(measured.offset_y, drawn.offset_y);
+    assert_eq!(measured.glyphs, drawn.glyphs);
+  }
+
+  #[test]
+  fn text_in_the_right_case_is_borrowed() {
+    let cases = [
+      (TextTransform::None, "New game"),
+      (TextTransform::Uppercase, "NEW GAME 2"),
+      (TextTransform::Lowercase, "new game 2"),
+      (TextTransform::Titlecase, "New Game Don't 2nd"),
+    ];
+
+    for (transform, text) in cases {
+      assert!(
+        matches!(transform.apply(text), Cow::Borrowed(_)),
+        "{transform:?}"
+      );
+    }
+
+    assert!(matches!(
+      TextTransform::Uppercase.apply_in("ILIK", &Locale::TURKISH),
+      Cow::Borrowed(_)
+    ));
+  }
+
+  #[test]
+  fn text_in_another_case_is_transformed() {
+    let cases = [
+      (TextTransform::Uppercase, "New game", "NEW GAME"),
+      (TextTransform::Uppercase, "straße", "STRASSE"),
+      (TextTransform::Lowercase, "New Game", "new game"),
+      (
+        TextTransform::Titlecase,
+        "new game, don't",
+        "New Game, Don't",
+      ),
+    ];
+
+    for (transform, text, expected) in cases {
+      assert_eq!(transform.apply(text), expected, "{transform:?}");
+    }
+
+    assert_eq!(
+      TextTransform::Uppercase.apply_in("ılık", &Locale::TURKISH),
+      "ILIK"
+    );
+    assert_eq!(
+      TextTransform::Titlecase.apply_in("istanbul", &Locale::TURKISH),
+      "İstanbul"
+    );
+  }
+}
@@ -0,0 +1,46 @@
+use fontdb::{Database, Family, Query};
+
+use crate::{FontError, FontResult, Fonts};
+
+impl<'a> Fonts<'a> {
+  /// Loads a font that is installed on the system by its family name
+  /// with a default scale of 100.0, the family name is also used as the name of the font
+  ///
+  /// The first call scans all installed fonts, which can take a moment,
+  /// later calls reuse the result of that scan
+  ///
+  /// **Example**
+  /// ```rs
+  /// fonts.load_system_font("Segoe UI")?;
+  /// ```
+  ///
+  /// **See** [Self::load_system_font_with_scale]
+  pub fn load_system_font(&mut self, family: &'a str) -> FontResult<()> {
+    self.load_system_font_with_scale(family, 100.0)
+  }
+
+  /// Loads a font that is installed on the system by its family name with a given scale,
+  /// the family name is also used as the name of the font
+  ///
+  /// **See** [Self::load_font_from_bytes_with_scale]
+  pub fn load_system_font_with_scale(&mut self, family: &'a str, scale: f32) -> FontResult<()> {
+    let db = self.system_fonts.get_or_insert_with(|| {
+      let mut db = Database::new();
+      db.load_system_fonts();
+      Box::new(db)
+    });
+
+    let id = db
+      .query(&Query {
+        families: &[Family::Name(family)],
+        ..Default::default()
+      })
+      .ok_or_else(|| FontError::NotFound(family.to_string()))?;
+
+    let (bytes, index) = db
+      .with_face_data(id, |data, index| (data.to_vec(), index))
+      .ok_or_else(|| FontError::NotFound(family.to_string()))?;
+
+    self.load_font_from_bytes_with_index(family, bytes, scale, index)
+  }
+}
@@ -0,0 +1,656 @@
+use std::borrow::Cow;
+
+use macroquad::prelude::{vec2, Color, Rect, TextDimensions, Vec2};
+
+use crate::{
+  camera::ScreenSpace, misc::draw_rounded_rect, DrawFrom, Fonts, MeasuredText, ParagraphStyle,
+  TextParams,
+};
+
+/// How lines are aligned horizontally inside a rect or a block of lines
+///
+/// **See** [Fonts::draw_text_in_rect], [Fonts::draw_lines]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HorizontalAlign {
+  #[default]
+  Left,
+  Center,
+  Right,
+}
+
+/// How the block of lines is aligned vertically inside a rect
+///
+/// **See** [Fonts::draw_text_in_rect]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VerticalAlign {
+  #[default]
+  Top,
+  Center,
+  Bottom,
+}
+
+/// What happens to lines that don't fit inside a rect
+///
+/// **See** [Fonts::draw_text_in_rect]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Overflow {
+  /// Draws every line, even outside of the rect
+  Visible,
+  /// Draws every line, but clips glyphs to the rect
+  #[default]
+  Clip,
+  /// Only draws the lines that fit
+  Truncate,
+  /// Only draws the lines that fit and ends the last one with '…'
+  /// if some text didn't fit
+  Ellipsis,
+}
+
+/// How text is laid out by [Fonts::draw_text_in_rect]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextBoxStyle {
+  /// How the text is drawn, the position is ignored
+  pub params: TextParams,
+  pub align_x: HorizontalAlign,
+  pub align_y: VerticalAlign,
+  pub overflow: Overflow,
+  /// If lines are wrapped at the width of the rect
+  ///
+  /// **Default** `true`
+  pub wrap: bool,
+  /// The most lines that get drawn, lines after it count as not fitting
+  /// and are handled by [Self::overflow] like lines that don't fit inside the rect,
+  /// except they're never drawn, `None` only limits lines by the height of the rect
+  ///
+  /// **Default** `None`
+  pub max_lines: Option<usize>,
+  /// How paragraphs are indented and spaced
+  ///
+  /// **See** [Fonts::measure_paragraphs]
+  pub paragraph: ParagraphStyle,
+}
+
+impl Default for TextBoxStyle {
+  fn default() -> Self {
+    Self {
+      params: TextParams::default(),
+      align_x: HorizontalAlign::Left,
+      align_y: VerticalAlign::Top,
+      overflow: Overflow::Clip,
+      wrap: true,
+      max_lines: None,
+      paragraph: ParagraphStyle::default(),
+    }
+  }
+}
+
+/// Where a box drawn by [Fonts::draw_text_box] goes
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BoxAnchor {
+  /// The box fills this rect, the text is laid out inside of it
+  Rect(Rect),
+  /// The box is sized to the text and placed so its pivot is at the position,
+  /// the pivot goes from `(0, 0)` at the top left to `(1, 1)` at the bottom right of the box
+  ///
+  /// **Example**
+  /// ```rs
+  /// // a speech bubble centered above a head
+  /// BoxAnchor::Point {
+  ///   position: head,
+  ///   pivot: vec2(0.5, 1.0),
+  /// }
+  /// ```
+  Point { position: Vec2, pivot: Vec2 },
+}
+
+impl From<Rect> for BoxAnchor {
+  fn from(rect: Rect) -> Self {
+    Self::Rect(rect)
+  }
+}
+
+/// Places the top left corner of the box at the position
+impl From<Vec2> for BoxAnchor {
+  fn from(position: Vec2) -> Self {
+    Self::Point {
+      position,
+      pivot: Vec2::ZERO,
+    }
+  }
+}
+
+/// How a box is drawn by [Fonts::draw_text_box]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoxStyle {
+  /// How the text is laid out inside of the box
+  pub text: TextBoxStyle,
+  /// **Default** `Color::from_rgba(0, 0, 0, 200)`
+  pub background: Color,
+  /// The space between the edges of the box and the text,
+  /// `x` on the left and right, `y` on the top and bottom
+  ///
+  /// **Default** `vec2(8.0, 6.0)`
+  pub padding: Vec2,
+  /// The radius of the corners, 0 draws a solid rect
+  ///
+  /// **Default** `4.0`
+  pub corner_radius: f32,
+  /// The width text is wrapped at when the box is sized to the text,
+  /// `None` doesn't wrap, ignored when the box fills a rect
+  ///
+  /// **Default** `None`
+  pub max_width: Option<f32>,
+}
+
+impl Default for BoxStyle {
+  fn default() -> Self {
+    Self {
+      text: TextBoxStyle::default(),
+      background: Color::from_rgba(0, 0, 0, 200),
+      padding: vec2(8.0, 6.0),
+      corner_radius: 4.0,
+      max_width: None,
+    }
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws text inside of a rect, wrapping and aligning it
+  /// and handling text that doesn't fit with [TextBoxStyle::overflow]
+  ///
+  /// Returns the byte index up to which the text fit entirely inside the rect
+  /// and [TextBoxStyle::max_lines], which is the length of the text if all of it fit,
+  /// the rest of the text starts there
+  ///
+  /// **Example**
+  /// ```rs
+  /// let fit = fonts.draw_text_in_rect(&dialogue, Rect::new(20.0, 20.0, 300.0, 80.0), &TextBoxStyle {
+  ///   align_x: HorizontalAlign::Center,
+  ///   overflow: Overflow::Ellipsis,
+  ///   ..Default::default()
+  /// });
+  ///
+  /// if fit < dialogue.len() {
+  ///   // show the rest on the next page
+  /// }
+  /// ```
+  pub fn draw_text_in_rect(&self, text: &str, rect: Rect, style: &TextBoxStyle) -> usize {
+    self.lay_out_in_rect(text, rect, style, |line, params| {
+      self.draw_text_ex(line, params);
+    })
+  }
+
+  /// Lays out text inside of a rect like [Self::draw_text_in_rect],
+  /// calling `draw` with every line that gets drawn and the params it's drawn with
+  fn lay_out_in_rect(
+    &self,
+    text: &str,
+    rect: Rect,
+    style: &TextBoxStyle,
+    mut draw: impl FnMut(&str, &TextParams),
+  ) -> usize {
+    let params = TextParams {
+      x: 0.0,
+      y: 0.0,
+      draw: DrawFrom::TopLeft,
+      ..style.params
+    };
+    // text aligned to the top only has to be laid out until it can't be seen anymore,
+    // lines below the rect are still needed to know how much text fit
+    let until = match (style.align_y, style.overflow) {
+      (VerticalAlign::Top, Overflow::Visible) => params
+        .visible
+        .map_or(f32::INFINITY, |it| rect.h.max(it.bottom() - rect.y)),
+      (VerticalAlign::Top, _) => rect.h,
+      _ => f32::INFINITY,
+    };
+    let layout = self.measure_paragraphs_until(
+      text,
+      &params,
+      style.wrap.then_some(rect.w),
+      &style.paragraph,
+      until,
+    );
+    // how far down a line is from the first line
+    let line_y = |i: usize| layout.lines[i].top - layout.lines[0].top;
+    let line_bottom = |i: usize| line_y(i) + layout.lines[i].height;
+
+    let max_lines = style.max_lines.unwrap_or(usize::MAX);
+    let fitting = (0..layout.lines.len())
+      .take_while(|i| *i < max_lines && line_bottom(*i) <= rect.h)
+      .count();
+    let fit = if fitting == layout.lines.len() {
+      text.len()
+    } else {
+      fitting
+        .checked_sub(1)
+        .map_or(0, |last| layout.lines[last].range.end)
+    };
+
+    let drawn = match style.overflow {
+      Overflow::Visible | Overflow::Clip => layout.lines.len().min(max_lines),
+      Overflow::Truncate | Overflow::Ellipsis => fitting,
+    };
+    let height = drawn.checked_sub(1).map_or(0.0, line_bottom);
+    let top = match style.align_y {
+      VerticalAlign::Top => rect.y,
+      VerticalAlign::Center => rect.y + (rect.h - height) / 2.0,
+      VerticalAlign::Bottom => rect.bottom() - height,
+    };
+    let clip = match style.overflow {
+      // an empty rect hides everything when the clip rects don't overlap
+      Overflow::Clip => Some(params.clip.map_or(rect, |it| {
+        it.intersect(rect)
+          .unwrap_or(Rect::new(rect.x, rect.y, 0.0, 0.0))
+      })),
+      _ => params.clip,
+    };
+
+    for (i, line) in layout.lines.iter().take(drawn).enumerate() {
+      let y = top + line_y(i);
+
+      if params.is_line_culled(y, y + line.height) {
+        continue;
+      }
+
+      let ellipsis = style.overflow == Overflow::Ellipsis && i + 1 == drawn && fit < text.len();
+      // the indent of the line
+      let indent = layout.glyphs.get(line.glyphs.start).map_or(0.0, |it| it.x);
+      let (line_text, width) = if ellipsis {
+        let line_text = self.ellipsize(&text[line.range.clone()], rect.w - indent, &params);
+        let width = indent + self.measure_params(&line_text, &params).width;
+
+        (Cow::Owned(line_text), width)
+      } else {
+        (Cow::Borrowed(&text[line.range.clone()]), line.width)
+      };
+      let x = indent
+        + match style.align_x {
+          HorizontalAlign::Left => rect.x,
+          HorizontalAlign::Center => rect.x + (rect.w - width) / 2.0,
+          HorizontalAlign::Right => rect.right() - width,
+        };
+
+      draw(
+        &line_text,
+        &TextParams {
+          x,
+          y,
+          clip,
+          ..params
+        },
+      );
+    }
+
+    fit
+  }
+
+  /// Draws a background sized to the text plus padding and then the text on top of it,
+  /// useful for tooltips and speech bubbles
+  ///
+  /// Returns the rect of the whole box
+  ///
+  /// **Example**
+  /// ```rs
+  /// let (x, y) = mouse_position();
+  ///
+  /// fonts.draw_text_box("Sword of Truth\n+5 damage", vec2(x + 16.0, y), &BoxStyle {
+  ///   max_width: Some(200.0),
+  ///   ..Default::default()
+  /// });
+  /// ```
+  ///
+  /// **See** [BoxAnchor], [Self::measure_text_box]
+  pub fn draw_text_box(&self, text: &str, anchor: impl Into<BoxAnchor>, style: &BoxStyle) -> Rect {
+    let (rect, inner) = self.text_box_rects(text, anchor.into(), style);
+
+    {
+      let _screen = ScreenSpace::new(style.text.params.space);
+      draw_rounded_rect(
+        rect,
+        style.corner_radius,
+        style.text.params.z,
+        style.background,
+      );
+    }
+
+    self.draw_text_in_rect(text, inner, &style.text);
+
+    rect
+  }
+
+  /// Returns the rect of the whole box [Self::draw_text_box] draws
+  /// and the rect inside of the padding the text is laid out in
+  fn text_box_rects(&self, text: &str, anchor: BoxAnchor, style: &BoxStyle) -> (Rect, Rect) {
+    let rect = match anchor {
+      BoxAnchor::Rect(rect) => rect,
+      BoxAnchor::Point { position, pivot } => {
+        let size = self.measure_text_box(text, style);
+
+        Rect::new(
+          position.x - size.x * pivot.x,
+          position.y - size.y * pivot.y,
+          size.x,
+          size.y,
+        )
+      }
+    };
+
+    let padding = style.padding;
+    let inner = Rect::new(
+      rect.x + padding.x,
+      rect.y + padding.y,
+      (rect.w - padding.x * 2.0).max(0.0),
+      (rect.h - padding.y * 2.0).max(0.0),
+    );
+
+    (rect, inner)
+  }
+
+  /// Returns the size of the box [Self::draw_text_box] draws
+  /// when it's sized to the text, padding included
+  pub fn measure_text_box(&self, text: &str, style: &BoxStyle) -> Vec2 {
+    let params = TextParams {
+      x: 0.0,
+      y: 0.0,
+      draw: DrawFrom::TopLeft,
+      ..style.text.params
+    };
+    let wrap = style.max_width.filter(|_| style.text.wrap);
+    let layout = self.measure_paragraphs(text, &params, wrap, &style.text.paragraph);
+    let lines = &layout.lines[..layout
+      .lines
+      .len()
+      .min(style.text.max_lines.unwrap_or(usize::MAX))];
+
+    let width = lines
+      .iter()
+      .map(|line| line.width + layout.glyphs.get(line.glyphs.start).map_or(0.0, |it| it.x))
+      .fold(0.0, f32::max);
+    let height = lines
+      .last()
+      .map_or(0.0, |last| last.top + last.height - layout.lines[0].top);
+
+    // rounded up, so laying out the text again inside of the box gives the same lines
+    vec2(width.ceil(), height.ceil()) + style.padding * 2.0
+  }
+
+  /// Draws lines below each other spaced by the line height of the first loaded font,
+  /// aligned inside the width of the widest line, starting at [TextParams::x] and [TextParams::y]
+  ///
+  /// Lines aren't wrapped and line breaks inside of them aren't handled,
+  /// use [Self::draw_text_in_rect] for that
+  ///
+  /// Returns the dimensions of the whole block,
+  /// its height is the number of lines times the line height
+  ///
+  /// **Example**
+  /// ```rs
+  /// let credits = ["Programming", "Art", "Music"];
+  ///
+  /// fonts.draw_lines(&credits, &TextParams {
+  ///   x: 20.0,
+  ///   y: 20.0,
+  ///   ..Default::default()
+  /// }, HorizontalAlign::Center);
+  /// ```
+  pub fn draw_lines(
+    &self,
+    lines: &[impl AsRef<str>],
+    params: &TextParams,
+    align: HorizontalAlign,
+  ) -> MeasuredText {
+    let line_height = self.line_height(params);
+    let dimensions = lines
+      .iter()
+      .map(|line| self.measure_text_metrics(line.as_ref(), params))
+      .collect::<Vec<_>>();
+    let width = dimensions.iter().map(|it| it.width).fold(0.0, f32::max);
+    let mut measured = MeasuredText {
+      lines: 0,
+      ..MeasuredText::empty(params)
+    };
+
+    for (i, (line, line_dimensions)) in lines.iter().zip(&dimensions).enumerate() {
+      let x = match align {
+        HorizontalAlign::Left => params.x,
+        HorizontalAlign::Center => params.x + (width - line_dimensions.width) / 2.0,
+        HorizontalAlign::Right => params.x + width - line_dimensions.width,
+      };
+
+      let line = self.draw_text_ex(
+        line.as_ref(),
+        &TextParams {
+          x,
+          y: params.y + i as f32 * line_height,
+          ..*params
+        },
+      );
+
+      measured.add(&line);
+    }
+
+    let dimensions = TextDimensions {
+      width,
+      height: lines.len() as f32 * line_height,
+      offset_y: dimensions.first().map_or(0.0, |it| it.offset_y),
+    };
+
+    MeasuredText {
+      lines: measured.lines.max(1),
+      ..measured
+    }
+    .with_dimensions(dimensions, params)
+  }
+
+  /// Cuts characters off the end of a line until it fits in `max_width` with '…' appended
+  fn ellipsize(&self, line: &str, max_width: f32, params: &TextParams) -> String {
+    let mut end = line.trim_end().len();
+
+    loop {
+      let text = format!("{}…", line[..end].trim_end());
+
+      if end == 0 || self.measure_text_metrics(&text, params).width <= max_width {
+        return text;
+      }
+
+      end = line[..end].char_indices().next_back().map_or(0, |(i, _)| i);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::AtlasConfig;
+
+  use super::*;
+
+  const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+  const TEXT: &str = "one two three four";
+
+  fn fonts() -> Fonts<'static> {
+    let mut fonts = Fonts::default();
+
+    // the default atlas is huge and slow to allocate in debug builds
+    fonts.set_atlas_config(AtlasConfig {
+      initial_size: 256,
+      ..Default::default()
+    });
+    fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+    fonts
+  }
+
+  /// A rect every word of [TEXT] fits in on its own line, two lines high
+  fn two_lines(fonts: &Fonts) -> Rect {
+    let params = TextParams::default();
+    let width = fonts.measure_text_metrics("three", &params).width * 1.2;
+    let lines = fonts.measure_paragraphs(TEXT, &params, Some(width), &Default::default());
+
+    Rect::new(
+      0.0,
+      0.0,
+      width,
+      lines.lines[1].top + lines.lines[1].height - lines.lines[0].top + 0.5,
+    )
+  }
+
+  fn lay_out(fonts: &Fonts, text: &str, rect: Rect, style: &TextBoxStyle) -> (Vec<String>, usize) {
+    let mut lines = Vec::new();
+    let fit = fonts.lay_out_in_rect(text, rect, style, |line, _| {
+      lines.push(line.trim_end().to_string())
+    });
+
+    (lines, fit)
+  }
+
+  #[test]
+  fn truncate_only_draws_lines_that_fit() {
+    let fonts = fonts();
+    let rect = two_lines(&fonts);
+    let (lines, fit) = lay_out(
+      &fonts,
+      TEXT,
+      rect,
+      &TextBoxStyle {
+        overflow: Overflow::Truncate,
+        ..Default::default()
+      },
+    );
+
+    assert_eq!(lines, ["one", "two"]);
+    assert_eq!(TEXT[fit..].trim_start(), "three four");
+  }
+
+  #[test]
+  fn ellipsis_ends_the_last_line_that_fits() {
+    let fonts = fonts();
+    let rect = two_lines(&fonts);
+    let style = TextBoxStyle {
+      overflow: Overflow::Ellipsis,
+      ..Default::default()
+    };
+    let (lines, fit) = lay_out(&fonts, TEXT, rect, &style);
+
+    assert_eq!(lines, ["one", "two…"]);
+    assert_eq!(TEXT[fit..].trim_start(), "three four");
+
+    // nothing is cut off when all of the text fits
+    let (lines, fit) = lay_out(&fonts, "one two", rect, &style);
+
+    assert_eq!(lines, ["one", "two"]);
+    assert_eq!(fit, "one two".len());
+  }
+
+  #[test]
+  fn visible_and_clip_draw_every_line() {
+    let fonts = fonts();
+    let rect = two_lines(&fonts);
+
+    for overflow in [Overflow::Visible, Overflow::Clip] {
+      let (lines, fit) = lay_out(
+        &fonts,
+        TEXT,
+        rect,
+        &TextBoxStyle {
+          overflow,
+          ..Default::default()
+        },
+      );
+
+      assert_eq!(lines, ["one", "two", "three", "four"]);
+      assert_eq!(TEXT[fit..].trim_start(), "three four");
+    }
+  }
+
+  #[test]
+  fn max_lines_limits_what_fits() {
+    let fonts = fonts();
+    let rect = Rect {
+      h: 1000.0,
+      ..two_lines(&fonts)
+    };
+
+    for overflow in [Overflow::Clip, Overflow::Truncate] {
+      let (lines, fit) = lay_out(
+        &fonts,
+        TEXT,
+        rect,
+        &TextBoxStyle {
+          overflow,
+          max_lines: Some(1),
+          ..Default::default()
+        },
+      );
+
+      assert_eq!(lines, ["one"]);
+      assert_eq!(TEXT[fit..].trim_start(), "two three four");
+    }
+
+    let (lines, fit) = lay_out(&fonts, TEXT, rect, &Default::default());
+
+    assert_eq!(lines.len(), 4);
+    assert_eq!(fit, TEXT.len());
+  }
+
+  #[test]
+  fn fit_is_the_same_when_laid_out_partway() {
+    let fonts = fonts();
+    let rect = two_lines(&fonts);
+    let text = TEXT.repeat(50);
+
+    // text aligned to the top stops being laid out below the rect,
+    // text aligned to the bottom is laid out entirely
+    let (top, top_fit) = lay_out(
+      &fonts,
+      &text,
+      rect,
+      &TextBoxStyle {
+        overflow: Overflow::Truncate,
+        ..Default::default()
+      },
+    );
+    let (bottom, bottom_fit) = lay_out(
+      &fonts,
+      &text,
+      rect,
+      &TextBoxStyle {
+        overflow: Overflow::Truncate,
+        align_y: VerticalAlign::Bottom,
+        ..Default::default()
+      },
+    );
+
+    assert_eq!(top, bottom);
+    assert_eq!(top_fit, bottom_fit);
+    assert!(top_fit < text.len());
+  }
+
+  #[test]
+  fn text_box_fits_its_text() {
+    let fonts = fonts();
+    let text = "A tooltip with a long first line\nand a short one";
+
+    for max_width in [None, Some(73.3), Some(120.7)] {
+      let style = BoxStyle {
+        max_width,
+        ..Default::default()
+      };
+      let (rect, inner) = fonts.text_box_rects(text, vec2(10.5, 20.25).into(), &style);
+
+      assert_eq!(rect.size(), fonts.measure_text_box(text, &style));
+
+      // the text is wrapped at the width of the box instead of max_width when it's drawn
+      let layout =
+        fonts.measure_paragraphs(text, &TextParams::default(), max_width, &Default::default());
+      let expected = layout
+        .lines
+        .iter()
+        .map(|line| text[line.range.clone()].trim_end())
+        .collect::<Vec<_>>();
+      let (lines, fit) = lay_out(&fonts, text, inner, &style.text);
+
+      assert_eq!(lines, expected);
+      assert_eq!(fit, text.len());
+    }
+  }
+}
@@ -0,0 +1,326 @@
+use std::rc::Rc;
+
+use macroquad::prelude::{draw_mesh, vec2, Mesh, Vec2, Vertex};
+use ttf_parser::{Face, OutlineBuilder};
+
+use crate::{
+  camera::ScreenSpace, misc::is_blank, Font, FontStyle, Fonts, MeasuredText, TextParams,
+};
+
+/// Collects the contours of a glyph outline as polygons, flattening curves
+struct Contours {
+  contours: Vec<Vec<Vec2>>,
+  /// The largest distance a flattened curve can be away from the real curve, in font units
+  tolerance: f32,
+}
+
+impl Contours {
+  fn last(&self) -> Vec2 {
+    self
+      .contours
+      .last()
+      .and_then(|it| it.last())
+      .copied()
+      .unwrap_or_default()
+  }
+
+  fn push(&mut self, point: Vec2) {
+    if let Some(contour) = self.contours.last_mut() {
+      contour.push(point);
+    }
+  }
+
+  /// How many segments a curve with a given second difference is flattened into
+  fn segments(&self, difference: f32) -> usize {
+    ((difference / (8.0 * self.tolerance)).sqrt().ceil() as usize).clamp(1, 64)
+  }
+}
+
+impl OutlineBuilder for Contours {
+  fn move_to(&mut self, x: f32, y: f32) {
+    self.contours.push(vec![vec2(x, y)]);
+  }
+
+  fn line_to(&mut self, x: f32, y: f32) {
+    self.push(vec2(x, y));
+  }
+
+  fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+    let (p0, p1, p2) = (self.last(), vec2(x1, y1), vec2(x, y));
+    let segments = self.segments((p0 - 2.0 * p1 + p2).length() * 2.0);
+
+    for i in 1..=segments {
+      let t = i as f32 / segments as f32;
+
+      self.push(p0.lerp(p1, t).lerp(p1.lerp(p2, t), t));
+    }
+  }
+
+  fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+    let (p0, p1, p2, p3) = (self.last(), vec2(x1, y1), vec2(x2, y2), vec2(x, y));
+    let difference = (p0 - 2.0 * p1 + p2)
+      .length()
+      .max((p1 - 2.0 * p2 + p3).length());
+    let segments = self.segments(difference * 6.0);
+
+    for i in 1..=segments {
+      let t = i as f32 / segments as f32;
+      let (a, b, c) = (p0.lerp(p1, t), p1.lerp(p2, t), p2.lerp(p3, t));
+
+      self.push(a.lerp(b, t).lerp(b.lerp(c, t), t));
+    }
+  }
+
+  fn close(&mut self) {}
+}
+
+/// Fills polygons with the nonzero winding rule by cutting them into horizontal trapezoids
+/// between every vertex, returns the triangles as a list of vertices
+///
+/// Edges that cross each other inside of a trapezoid aren't split,
+/// glyph outlines don't overlap like that in practice
+fn triangulate(contours: &[Vec<Vec2>]) -> Vec<Vec2> {
+  // (top, bottom, winding), top has the smaller y-coordinate
+  let mut edges = Vec::new();
+
+  for contour in contours {
+    for (i, a) in contour.iter().enumerate() {
+      let b = contour[(i + 1) % contour.len()];
+
+      if a.y < b.y {
+        edges.push((*a, b, 1));
+      } else if a.y > b.y {
+        edges.push((b, *a, -1));
+      }
+    }
+  }
+
+  let mut ys = edges
+    .iter()
+    .flat_map(|(top, bottom, _)| [top.y, bottom.y])
+    .collect::<Vec<_>>();
+
+  ys.sort_by(f32::total_cmp);
+  ys.dedup();
+
+  let mut triangles = Vec::new();
+  let mut crossing = Vec::new();
+
+  for band in ys.windows(2) {
+    let (y0, y1) = (band[0], band[1]);
+    let x_at = |(top, bottom): (Vec2, Vec2), y: f32| {
+      top.x + (bottom.x - top.x) * (y - top.y) / (bottom.y - top.y)
+    };
+
+    crossing.clear();
+    crossing.extend(
+      edges
+        .iter()
+        .filter(|(top, bottom, _)| top.y <= y0 && bottom.y >= y1)
+        .map(|(top, bottom, winding)| {
+          (
+            x_at((*top, *bottom), y0),
+            x_at((*top, *bottom), y1),
+            *winding,
+          )
+        }),
+    );
+    crossing.sort_by(|a, b| (a.0 + a.1).total_cmp(&(b.0 + b.1)));
+
+    let mut winding = 0;
+    let mut left = (0.0, 0.0);
+
+    for (x0, x1, edge_winding) in crossing.iter().copied() {
+      let was_inside = winding != 0;
+      winding += edge_winding;
+
+      if !was_inside {
+        left = (x0, x1);
+      } else if winding == 0 {
+        let (a, b) = (vec2(left.0, y0), vec2(x0, y0));
+        let (c, d) = (vec2(x1, y1), vec2(left.1, y1));
+
+        triangles.extend([a, b, c, a, c, d]);
+      }
+    }
+  }
+
+  triangles
+}
+
+impl<'a> Font<'a> {
+  /// Returns the outline of a glyph filled with triangles, every 3 points are a triangle,
+  /// in font units with y pointing up, multiply by `size / units_per_em()` to get pixels
+  ///
+  /// Triangles are cached, so asking again doesn't tessellate again
+  ///
+  /// **Example**
+  /// ```rs
+  /// let font = fonts.get_font_by_char_or_panic('A');
+  /// let scale = 400.0 / font.units_per_em();
+  ///
+  /// let vertices = font
+  ///   .glyph_triangles('A')
+  ///   .iter()
+  ///   .map(|it| Vertex::new(x + it.x * scale, y - it.y * scale, 0.0, 0.0, 0.0, WHITE))
+  ///   .collect::<Vec<_>>();
+  /// ```
+  pub fn glyph_triangles(&self, c: char) -> Rc<[Vec2]> {
+    self
+      .outlines
+      .borrow_mut()
+      .entry(c)
+      .or_insert_with(|| {
+        let Ok(face) = Face::from_slice(&self.data, self.collection_index) else {
+          return Rc::from([]);
+        };
+        let mut contours = Contours {
+          contours: Vec::new(),
+          tolerance: face.units_per_em() as f32 / 2048.0,
+        };
+
+        if let Some(glyph) = face.glyph_index(c) {
+          face.outline_glyph(glyph, &mut contours);
+        }
+
+        triangulate(&contours.contours).into()
+      })
+      .clone()
+  }
+}
+
+impl<'a> Fonts<'a> {
+  /// Draws text with given [TextParams] from glyph outlines filled with triangles
+  /// instead of glyphs from the atlas, so it stays sharp at any size and zoom level,
+  /// useful for things like zoomable maps where caching glyphs at every zoom level isn't practical
+  ///
+  /// Glyphs are drawn right away without antialiasing and aren't clipped,
+  /// [TextParams::bottom_color] is ignored
+  ///
+  /// **Example**
+  /// ```rs
+  /// set_camera(&map_camera);
+  ///
+  /// fonts.draw_text_vector("Mountains", &TextParams {
+  ///   x: 120.0,
+  ///   y: 80.0,
+  ///   size: 4.0,
+  ///   ..Default::default()
+  /// });
+  /// ```
+  ///
+  /// **See** [Self::vector_meshes]
+  pub fn draw_text_vector(&self, text: &str, params: &TextParams) -> MeasuredText {
+    let (meshes, dimensions) = self.build_vector_meshes(text, params);
+    let _screen = ScreenSpace::new(params.space);
+
+    for mesh in &meshes {
+      draw_mesh(mesh);
+    }
+
+    dimensions
+  }
+
+  /// Builds the meshes [Self::draw_text_vector] would draw,
+  /// useful for building the meshes once and drawing them every frame
+  pub fn vector_meshes(&self, text: &str, params: &TextParams) -> Vec<Mesh> {
+    self.build_vector_meshes(text, params).0
+  }
+
+  /// Builds the meshes of text, '\n' starts a new line like in [Self::draw_text_ex]
+  fn build_vector_meshes(&self, text: &str, params: &TextParams) -> (Vec<Mesh>, MeasuredText) {
+    let mut meshes = Vec::<Mesh>::new();
+
+    if !text.contains('\n') {
+      let dimensions = self.build_vector_line(text, params, &mut meshes);
+
+      return (meshes, dimensions);
+    }
+
+    let lines = self.split_lines(text, params).map(|(line, line_params)| {
+      (
+        line,
+        line_params.y - params.y,
+        self.build_vector_line(line, &line_params, &mut meshes),
+      )
+    });
+    let dimensions = Self::stack_lines(lines, params);
+
+    (meshes, dimensions)
+  }
+
+  /// Adds the triangles of a single line of text to the meshes
+  fn build_vector_line(
+    &self,
+    text: &str,
+    params: &TextParams,
+    meshes: &mut Vec<Mesh>,
+  ) -> MeasuredText {
+    let mut pen_x = 0.0;
+    let baseline = self.baseline_y(params);
+
+    self.layout_measured(text, params, false, |_, c, placement| {
+      let x = params.x + pen_x;
+      pen_x += placement.advance;
+
+      let Some(c) = self.control_chars.apply(c).filter(|c| !is_blank(*c)) else {
+        return;
+      };
+
+      let font = placement.font;
+      let triangles = font.glyph_triangles(c);
+
+      // mesh indices are u16, a glyph this detailed can't be drawn
+      if triangles.len() > u16::MAX as usize {
+        return;
+      }
+
+      let scale = font.adjusted_size(params.size) / font.units_per_em();
+      let (scale_x, scale_y) = (
+        scale * params.horizontal_scale(),
+        scale * params.vertical_scale(),
+      );
+      let y = baseline - font.baseline_shift(params);
+      let skew = match placement.synthesized.style {
+        FontStyle::Italic => 0.2,
+        FontStyle::Normal => 0.0,
+      };
+      let bold_offset = Self::synthetic_bold_offset(placement.synthesized, params);
+      let offsets: &[f32] = if bold_offset > 0.0 {
+        &[bold_offset, 0.0]
+      } else {
+        &[0.0]
+      };
+
+      for offset in offsets {
+        let reuse = meshes
+          .last()
+          .is_some_and(|it| it.vertices.len() + triangles.len() <= u16::MAX as usize);
+
+        if !reuse {
+          meshes.push(Mesh {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            texture: None,
+          });
+        }
+
+        let mesh = meshes.last_mut().unwrap();
+
+        for point in triangles.iter() {
+          let up = point.y * scale_y;
+
+          mesh.indices.push(mesh.vertices.len() as u16);
+          mesh.vertices.push(Vertex::new(
+            x + offset + point.x * scale_x + up * skew,
+            y - up,
+            params.z,
+            0.0,
+            0.0,
+            params.color,
+          ));
+        }
+      }
+    })
+  }
+}
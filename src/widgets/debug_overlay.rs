@@ -0,0 +1,161 @@
+use std::fmt::{Display, Write};
+
+use macroquad::prelude::{get_fps, get_time, screen_height, screen_width, vec2, Rect};
+
+use crate::{BoxAnchor, BoxStyle, DrawSpace, Fonts, HorizontalAlign, TextParams, VerticalAlign};
+
+/// A small box in a corner of the screen showing the FPS and values registered by key,
+/// drawn in screen space even when a camera is set
+///
+/// The text only changes when a value or the FPS does, and the FPS is only updated
+/// a few times a second, so the layout is reused from the layout cache most frames
+///
+/// **Example**
+/// ```rs
+/// let mut overlay = DebugOverlay::new();
+///
+/// loop {
+///   overlay.set("entities", world.len());
+///   overlay.set("player", format!("{:.1}, {:.1}", player.x, player.y));
+///   overlay.draw(&fonts);
+///
+///   next_frame().await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DebugOverlay {
+  values: Vec<(String, String)>,
+  fps: i32,
+  last_fps_update: f64,
+  text: String,
+  /// How the box and the text are drawn, [TextParams::space] is ignored
+  pub style: BoxStyle,
+  /// Which side of the screen the box is on horizontally
+  ///
+  /// **Default** [HorizontalAlign::Left]
+  pub align_x: HorizontalAlign,
+  /// Which side of the screen the box is on vertically
+  ///
+  /// **Default** [VerticalAlign::Top]
+  pub align_y: VerticalAlign,
+  /// The space between the box and the edges of the screen
+  ///
+  /// **Default** `8.0`
+  pub margin: f32,
+  /// If the FPS is shown above the values
+  ///
+  /// **Default** `true`
+  pub show_fps: bool,
+}
+
+impl DebugOverlay {
+  // how often the shown fps is updated in seconds
+  const FPS_INTERVAL: f64 = 0.5;
+
+  /// Creates an overlay in the top left corner that only shows the FPS
+  pub fn new() -> Self {
+    let mut style = BoxStyle::default();
+
+    style.text.params = TextParams {
+      size: 16.0,
+      space: DrawSpace::Screen,
+      ..Default::default()
+    };
+    style.text.wrap = false;
+
+    Self {
+      values: Vec::new(),
+      fps: 0,
+      last_fps_update: f64::NEG_INFINITY,
+      text: String::new(),
+      style,
+      align_x: HorizontalAlign::Left,
+      align_y: VerticalAlign::Top,
+      margin: 8.0,
+      show_fps: true,
+    }
+  }
+
+  /// Sets the value shown for a key, keys are shown in the order they were first set
+  pub fn set(&mut self, key: &str, value: impl Display) {
+    let entry = match self.values.iter().position(|(it, _)| it == key) {
+      Some(i) => &mut self.values[i].1,
+      None => {
+        self.values.push((key.to_string(), String::new()));
+        &mut self.values.last_mut().unwrap().1
+      }
+    };
+
+    entry.clear();
+    // writing to a string can't fail
+    let _ = write!(entry, "{value}");
+  }
+
+  /// Removes the value of a key, returns true if it was set
+  pub fn remove(&mut self, key: &str) -> bool {
+    let len = self.values.len();
+
+    self.values.retain(|(it, _)| it != key);
+    self.values.len() != len
+  }
+
+  /// Removes every value
+  pub fn clear(&mut self) {
+    self.values.clear();
+  }
+
+  /// Draws the overlay, call this once every frame after drawing everything else
+  ///
+  /// Returns the rect of the box, nothing is drawn if there's nothing to show
+  pub fn draw(&mut self, fonts: &Fonts) -> Option<Rect> {
+    if get_time() - self.last_fps_update >= Self::FPS_INTERVAL {
+      self.fps = get_fps();
+      self.last_fps_update = get_time();
+    }
+
+    self.text.clear();
+
+    if self.show_fps {
+      let _ = writeln!(self.text, "FPS: {}", self.fps);
+    }
+
+    for (key, value) in &self.values {
+      let _ = writeln!(self.text, "{key}: {value}");
+    }
+
+    let text = self.text.trim_end_matches('\n');
+
+    if text.is_empty() {
+      return None;
+    }
+
+    let (position_x, pivot_x) = match self.align_x {
+      HorizontalAlign::Left => (self.margin, 0.0),
+      HorizontalAlign::Center => (screen_width() / 2.0, 0.5),
+      HorizontalAlign::Right => (screen_width() - self.margin, 1.0),
+    };
+    let (position_y, pivot_y) = match self.align_y {
+      VerticalAlign::Top => (self.margin, 0.0),
+      VerticalAlign::Center => (screen_height() / 2.0, 0.5),
+      VerticalAlign::Bottom => (screen_height() - self.margin, 1.0),
+    };
+
+    let mut style = self.style;
+    style.text.params.space = DrawSpace::Screen;
+
+    Some(fonts.draw_text_box(
+      text,
+      BoxAnchor::Point {
+        position: vec2(position_x, position_y),
+        pivot: vec2(pivot_x, pivot_y),
+      },
+      &style,
+    ))
+  }
+}
+
+impl Default for DebugOverlay {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,9 @@
+//! Small optional widgets built on [Fonts](crate::Fonts), enabled with the `widgets` feature
+
+pub(crate) mod debug_overlay;
+pub(crate) mod text_input;
+pub(crate) mod text_log;
+
+pub use debug_overlay::DebugOverlay;
+pub use text_input::TextInput;
+pub use text_log::TextLog;
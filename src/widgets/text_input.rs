@@ -0,0 +1,216 @@
+use macroquad::prelude::{
+  draw_line, get_char_pressed, get_time, is_key_pressed, is_mouse_button_pressed, mouse_position,
+  vec2, KeyCode, MouseButton, Rect,
+};
+
+use crate::{Composition, Fonts, TextParams};
+
+/// A single line text input, owns the text and handles keyboard and mouse input
+///
+/// **Example**
+/// ```rs
+/// let mut input = TextInput::new(TextParams {
+///   x: 20.0,
+///   y: 20.0,
+///   ..Default::default()
+/// }, 300.0);
+///
+/// loop {
+///   input.update(&fonts);
+///   input.draw(&fonts);
+///
+///   if is_key_pressed(KeyCode::Enter) {
+///     println!("{}", input.value());
+///   }
+///
+///   next_frame().await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextInput {
+  value: String,
+  caret: usize,
+  composition: Option<(String, usize)>,
+  last_input: f64,
+  /// How the text is drawn
+  pub params: TextParams,
+  /// The width of the clickable area
+  pub width: f32,
+  /// If this input receives keyboard input
+  pub focused: bool,
+  /// The maximum amount of characters, `None` means there is no limit
+  pub max_chars: Option<usize>,
+}
+
+impl TextInput {
+  // how long the caret stays visible or hidden in seconds
+  const BLINK: f64 = 0.5;
+
+  /// Creates a new empty and focused text input
+  pub fn new(params: TextParams, width: f32) -> Self {
+    Self {
+      value: String::new(),
+      caret: 0,
+      composition: None,
+      last_input: 0.0,
+      params,
+      width,
+      focused: true,
+      max_chars: None,
+    }
+  }
+
+  /// Returns the current text
+  pub fn value(&self) -> &str {
+    &self.value
+  }
+
+  /// Replaces the current text and moves the caret to the end
+  pub fn set_value(&mut self, value: impl Into<String>) {
+    self.value = value.into();
+    self.caret = self.value.len();
+  }
+
+  /// Returns the byte index of the caret
+  pub fn caret(&self) -> usize {
+    self.caret
+  }
+
+  /// Sets the text an input method (IME) is composing and the byte index of its cursor,
+  /// it's drawn at the caret until it's committed or cleared
+  ///
+  /// **See** [Composition]
+  pub fn set_composition(&mut self, text: impl Into<String>, cursor: usize) {
+    self.composition = Some((text.into(), cursor));
+  }
+
+  /// Removes the composition, call this when the input method commits or cancels
+  pub fn clear_composition(&mut self) {
+    self.composition = None;
+  }
+
+  /// Returns the area of the input on screen
+  pub fn rect(&self, fonts: &Fonts) -> Rect {
+    let (top, bottom) = fonts.line_box(&self.params);
+
+    Rect::new(self.params.x, top, self.width, bottom - top)
+  }
+
+  /// Handles keyboard and mouse input, call this once every frame,
+  /// returns true if the text changed
+  pub fn update(&mut self, fonts: &Fonts) -> bool {
+    let mut changed = false;
+
+    if is_mouse_button_pressed(MouseButton::Left) {
+      let point = vec2(mouse_position().0, mouse_position().1);
+
+      self.focused = self.rect(fonts).contains(point);
+
+      if let Some(index) = fonts
+        .char_index_at(&self.value, &self.params, point)
+        .filter(|_| self.focused)
+      {
+        self.caret = index;
+        self.last_input = get_time();
+      }
+    }
+
+    if !self.focused {
+      // drain the queue so typing while unfocused doesn't show up later
+      while get_char_pressed().is_some() {}
+      return false;
+    }
+
+    while let Some(c) = get_char_pressed() {
+      let full = self
+        .max_chars
+        .is_some_and(|max| self.value.chars().count() >= max);
+
+      if c.is_control() || full {
+        continue;
+      }
+
+      self.value.insert(self.caret, c);
+      self.caret += c.len_utf8();
+      changed = true;
+    }
+
+    if is_key_pressed(KeyCode::Backspace) {
+      if let Some(c) = self.value[..self.caret].chars().next_back() {
+        self.caret -= c.len_utf8();
+        self.value.remove(self.caret);
+        changed = true;
+      }
+    }
+
+    if is_key_pressed(KeyCode::Delete) && self.caret < self.value.len() {
+      self.value.remove(self.caret);
+      changed = true;
+    }
+
+    if is_key_pressed(KeyCode::Left) {
+      if let Some(c) = self.value[..self.caret].chars().next_back() {
+        self.caret -= c.len_utf8();
+      }
+    }
+
+    if is_key_pressed(KeyCode::Right) {
+      if let Some(c) = self.value[self.caret..].chars().next() {
+        self.caret += c.len_utf8();
+      }
+    }
+
+    if is_key_pressed(KeyCode::Home) {
+      self.caret = 0;
+    }
+
+    if is_key_pressed(KeyCode::End) {
+      self.caret = self.value.len();
+    }
+
+    if changed
+      || [KeyCode::Left, KeyCode::Right, KeyCode::Home, KeyCode::End]
+        .into_iter()
+        .any(is_key_pressed)
+    {
+      self.last_input = get_time();
+    }
+
+    changed
+  }
+
+  /// Draws the text and a blinking caret if focused
+  pub fn draw(&self, fonts: &Fonts) {
+    let caret = match &self.composition {
+      Some((text, cursor)) => fonts.draw_text_with_composition(
+        &self.value,
+        &self.params,
+        self.caret,
+        &Composition {
+          text,
+          cursor: *cursor,
+          color: None,
+        },
+      ),
+      None => {
+        fonts.draw_text_ex(&self.value, &self.params);
+        fonts.caret_at(&self.value, &self.params, self.caret)
+      }
+    };
+
+    let blink = (((get_time() - self.last_input) / Self::BLINK) as u64).is_multiple_of(2);
+
+    if self.focused && blink {
+      let thickness = (self.params.size * self.params.vertical_scale() / 16.0).max(1.0);
+
+      draw_line(
+        caret.x,
+        caret.y,
+        caret.x,
+        caret.y + caret.height,
+        thickness,
+        self.params.color,
+      );
+    }
+  }
+}
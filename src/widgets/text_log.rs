@@ -0,0 +1,184 @@
+use std::{collections::VecDeque, ops::Range};
+
+use macroquad::prelude::{mouse_position, mouse_wheel, vec2, Color, Rect};
+
+use crate::{DrawFrom, Fonts, TextParams};
+
+/// A single entry of a [TextLog], split into the lines it wraps to
+#[derive(Debug, Clone)]
+struct Entry {
+  text: String,
+  color: Color,
+  /// Byte ranges of the wrapped lines, empty if it has to be wrapped again
+  lines: Vec<Range<usize>>,
+}
+
+/// A scrollable log of text entries wrapped to a rect, like an in-game console,
+/// only the lines that are visible get drawn
+///
+/// Entries are only wrapped again when they're added or when the width of the rect
+/// or the text params change, so drawing a long history stays cheap
+///
+/// **Example**
+/// ```rs
+/// let mut log = TextLog::new(Rect::new(10.0, 10.0, 400.0, 200.0), TextParams::default());
+///
+/// log.push("Hello");
+/// log.push_colored("Something went wrong", RED);
+///
+/// loop {
+///   log.update();
+///   log.draw(&fonts);
+///
+///   next_frame().await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextLog {
+  entries: VecDeque<Entry>,
+  /// How many lines the log is scrolled up from the bottom
+  scroll: usize,
+  /// The width and params the entries were last wrapped with
+  wrapped_with: Option<(f32, TextParams)>,
+  /// The area the log is drawn in
+  pub rect: Rect,
+  /// How the text is drawn, the position is ignored
+  pub params: TextParams,
+  /// The maximum amount of entries kept, the oldest are removed first,
+  /// `None` means there is no limit
+  pub max_entries: Option<usize>,
+}
+
+impl TextLog {
+  /// Creates a new empty log drawn in `rect`
+  pub fn new(rect: Rect, params: TextParams) -> Self {
+    Self {
+      entries: VecDeque::new(),
+      scroll: 0,
+      wrapped_with: None,
+      rect,
+      params,
+      max_entries: None,
+    }
+  }
+
+  /// Adds an entry with the color of [Self::params]
+  pub fn push(&mut self, text: impl Into<String>) {
+    self.push_colored(text, self.params.color);
+  }
+
+  /// Adds an entry with a given color
+  pub fn push_colored(&mut self, text: impl Into<String>, color: Color) {
+    self.entries.push_back(Entry {
+      text: text.into(),
+      color,
+      lines: Vec::new(),
+    });
+
+    if let Some(max) = self.max_entries {
+      while self.entries.len() > max {
+        self.entries.pop_front();
+      }
+    }
+  }
+
+  /// Removes every entry
+  pub fn clear(&mut self) {
+    self.entries.clear();
+    self.scroll = 0;
+  }
+
+  /// Returns the text of every entry, oldest first
+  pub fn entries(&self) -> impl Iterator<Item = &str> {
+    self.entries.iter().map(|it| it.text.as_str())
+  }
+
+  /// Returns how many lines the log is scrolled up from the bottom
+  pub fn scroll(&self) -> usize {
+    self.scroll
+  }
+
+  /// Scrolls the log up by positive and down by negative amounts of lines,
+  /// clamped when drawing
+  pub fn scroll_by(&mut self, lines: isize) {
+    self.scroll = self.scroll.saturating_add_signed(lines);
+  }
+
+  /// Scrolls to the newest entry
+  pub fn scroll_to_bottom(&mut self) {
+    self.scroll = 0;
+  }
+
+  /// Scrolls with the mouse wheel while the mouse is over the log,
+  /// call this once every frame
+  pub fn update(&mut self) {
+    let (x, y) = mouse_position();
+
+    if !self.rect.contains(vec2(x, y)) {
+      return;
+    }
+
+    let (_, wheel) = mouse_wheel();
+
+    if wheel != 0.0 {
+      self.scroll_by(wheel.signum() as isize * 3);
+    }
+  }
+
+  /// Draws the visible lines, newest at the bottom
+  pub fn draw(&mut self, fonts: &Fonts) {
+    self.wrap(fonts);
+
+    let line_height = fonts.line_height(&self.params);
+    let visible = (self.rect.h / line_height).floor().max(0.0) as usize;
+    let total: usize = self.entries.iter().map(|it| it.lines.len()).sum();
+
+    self.scroll = self.scroll.min(total.saturating_sub(visible));
+
+    let lines = self
+      .entries
+      .iter()
+      .rev()
+      .flat_map(|entry| entry.lines.iter().rev().map(move |line| (entry, line)))
+      .skip(self.scroll)
+      .take(visible);
+
+    for (i, (entry, line)) in lines.enumerate() {
+      fonts.draw_text_ex(
+        &entry.text[line.clone()],
+        &TextParams {
+          x: self.rect.x,
+          y: self.rect.bottom() - (i + 1) as f32 * line_height,
+          color: entry.color,
+          draw: DrawFrom::TopLeft,
+          ..self.params
+        },
+      );
+    }
+  }
+
+  /// Wraps entries that haven't been wrapped yet,
+  /// or every entry if the width or params changed
+  fn wrap(&mut self, fonts: &Fonts) {
+    let key = (self.rect.w, self.params);
+
+    if self.wrapped_with != Some(key) {
+      self.entries.iter_mut().for_each(|it| it.lines.clear());
+      self.wrapped_with = Some(key);
+    }
+
+    for entry in self.entries.iter_mut().filter(|it| it.lines.is_empty()) {
+      let params = TextParams {
+        x: 0.0,
+        ..self.params
+      };
+
+      entry.lines = fonts
+        .measure_multiline(&entry.text, &params, Some(self.rect.w))
+        .lines
+        .into_iter()
+        .map(|it| it.range)
+        .collect();
+    }
+  }
+}
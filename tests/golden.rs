@@ -0,0 +1,100 @@
+//! Compares rendered text against the reference PNGs in tests/golden,
+//! run with `UPDATE_GOLDEN=1` to write them again after an intended change
+
+use macroquad::prelude::{Color, Image, Rect, BLUE, WHITE};
+
+use macroquad_text::{assert_golden, AtlasConfig, FontStyle, FontWeight, Fonts, TextParams};
+
+const NOTO_SANS: &[u8] = include_bytes!("../assets/fonts/NotoSans-Regular.ttf");
+const NOTO_SANS_JP: &[u8] = include_bytes!("../assets/fonts/NotoSansJP-Regular.otf");
+
+// rasterization can round slightly differently between platforms
+const TOLERANCE: u8 = 8;
+
+fn fonts() -> Fonts<'static> {
+  let mut fonts = Fonts::default();
+
+  fonts.set_atlas_config(AtlasConfig {
+    initial_size: 512,
+    ..Default::default()
+  });
+  fonts.load_font_from_bytes("Noto Sans", NOTO_SANS).unwrap();
+  fonts
+    .load_font_from_bytes("Noto Sans JP", NOTO_SANS_JP)
+    .unwrap();
+  fonts
+}
+
+fn render(text: &str, params: &TextParams, width: u16, height: u16) -> Image {
+  fonts().render_to_image(text, params, width, height)
+}
+
+fn golden(name: &str) -> String {
+  format!("{}/tests/golden/{name}.png", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn latin() {
+  let params = TextParams {
+    size: 32.0,
+    color: WHITE,
+    ..Default::default()
+  };
+
+  assert_golden(&render("Nice", &params, 80, 48), golden("latin"), TOLERANCE);
+}
+
+#[test]
+fn fallback() {
+  let params = TextParams {
+    size: 32.0,
+    color: WHITE,
+    ..Default::default()
+  };
+
+  let image = render("Nice 良い", &params, 144, 48);
+
+  // the japanese glyphs come from the second font
+  assert!((80..144).any(|x| (0..48).any(|y| image.get_pixel(x, y).a > 0.0)));
+
+  assert_golden(&image, golden("fallback"), TOLERANCE);
+}
+
+#[test]
+fn synthesized_bold_italic() {
+  let params = TextParams {
+    size: 32.0,
+    color: WHITE,
+    weight: FontWeight::Bold,
+    style: FontStyle::Italic,
+    ..Default::default()
+  };
+
+  assert_golden(
+    &render("Bold", &params, 96, 48),
+    golden("bold_italic"),
+    TOLERANCE,
+  );
+}
+
+#[test]
+fn gradient_and_clip() {
+  let params = TextParams {
+    size: 32.0,
+    color: WHITE,
+    bottom_color: Some(BLUE),
+    clip: Some(Rect::new(0.0, 0.0, 40.0, 48.0)),
+    ..Default::default()
+  };
+
+  let image = render("Clipped", &params, 112, 48);
+
+  // nothing is drawn right of the clip rect
+  for y in 0..image.height as u32 {
+    for x in 40..image.width as u32 {
+      assert_eq!(image.get_pixel(x, y), Color::new(0.0, 0.0, 0.0, 0.0));
+    }
+  }
+
+  assert_golden(&image, golden("gradient_and_clip"), TOLERANCE);
+}